@@ -2,7 +2,7 @@
 //
 //! Rendering Base
 
-use crate::{Color, Pixel, PixelSource};
+use crate::{Color, LcdPixel, Pixel, PixelSource};
 use core::cmp::{max, min};
 use devela::iif;
 
@@ -63,6 +63,42 @@ impl<T: Pixel> RenderingBase<T> {
         self.pixf.blend_hline(x1, y, x2 - x1 + 1, c, cover);
     }
 
+    /// Blends a color vertically from `y1` to `y2` at column `x` with the specified coverage.
+    ///
+    /// Ensures the blending stays within the image boundaries.
+    pub fn blend_vline<C: Color>(&mut self, x: i64, y1: i64, y2: i64, c: C, cover: u64) {
+        let (xmin, xmax, ymin, ymax) = self.limits();
+        let (y1, y2) = if y2 > y1 { (y1, y2) } else { (y2, y1) };
+        iif![x > xmax || x < xmin || y1 > ymax || y2 < ymin; return];
+        let y1 = max(y1, ymin);
+        let y2 = min(y2, ymax);
+        self.pixf.blend_vline(x, y1, y2 - y1 + 1, c, cover);
+    }
+
+    /// Blends a color over the rectangle from `(x1, y1)` to `(x2, y2)`, both
+    /// included, with the specified coverage.
+    ///
+    /// Ensures the blending stays within the image boundaries.
+    pub fn blend_bar<C: Color>(&mut self, x1: i64, y1: i64, x2: i64, y2: i64, c: C, cover: u64) {
+        let (xmin, xmax, ymin, ymax) = self.limits();
+        let (x1, x2) = if x2 > x1 { (x1, x2) } else { (x2, x1) };
+        let (y1, y2) = if y2 > y1 { (y1, y2) } else { (y2, y1) };
+        iif![x1 > xmax || x2 < xmin || y1 > ymax || y2 < ymin; return];
+        let (x1, x2) = (max(x1, xmin), min(x2, xmax));
+        for y in max(y1, ymin)..=min(y2, ymax) {
+            self.pixf.blend_hline(x1, y, x2 - x1 + 1, c, cover);
+        }
+    }
+
+    /// Blends a color on the pixel at `(x, y)` with the specified coverage.
+    ///
+    /// Pixels outside of the image boundaries are ignored.
+    pub fn blend_pixel<C: Color>(&mut self, x: i64, y: i64, c: C, cover: u64) {
+        let (xmin, xmax, ymin, ymax) = self.limits();
+        iif![x > xmax || x < xmin || y > ymax || y < ymin; return];
+        self.pixf.copy_or_blend_pix_with_cover((x as usize, y as usize), c, cover);
+    }
+
     /// Blends a horizontal span of pixels from `(x, y)` with individual coverage values.
     ///
     /// Coverage values in `covers` are applied to each pixel in the span.
@@ -197,3 +233,17 @@ impl<T: Pixel> RenderingBase<T> {
         }
     }
 }
+
+impl<T: LcdPixel> RenderingBase<T> {
+    /// Blends a horizontal span of pixels from `(x, y)` with the red, green
+    /// and blue `covers` of each pixel, for LCD subpixel rendering.
+    pub fn blend_lcd_hspan<C: Color>(&mut self, x: i64, y: i64, c: C, covers: &[[u64; 3]]) {
+        let (xmin, xmax, ymin, ymax) = self.limits();
+        iif![y > ymax || y < ymin || c.is_transparent(); return];
+        let first = max(x, xmin);
+        let last = min(x + covers.len() as i64 - 1, xmax);
+        for px in first..=last {
+            self.pixf.blend_pix_lcd((px as usize, y as usize), c, covers[(px - x) as usize]);
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! Rendering Cells
 
 use crate::util::*;
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
 use core::cmp::{max, min};
 
 /// Rendering Cell
@@ -55,11 +55,44 @@ impl Cell {
     //}
 }
 
+/// The number of cells allocated first and the unit of the cell limit,
+/// like AGG's `cell_block_size`.
+pub const CELL_BLOCK_SIZE: usize = 4096;
+
+/// The default maximum number of cells, like AGG's `cell_block_limit` of 1024 blocks.
+pub const CELL_LIMIT: usize = 1024 * CELL_BLOCK_SIZE;
+
+/// What a rasterizer does with a path once it reaches its cell limit.
+///
+/// See [`RasterizerScanline::set_cell_limit`][crate::RasterizerScanline::set_cell_limit].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CellOverflow {
+    /// Keeps the cells up to the limit and drops the rest,
+    /// so only part of the path is rendered, like AGG does (default).
+    #[default]
+    Truncate,
+    /// Drops all the cells, so nothing is rendered until the next reset.
+    Discard,
+}
+
 /// Collection of Cells
-#[derive(Debug, Default)]
+///
+/// The cells start with a block of [`CELL_BLOCK_SIZE`] and double their
+/// capacity up to a limit, and the allocation is reused across resets.
+#[derive(Debug)]
 pub(crate) struct RasterizerCell {
-    /// Cells
+    /// Cells, sorted by y position, then x position, once `sorted` is set
     cells: Vec<Cell>,
+    /// Whether the cells are sorted
+    sorted: bool,
+    /// Maximum number of cells, a multiple of `CELL_BLOCK_SIZE`
+    limit: usize,
+    /// What to do once the limit is reached
+    overflow: CellOverflow,
+    /// Whether the limit has been reached, the last cell is then a scratch one
+    limit_reached: bool,
+    /// Rows of the lines to keep, from the first included to the last excluded
+    band: (i64, i64),
     /// Minimum x value of current cells
     pub min_x: i64,
     /// Maximum x value of current cells
@@ -68,64 +101,108 @@ pub(crate) struct RasterizerCell {
     pub min_y: i64,
     /// Maximum y value of current cells
     pub max_y: i64,
-    /// Cells sorted by y position, then x position
-    pub sorted_y: Vec<Vec<Cell>>,
+}
+
+impl Default for RasterizerCell {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RasterizerCell {
     /// Create new Cell collection
     pub fn new() -> Self {
         Self {
-            cells: Vec::with_capacity(256),
+            cells: Vec::new(),
+            sorted: false,
+            limit: CELL_LIMIT,
+            overflow: CellOverflow::Truncate,
+            limit_reached: false,
+            band: (i64::MIN, i64::MAX),
             min_x: i64::MAX,
             min_y: i64::MAX,
             max_x: i64::MIN,
             max_y: i64::MIN,
-            sorted_y: vec![],
         }
     }
-    /// Clear cells
+    /// Clear cells, keeping their allocation
     pub fn reset(&mut self) {
         self.max_x = i64::MIN;
         self.max_y = i64::MIN;
         self.min_x = i64::MAX;
         self.min_y = i64::MAX;
-        self.sorted_y.clear(); // Not sure if this should be cleared
-        self.cells.clear(); // Not sure if this should be cleared
+        self.cells.clear();
+        self.sorted = false;
+        self.limit_reached = false;
+    }
+
+    /// Sets the maximum number of cells, rounded up to whole blocks
+    ///
+    /// Frees the allocated cells beyond the new limit that aren't in use.
+    pub fn set_limit(&mut self, max_cells: usize) {
+        self.limit = max_cells.div_ceil(CELL_BLOCK_SIZE).max(1) * CELL_BLOCK_SIZE;
+        if self.cells.capacity() > self.limit {
+            self.cells.shrink_to(self.limit.max(self.cells.len()));
+        }
     }
+    /// Returns the maximum number of cells
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn limit(&self) -> usize { self.limit }
+
+    /// Sets what to do once the limit is reached
+    #[inline] #[rustfmt::skip]
+    pub fn set_overflow(&mut self, overflow: CellOverflow) { self.overflow = overflow; }
+
+    /// Returns whether the limit has been reached since the last reset
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn limit_reached(&self) -> bool { self.limit_reached }
+
+    /// Sets the rows of the lines to keep, from `y1` included to `y2` excluded
+    ///
+    /// The lines outside of them are skipped, and the rest are kept whole,
+    /// so the cells in those rows are the same as without a band.
+    #[inline] #[rustfmt::skip]
+    #[cfg(feature = "std")]
+    pub fn set_band(&mut self, y1: i64, y2: i64) { self.band = (y1, y2); }
+
+    /// Returns the bytes allocated for the cells
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn memory_usage(&self) -> usize { self.cells.capacity() * size_of::<Cell>() }
 
     /// Return total number of cells
     pub fn total_cells(&self) -> usize {
         self.cells.len()
     }
 
-    /// Sort cells into sorted_y cells
+    /// Returns whether the cells are sorted
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn is_sorted(&self) -> bool { self.sorted }
+
+    /// Marks the cells to be sorted again, after adding more
+    #[inline] #[rustfmt::skip]
+    pub fn unsort(&mut self) { self.sorted = false; }
+
+    /// Sort cells by y position, then x position
     ///
-    /// Cells are distributed into y bins, then sorted by x value
+    /// The cells are sorted in place, without allocating.
     pub fn sort_cells(&mut self) {
-        if !self.sorted_y.is_empty() || self.max_y < 0 {
+        if self.sorted {
             return;
         }
-        // Distribute into
-        self.sorted_y = vec![Vec::with_capacity(8); (self.max_y + 1) as usize];
-        for c in self.cells.iter() {
-            if c.y >= 0 {
-                let y = c.y as usize;
-                self.sorted_y[y].push(*c);
+        self.sorted = true;
+        if self.limit_reached {
+            match self.overflow {
+                CellOverflow::Truncate => self.cells.truncate(self.limit - 1),
+                CellOverflow::Discard => self.cells.clear(),
             }
         }
-        // Sort by the x value
-        for i in 0..self.sorted_y.len() {
-            self.sorted_y[i].sort_by(|a, b| (a.x).cmp(&b.x));
-        }
-    }
-    /// Return number of cells in a specific y row
-    pub fn scanline_num_cells(&self, y: i64) -> usize {
-        self.sorted_y[y as usize].len()
+        self.cells.sort_unstable_by_key(|c| (c.y, c.x));
     }
     /// Returns the cells of a specific y row
     pub fn scanline_cells(&self, y: i64) -> &[Cell] {
-        &self.sorted_y[y as usize]
+        let start = self.cells.partition_point(|c| c.y < y);
+        let end = start + self.cells[start..].partition_point(|c| c.y == y);
+        &self.cells[start..end]
     }
 
     //pub fn add_curr_cell(&mut self, new_cell: Cell) {
@@ -170,10 +247,23 @@ impl RasterizerCell {
     ///
     /// Current cell is removed if empty (cover and area equal to 0)
     /// New cell is added to cell list
+    ///
+    /// Once the limit is reached the last cell is reused as a scratch cell,
+    /// to be dropped when sorting.
     fn set_curr_cell(&mut self, x: i64, y: i64) {
         if self.curr_cell_not_equal(x, y) {
             self.pop_last_cell_if_empty();
-            self.cells.push(Cell::at(x, y));
+            if self.cells.len() < self.limit {
+                if self.cells.len() == self.cells.capacity() {
+                    // Doubles the capacity, without going over the limit
+                    let len = self.cells.len();
+                    self.cells.reserve_exact(len.max(CELL_BLOCK_SIZE).min(self.limit - len));
+                }
+                self.cells.push(Cell::at(x, y));
+            } else {
+                self.limit_reached = true;
+                *self.cells.last_mut().unwrap() = Cell::at(x, y);
+            }
         }
     }
 
@@ -260,6 +350,14 @@ impl RasterizerCell {
     ///
     /// Input coordinates are at subpixel scale
     pub fn line(&mut self, x1: i64, y1: i64, x2: i64, y2: i64) {
+        if self.limit_reached && self.overflow == CellOverflow::Discard {
+            return;
+        }
+        if max(y1, y2) >> POLY_SUBPIXEL_SHIFT < self.band.0
+            || min(y1, y2) >> POLY_SUBPIXEL_SHIFT >= self.band.1
+        {
+            return;
+        }
         let dx_limit = 16384 << POLY_SUBPIXEL_SHIFT;
         let dx = x2 - x1;
         // Split long lines in half
@@ -268,6 +366,7 @@ impl RasterizerCell {
             let cy = (y1 + y2) / 2;
             self.line(x1, y1, cx, cy);
             self.line(cx, cy, x2, y2);
+            return;
         }
         let dy = y2 - y1;
         // Downshift
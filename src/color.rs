@@ -180,15 +180,15 @@ impl Rgb8 {
     #[must_use]
     pub fn from_wavelength_gamma(w: f64, gamma: f64) -> Self {
         let (r, g, b) = if (380.0..=440.0).contains(&w) {
-            (-1.0 * (w - 440.0) / (440.0 - 380.0), 0.0, 1.0)
+            (-(w - 440.0) / (440.0 - 380.0), 0.0, 1.0)
         } else if (440.0..=490.0).contains(&w) {
             (0.0, (w - 440.0) / (490.0 - 440.0), 1.0)
         } else if (490.0..=510.0).contains(&w) {
-            (0.0, 1.0, -1.0 * (w - 510.0) / (510.0 - 490.0))
+            (0.0, 1.0, -(w - 510.0) / (510.0 - 490.0))
         } else if (510.0..=580.0).contains(&w) {
             ((w - 510.0) / (580.0 - 510.0), 1.0, 0.0)
         } else if (580.0..=645.0).contains(&w) {
-            (1.0, -1.0 * (w - 645.0) / (645.0 - 580.0), 0.0)
+            (1.0, -(w - 645.0) / (645.0 - 580.0), 0.0)
         } else if (645.0..=780.0).contains(&w) {
             (1.0, 0.0, 0.0)
         } else {
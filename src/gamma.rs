@@ -0,0 +1,266 @@
+// agrega::gamma
+//
+//! Gamma functions and lookup tables.
+//!
+//! The gamma functions shape the coverage of the antialiased pixels in
+//! [`RasterizerScanline::set_gamma`][crate::RasterizerScanline::set_gamma], and a
+//! [`GammaLut`] corrects whole pixel buffers with
+//! [`apply_gamma_dir`][crate::Pixfmt::apply_gamma_dir] and
+//! [`apply_gamma_inv`][crate::Pixfmt::apply_gamma_inv].
+//!
+//! # Examples
+//! ```
+//! use agrega::{Gamma, GammaLinear, GammaLut, GammaPower, RasterizerScanline};
+//!
+//! let mut ras = RasterizerScanline::new();
+//! ras.set_gamma(&GammaLinear::new(0.2, 0.8));
+//!
+//! let power = GammaPower::new(2.2);
+//! let lut = GammaLut::new(power.gamma);
+//! assert_eq!(lut.dir(128), (power.apply(128.0 / 255.0) * 255.0).round() as u8);
+//! ras.set_gamma(&lut);
+//! ```
+//
+// TOC
+// - trait Gamma
+// - struct GammaNone
+// - struct GammaPower
+// - struct GammaThreshold
+// - struct GammaLinear
+// - struct GammaMultiply
+// - struct GammaLut
+
+#[allow(unused_imports)]
+use devela::{iif, ExtFloat};
+
+/// A gamma function, mapping a value in `[0, 1]` to another one in `[0, 1]`.
+///
+/// It's implemented by the named gamma types and by any `Fn(f64) -> f64` closure.
+pub trait Gamma {
+    /// Returns the gamma corrected `x`.
+    #[must_use]
+    fn apply(&self, x: f64) -> f64;
+}
+
+impl<F: Fn(f64) -> f64> Gamma for F {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        self(x)
+    }
+}
+
+/// The identity gamma function.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GammaNone;
+
+impl Gamma for GammaNone {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        x
+    }
+}
+
+/// The power gamma function, `x` raised to `gamma`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaPower {
+    /// The exponent.
+    pub gamma: f64,
+}
+
+impl Default for GammaPower {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl GammaPower {
+    /// Creates a new power gamma function.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn new(gamma: f64) -> Self { Self { gamma } }
+}
+
+impl Gamma for GammaPower {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        x.powf(self.gamma)
+    }
+}
+
+/// The threshold gamma function, 0 below `threshold` and 1 from it.
+///
+/// It turns off the antialiasing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaThreshold {
+    /// The value where the output goes from 0 to 1.
+    pub threshold: f64,
+}
+
+impl Default for GammaThreshold {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl GammaThreshold {
+    /// Creates a new threshold gamma function.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn new(threshold: f64) -> Self { Self { threshold } }
+}
+
+impl Gamma for GammaThreshold {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        iif![x < self.threshold; 0.0; 1.0]
+    }
+}
+
+/// The linear gamma function, 0 below `start`, 1 above `end`, and linear between them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaLinear {
+    /// The value where the output starts to grow from 0.
+    pub start: f64,
+    /// The value where the output reaches 1.
+    pub end: f64,
+}
+
+impl Default for GammaLinear {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl GammaLinear {
+    /// Creates a new linear gamma function.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn new(start: f64, end: f64) -> Self { Self { start, end } }
+}
+
+impl Gamma for GammaLinear {
+    fn apply(&self, x: f64) -> f64 {
+        if x < self.start {
+            0.0
+        } else if x > self.end {
+            1.0
+        } else {
+            (x - self.start) / (self.end - self.start)
+        }
+    }
+}
+
+/// The multiply gamma function, `x` multiplied by `multiplier` up to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GammaMultiply {
+    /// The factor.
+    pub multiplier: f64,
+}
+
+impl Default for GammaMultiply {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl GammaMultiply {
+    /// Creates a new multiply gamma function.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn new(multiplier: f64) -> Self { Self { multiplier } }
+}
+
+impl Gamma for GammaMultiply {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        (x * self.multiplier).min(1.0)
+    }
+}
+
+/// Gamma lookup tables for 8-bit color components.
+///
+/// Holds the direct power curve and its inverse, like AGG's `gamma_lut`,
+/// to convert pixel buffers between linear and gamma corrected values.
+/// As a [`Gamma`] function it applies the direct curve.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GammaLut {
+    gamma: f64,
+    dir: [u8; 256],
+    inv: [u8; 256],
+}
+
+impl Default for GammaLut {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl GammaLut {
+    /// Creates the lookup tables for the power `gamma`.
+    #[must_use]
+    pub fn new(gamma: f64) -> Self {
+        let (mut dir, mut inv) = ([0; 256], [0; 256]);
+        for i in 0..256 {
+            let v = i as f64 / 255.0;
+            dir[i] = (v.powf(gamma) * 255.0).round() as u8;
+            inv[i] = (v.powf(1.0 / gamma) * 255.0).round() as u8;
+        }
+        Self { gamma, dir, inv }
+    }
+
+    /// Returns the power of the direct curve.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn gamma(&self) -> f64 { self.gamma }
+
+    /// Returns the gamma corrected `v`.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn dir(&self, v: u8) -> u8 { self.dir[v as usize] }
+
+    /// Returns the linear value of the gamma corrected `v`.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn inv(&self, v: u8) -> u8 { self.inv[v as usize] }
+
+    /// Returns the direct table.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn dir_table(&self) -> &[u8; 256] { &self.dir }
+
+    /// Returns the inverse table.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn inv_table(&self) -> &[u8; 256] { &self.inv }
+}
+
+impl Gamma for GammaLut {
+    #[inline]
+    fn apply(&self, x: f64) -> f64 {
+        f64::from(self.dir[(x.clamp(0.0, 1.0) * 255.0).round() as usize]) / 255.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamma_functions() {
+        assert_eq!(GammaNone.apply(0.3), 0.3);
+        assert_eq!(GammaPower::new(2.0).apply(0.5), 0.25);
+        assert_eq!(GammaThreshold::new(0.5).apply(0.49), 0.0);
+        assert_eq!(GammaThreshold::new(0.5).apply(0.5), 1.0);
+        let linear = GammaLinear::new(0.25, 0.75);
+        assert_eq!([linear.apply(0.1), linear.apply(0.5), linear.apply(0.9)], [0.0, 0.5, 1.0]);
+        assert_eq!(GammaMultiply::new(2.0).apply(0.25), 0.5);
+        assert_eq!(GammaMultiply::new(2.0).apply(0.75), 1.0);
+        assert_eq!((|x: f64| 1.0 - x).apply(0.25), 0.75);
+    }
+
+    #[test]
+    fn gamma_lut() {
+        let identity = GammaLut::default();
+        assert!((0..=255).all(|v| identity.dir(v) == v && identity.inv(v) == v));
+
+        let lut = GammaLut::new(2.2);
+        assert_eq!(lut.gamma(), 2.2);
+        assert_eq!((lut.dir(0), lut.dir(255), lut.inv(0), lut.inv(255)), (0, 255, 0, 255));
+        assert!(lut.dir(128) < 128 && lut.inv(128) > 128);
+        for v in [16, 64, 128, 200] {
+            assert!(lut.inv(lut.dir(v)).abs_diff(v) <= 8);
+        }
+        assert_eq!(lut.apply(128.0 / 255.0), f64::from(lut.dir(128)) / 255.0);
+    }
+}
@@ -803,6 +803,7 @@ impl AA3 {
                 npix += 1;
             }
             dy += 1;
+            dist = self.li.dist[dy] + s1;
         }
         ren.blend_solid_vspan(
             self.li.x,
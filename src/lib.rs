@@ -51,6 +51,7 @@ items! {
 items! {
     mod alphamask;
     mod base;
+    pub mod gamma;
     pub mod interp;
     pub mod outline;
     pub mod pixfmt;
@@ -58,7 +59,7 @@ items! {
 
     #[doc(hidden)]
     #[allow(unused_imports)]
-    pub use {alphamask::*, base::*, interp::*, outline::*,  pixfmt::*, text::*};
+    pub use {alphamask::*, base::*, gamma::*, interp::*, outline::*,  pixfmt::*, text::*};
 }
 
 /// All items are flat re-exported here.<br/><hr>
@@ -68,7 +69,7 @@ pub mod all {
 
     #[doc(inline)]
     #[cfg(feature = "alloc")]
-    pub use super::{paths::*, scanline::*};
+    pub use super::{cell::*, paths::*, scanline::*};
 
     #[doc(inline)]
     #[cfg(any(feature = "std", feature = "no_std"))]
@@ -77,7 +78,7 @@ pub mod all {
     #[doc(inline)]
     #[allow(unused_imports)]
     #[cfg(any(feature = "std", all(feature = "no_std", feature = "alloc")))]
-    pub use super::{alphamask::*, base::*, interp::*, outline::*, pixfmt::*, text::*};
+    pub use super::{alphamask::*, base::*, gamma::*, interp::*, outline::*, pixfmt::*, text::*};
 }
 /// Library dependencies.<br/><hr>
 pub mod _dep {
@@ -5,8 +5,9 @@ use crate::{
     POLY_SUBPIXEL_SCALE,
 };
 use alloc::{vec, vec::Vec};
+use core::mem;
 #[allow(unused_imports)]
-use devela::ExtFloat;
+use devela::{iif, ExtFloat};
 
 /// Outline Rasterizer with Anti-Aliasing
 pub struct RasterizerOutlineAA<'a, T: DrawOutline> {
@@ -16,6 +17,8 @@ pub struct RasterizerOutlineAA<'a, T: DrawOutline> {
     vertices: Vec<Vertex<i64>>,
     round_cap: bool,
     line_join: LineJoin,
+    dashes: Vec<f64>,
+    dash_start: f64,
 }
 
 impl<'a, T: DrawOutline> RasterizerOutlineAA<'a, T> {
@@ -26,20 +29,77 @@ impl<'a, T: DrawOutline> RasterizerOutlineAA<'a, T> {
         } else {
             LineJoin::Round
         };
-        Self { ren, start_x: 0, start_y: 0, vertices: vec![], round_cap: false, line_join }
+        Self {
+            ren,
+            start_x: 0,
+            start_y: 0,
+            vertices: vec![],
+            round_cap: false,
+            line_join,
+            dashes: vec![],
+            dash_start: 0.0,
+        }
     }
     /// Set Rounded End Caps
     pub fn round_cap(&mut self, on: bool) {
         self.round_cap = on;
     }
+    /// Set the Line Join
+    ///
+    /// Accepts [`LineJoin::None`], [`LineJoin::Miter`], [`LineJoin::Round`]
+    /// and [`LineJoin::MiterAccurate`]. [`LineJoin::Bevel`] is drawn as
+    /// `None`, and the other miter variants as `Miter`.
+    ///
+    /// Renderers that only support accurate joins always use
+    /// [`LineJoin::MiterAccurate`].
+    pub fn line_join(&mut self, join: LineJoin) {
+        self.line_join = if self.ren.accurate_join_only() {
+            LineJoin::MiterAccurate
+        } else {
+            match join {
+                LineJoin::None | LineJoin::Bevel => LineJoin::None,
+                LineJoin::Miter | LineJoin::MiterRevert | LineJoin::MiterRound => LineJoin::Miter,
+                LineJoin::Round | LineJoin::MiterAccurate => join,
+            }
+        };
+    }
+    /// Add a dash of `length` followed by a `gap` to the dash pattern, in pixels
+    ///
+    /// Non-positive lengths or gaps are ignored. Without dashes the lines are solid.
+    pub fn add_dash(&mut self, length: f64, gap: f64) {
+        if length <= 0.0 || gap <= 0.0 {
+            return;
+        }
+        self.dashes.push(length);
+        self.dashes.push(gap);
+    }
+    /// Remove all dashes, drawing solid lines
+    pub fn remove_all_dashes(&mut self) {
+        self.dashes.clear();
+    }
+    /// Set the offset into the dash pattern at the start of each path, in pixels
+    pub fn dash_start(&mut self, start: f64) {
+        self.dash_start = start;
+    }
     /// Add and Render a path
+    ///
+    /// Each sub-path is rendered when the next one starts, and closed
+    /// sub-paths are rendered as polygons, joined at their first vertex.
     pub fn add_path<VS: VertexSource>(&mut self, path: &VS) {
-        for v in path.xconvert().iter() {
+        for v in path.vertices() {
             match v.cmd {
-                PathCommand::MoveTo => self.move_to_d(v.x, v.y),
-                PathCommand::LineTo => self.line_to_d(v.x, v.y),
-                PathCommand::Close => self.close_path(),
-                PathCommand::Stop => unimplemented!("stop encountered"),
+                PathCommand::MoveTo => {
+                    self.render(false);
+                    self.move_to_d(v.x, v.y);
+                }
+                PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4 => {
+                    self.line_to_d(v.x, v.y)
+                }
+                PathCommand::Close => {
+                    self.render(true);
+                    self.move_to(self.start_x, self.start_y);
+                }
+                PathCommand::Stop => break,
             }
         }
         self.render(false);
@@ -203,7 +263,7 @@ impl<'a, T: DrawOutline> RasterizerOutlineAA<'a, T> {
                     x2 + (y2 - y1),
                     y2 - (x2 - x1),
                     dv.curr.x1 + (dv.curr.y2 - dv.curr.y1),
-                    dv.curr.y1 + (dv.curr.x2 - dv.curr.x1),
+                    dv.curr.y1 - (dv.curr.x2 - dv.curr.x1),
                 );
             } else {
                 let (xb1, yb1) = Self::bisectrix(&prev, &dv.curr);
@@ -261,18 +321,169 @@ impl<'a, T: DrawOutline> RasterizerOutlineAA<'a, T> {
     ///  [`line_to_d`](#method.line_to_d).  Paths drawn with [`add_path`](#method.add_path)
     ///  are automatically rendered
     ///
+    /// If `close_polygon` is true, the last vertex is joined back to the first
+    /// one, and the path needs at least 3 distinct vertices to be drawn.
     pub fn render(&mut self, close_polygon: bool) {
         if close_polygon {
-            unimplemented!("no closed polygons yet");
+            while self.vertices.len() > 1 {
+                let (first, last) = (self.vertices[0], self.vertices[self.vertices.len() - 1]);
+                if len_i64(&last, &first) >= POLY_SUBPIXEL_SCALE + POLY_SUBPIXEL_SCALE / 2 {
+                    break;
+                }
+                self.vertices.pop();
+            }
+        }
+        if self.dashes.is_empty() || self.vertices.len() < 2 {
+            self.render_solid(close_polygon);
+        } else {
+            self.render_dashed(close_polygon);
+        }
+        self.vertices.clear();
+    }
+    fn render_solid(&mut self, close_polygon: bool) {
+        if close_polygon {
+            if self.vertices.len() >= 3 {
+                self.draw_polygon();
+            }
         } else {
             match self.vertices.len() {
-                0 | 1 => return,
+                0 | 1 => (),
                 2 => self.draw_two_points(),
                 3 => self.draw_three_points(),
                 _ => self.draw_many_points(),
             }
         }
-        self.vertices.clear();
+    }
+    /// Renders each dash of the current path as an open polyline.
+    ///
+    /// The pattern continues across the vertices, and on closed polygons
+    /// a dash crossing the first vertex is drawn as a single polyline.
+    fn render_dashed(&mut self, close_polygon: bool) {
+        let src = mem::take(&mut self.vertices);
+        let (start_x, start_y) = (self.start_x, self.start_y);
+        match self.dash_pieces(&src, close_polygon) {
+            None => {
+                self.vertices = src;
+                self.render_solid(close_polygon);
+            }
+            Some(pieces) => {
+                for (gap, piece) in pieces {
+                    self.ren.skip(gap);
+                    self.move_to(piece[0].x, piece[0].y);
+                    for w in piece.windows(2).filter(|w| w[0].x != w[1].x || w[0].y != w[1].y) {
+                        self.line_to(w[1].x, w[1].y);
+                    }
+                    self.render_solid(false);
+                    self.vertices.clear();
+                }
+            }
+        }
+        (self.start_x, self.start_y) = (start_x, start_y);
+    }
+    /// Splits the `src` polyline into dashes, each one with the length of the
+    /// gap before it, in subpixels.
+    ///
+    /// Returns `None` if the whole path falls within a single dash.
+    #[allow(clippy::type_complexity)]
+    fn dash_pieces(
+        &self,
+        src: &[Vertex<i64>],
+        closed: bool,
+    ) -> Option<Vec<(i64, Vec<Vertex<i64>>)>> {
+        let scale = POLY_SUBPIXEL_SCALE as f64;
+        let total: f64 = self.dashes.iter().sum();
+        let (mut curr, mut curr_start) = (0, self.dash_start.rem_euclid(total));
+        while curr_start >= self.dashes[curr] {
+            curr_start -= self.dashes[curr];
+            curr = (curr + 1) % self.dashes.len();
+        }
+        let first_on = curr % 2 == 0;
+        let (mut pieces, mut piece, mut gap) = (vec![], vec![], 0.0);
+        iif![first_on; piece.push(src[0])];
+
+        let n = src.len();
+        for i in 0..iif![closed; n; n - 1] {
+            let (v1, v2) = (src[i], src[(i + 1) % n]);
+            let (dx, dy) = ((v2.x - v1.x) as f64, (v2.y - v1.y) as f64);
+            let len = (dx * dx + dy * dy).sqrt();
+            let mut pos = 0.0;
+            loop {
+                let rest = (self.dashes[curr] - curr_start) * scale;
+                if len - pos <= rest {
+                    curr_start += (len - pos) / scale;
+                    iif![curr % 2 == 0; piece.push(v2); gap += len - pos];
+                    break;
+                }
+                pos += rest;
+                let t = pos / len;
+                let v =
+                    Vertex::line_to(v1.x + (dx * t).round() as i64, v1.y + (dy * t).round() as i64);
+                piece.push(v);
+                if curr % 2 == 0 {
+                    pieces.push((gap.round() as i64, mem::take(&mut piece)));
+                    gap = 0.0;
+                } else {
+                    gap += rest;
+                }
+                curr = (curr + 1) % self.dashes.len();
+                curr_start = 0.0;
+            }
+        }
+        if piece.is_empty() {
+            return Some(pieces);
+        }
+        if pieces.is_empty() && first_on {
+            // no gap along the whole path
+            return None;
+        }
+        if closed && first_on {
+            // the last dash continues into the first one
+            let (_, first) = pieces.remove(0);
+            if let Some(next) = pieces.first_mut() {
+                next.0 += first.windows(2).map(|w| len_i64(&w[0], &w[1])).sum::<i64>();
+            }
+            piece.extend_from_slice(&first[1..]);
+        }
+        pieces.push((gap.round() as i64, piece));
+        Some(pieces)
+    }
+    fn draw_polygon(&mut self) {
+        let n = self.vertices.len();
+        debug_assert!(n >= 3);
+        let (v0, v1, v2) = (self.vertices[0], self.vertices[1], self.vertices[2]);
+        let vlast = self.vertices[n - 1];
+
+        let mut dv = DrawVars { idx: 2, ..Default::default() };
+        let prev = LineParameters::new(vlast.x, vlast.y, v0.x, v0.y, len_i64(&vlast, &v0));
+        dv.lcurr = len_i64(&v0, &v1);
+        dv.lnext = len_i64(&v1, &v2);
+        dv.x1 = v1.x;
+        dv.y1 = v1.y;
+        dv.curr = LineParameters::new(v0.x, v0.y, dv.x1, dv.y1, dv.lcurr);
+        dv.x2 = v2.x;
+        dv.y2 = v2.y;
+        dv.next = LineParameters::new(dv.x1, dv.y1, dv.x2, dv.y2, dv.lnext);
+        dv.flags = match self.line_join {
+            LineJoin::MiterAccurate => 0,
+            LineJoin::Miter | LineJoin::Round => {
+                let mut v = 0;
+                if prev.diagonal_quadrant() == dv.curr.diagonal_quadrant() {
+                    v |= 1;
+                }
+                if dv.curr.diagonal_quadrant() == dv.next.diagonal_quadrant() {
+                    v |= 2;
+                }
+                v
+            }
+            _ => 3,
+        };
+        if (dv.flags & 1) == 0 && self.line_join != LineJoin::Round {
+            (dv.xb1, dv.yb1) = Self::bisectrix(&prev, &dv.curr);
+        }
+        if (dv.flags & 2) == 0 && self.line_join != LineJoin::Round {
+            (dv.xb2, dv.yb2) = Self::bisectrix(&dv.curr, &dv.next);
+        }
+        self.draw(&mut dv, 0, n);
     }
     fn draw(&mut self, dv: &mut DrawVars, start: usize, end: usize) {
         for _i in start..end {
@@ -2,8 +2,9 @@
 
 use crate::{
     clip_line_segment, len_i64_xy, Color, DistanceInterpolator0, DistanceInterpolator00,
-    DrawOutline, LineParameters, Pixel, Rectangle, RenderOutline, RenderingBase, Rgba8,
-    LINE_MAX_LENGTH, MAX_HALF_WIDTH, POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT,
+    DrawOutline, EllipseInterpolator, LineParameters, Pixel, Rectangle, RenderOutline,
+    RenderingBase, Rgba8, LINE_MAX_LENGTH, MAX_HALF_WIDTH, POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE,
+    POLY_SUBPIXEL_SHIFT,
 };
 use alloc::{vec, vec::Vec};
 #[allow(unused_imports)]
@@ -556,87 +557,3 @@ impl LineProfileAA {
         }
     }
 }
-
-/// Ellipse Interpolator
-#[derive(Debug)]
-struct EllipseInterpolator {
-    rx2: i64,
-    ry2: i64,
-    two_rx2: i64,
-    two_ry2: i64,
-    dx: i64,
-    dy: i64,
-    inc_x: i64,
-    inc_y: i64,
-    cur_f: i64,
-}
-
-impl EllipseInterpolator {
-    /// Create new Ellipse Interpolator with axes lenghts `rx` and `ry`
-    pub fn new(rx: i64, ry: i64) -> Self {
-        let rx2 = rx * rx;
-        let ry2 = ry * ry;
-        let two_rx2 = rx2 * 2;
-        let two_ry2 = ry2 * 2;
-        let dx = 0;
-        let dy = 0;
-        let inc_x = 0;
-        let inc_y = -ry * two_rx2;
-        let cur_f = 0;
-
-        Self { rx2, ry2, two_rx2, two_ry2, dx, dy, inc_x, inc_y, cur_f }
-    }
-
-    /// Increment the Interpolator
-    fn inc(&mut self) {
-        //
-        let mut mx = self.cur_f + self.inc_x + self.ry2;
-        let fx = mx;
-        if mx < 0 {
-            mx = -mx;
-        }
-
-        let mut my = self.cur_f + self.inc_y + self.rx2;
-        let fy = my;
-        if my < 0 {
-            my = -my;
-        }
-
-        let mut mxy = self.cur_f + self.inc_x + self.ry2 + self.inc_y + self.rx2;
-        let fxy = mxy;
-        if mxy < 0 {
-            mxy = -mxy;
-        }
-
-        let mut min_m = mx;
-
-        let flag = if min_m > my {
-            min_m = my;
-            false
-        } else {
-            true
-        };
-
-        self.dx = 0;
-        self.dy = 0;
-        if min_m > mxy {
-            self.inc_x += self.two_ry2;
-            self.inc_y += self.two_rx2;
-            self.cur_f = fxy;
-            self.dx = 1;
-            self.dy = 1;
-            return;
-        }
-
-        if flag {
-            self.inc_x += self.two_ry2;
-            self.cur_f = fx;
-            self.dx = 1;
-            return;
-        }
-
-        self.inc_y += self.two_rx2;
-        self.cur_f = fy;
-        self.dy = 1;
-    }
-}
@@ -0,0 +1,235 @@
+// agrega::outline::markers
+//
+//! Markers
+//!
+//! Small aliased shapes drawn directly on the pixels, like AGG's
+//! `renderer_markers`, fast enough for scatter plots with many points.
+//!
+//! # Examples
+//! ```
+//! use agrega::{Marker, Pixfmt, RendererOutline, RenderingBase, Rgb8};
+//!
+//! let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 100));
+//! ren_base.clear(Rgb8::white());
+//!
+//! let mut ren = RendererOutline::with_base(&mut ren_base);
+//! ren.line_color(Rgb8::black());
+//! ren.fill_color(Rgb8::new(255, 0, 0));
+//! ren.marker(50, 50, 5, Marker::Circle);
+//! ren.markers(&[(10, 10), (20, 30), (90, 90)], 3, Marker::Diamond);
+//! ```
+//
+// TOC
+// - enum Marker
+// - impl RendererOutline
+
+use crate::{EllipseInterpolator, Pixel, RendererOutline, Rgba8};
+use devela::iif;
+
+/// The shape of a marker drawn by [`RendererOutline::marker`].
+///
+/// Markers are outlined with the line color and filled with the fill color,
+/// except the ones made only of lines. A marker with a radius of 0 is a
+/// single pixel of the fill color.
+///
+/// The directions are the ones seen on the image, with the y axis pointing down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Marker {
+    /// A square.
+    Square,
+    /// A square rotated 45 degrees.
+    Diamond,
+    /// A circle.
+    Circle,
+    /// A circle crossed by short lines on each axis.
+    CrossedCircle,
+    /// Half an ellipse, rounded on the left.
+    SemiEllipseLeft,
+    /// Half an ellipse, rounded on the right.
+    SemiEllipseRight,
+    /// Half an ellipse, rounded on the top.
+    SemiEllipseUp,
+    /// Half an ellipse, rounded on the bottom.
+    SemiEllipseDown,
+    /// A triangle pointing left.
+    TriangleLeft,
+    /// A triangle pointing right.
+    TriangleRight,
+    /// A triangle pointing up.
+    TriangleUp,
+    /// A triangle pointing down.
+    TriangleDown,
+    /// A star of four rays.
+    FourRays,
+    /// A vertical and a horizontal line.
+    Cross,
+    /// Two diagonal lines.
+    X,
+    /// A horizontal line.
+    Dash,
+    /// A filled circle.
+    Dot,
+    /// A single pixel.
+    Pixel,
+}
+
+impl<T: Pixel> RendererOutline<'_, T> {
+    /// Draws a `marker` of radius `r` centered at `(x, y)`.
+    ///
+    /// Markers entirely outside of the image are skipped.
+    pub fn marker(&mut self, x: i64, y: i64, r: i64, marker: Marker) {
+        iif![!self.visible(x, y, r); return];
+        if r == 0 {
+            self.base.blend_pixel(x, y, self.fill_color, T::cover_mask());
+            return;
+        }
+        match marker {
+            Marker::Square => self.outlined_rectangle(x - r, y - r, x + r, y + r),
+            Marker::Diamond => self.diamond(x, y, r),
+            Marker::Circle => self.outlined_ellipse(x, y, r, r),
+            Marker::CrossedCircle => self.crossed_circle(x, y, r),
+            Marker::SemiEllipseLeft => self.semiellipse(x, y, r, |dx, dy| (dy, dx)),
+            Marker::SemiEllipseRight => self.semiellipse(x, y, r, |dx, dy| (-dy, dx)),
+            Marker::SemiEllipseUp => self.semiellipse(x, y, r, |dx, dy| (dx, dy)),
+            Marker::SemiEllipseDown => self.semiellipse(x, y, r, |dx, dy| (dx, -dy)),
+            Marker::TriangleLeft => self.triangle(x, y, r, |dx, dy| (dy, dx)),
+            Marker::TriangleRight => self.triangle(x, y, r, |dx, dy| (-dy, dx)),
+            Marker::TriangleUp => self.triangle(x, y, r, |dx, dy| (dx, dy)),
+            Marker::TriangleDown => self.triangle(x, y, r, |dx, dy| (dx, -dy)),
+            Marker::FourRays => self.four_rays(x, y, r),
+            Marker::Cross => {
+                let (c, cover) = (self.line_color, T::cover_mask());
+                self.base.blend_vline(x, y - r, y + r, c, cover);
+                self.base.blend_hline(x - r, y, x + r, c, cover);
+            }
+            Marker::X => {
+                let (c, cover) = (self.line_color, T::cover_mask());
+                for d in -r * 7 / 10..0 {
+                    self.base.blend_pixel(x + d, y + d, c, cover);
+                    self.base.blend_pixel(x - d, y + d, c, cover);
+                    self.base.blend_pixel(x + d, y - d, c, cover);
+                    self.base.blend_pixel(x - d, y - d, c, cover);
+                }
+                self.base.blend_pixel(x, y, self.fill_color, cover);
+            }
+            Marker::Dash => {
+                self.base.blend_hline(x - r, y, x + r, self.line_color, T::cover_mask())
+            }
+            Marker::Dot => self.solid_ellipse(x, y, r, r),
+            Marker::Pixel => self.base.blend_pixel(x, y, self.fill_color, T::cover_mask()),
+        }
+    }
+
+    /// Draws a `marker` of radius `r` centered at each of the `points`.
+    pub fn markers(&mut self, points: &[(i64, i64)], r: i64, marker: Marker) {
+        for &(x, y) in points {
+            self.marker(x, y, r, marker);
+        }
+    }
+
+    /* private */
+
+    /// Returns whether a marker of radius `r` at `(x, y)` touches the image.
+    fn visible(&self, x: i64, y: i64, r: i64) -> bool {
+        let (xmin, xmax, ymin, ymax) = self.base.limits();
+        x + r >= xmin && x - r <= xmax && y + r >= ymin && y - r <= ymax
+    }
+
+    fn diamond(&mut self, x: i64, y: i64, r: i64) {
+        let (line, fill, cover) = (self.line_color, self.fill_color, T::cover_mask());
+        for (dx, dy) in (0..=r).map(|d| (d, d - r)) {
+            self.base.blend_pixel(x - dx, y + dy, line, cover);
+            self.base.blend_pixel(x + dx, y + dy, line, cover);
+            self.base.blend_pixel(x - dx, y - dy, line, cover);
+            self.base.blend_pixel(x + dx, y - dy, line, cover);
+            if dx != 0 {
+                self.base.blend_hline(x - dx + 1, y + dy, x + dx - 1, fill, cover);
+                self.base.blend_hline(x - dx + 1, y - dy, x + dx - 1, fill, cover);
+            }
+        }
+    }
+
+    fn crossed_circle(&mut self, x: i64, y: i64, r: i64) {
+        let (c, cover) = (self.line_color, T::cover_mask());
+        self.outlined_ellipse(x, y, r, r);
+        let r6 = r + (r >> 1) + i64::from(r <= 2);
+        let r = r >> 1;
+        self.base.blend_hline(x - r6, y, x - r, c, cover);
+        self.base.blend_hline(x + r, y, x + r6, c, cover);
+        self.base.blend_vline(x, y - r6, y - r, c, cover);
+        self.base.blend_vline(x, y + r, y + r6, c, cover);
+    }
+
+    /// Draws half an ellipse, rounded on the top, with its offsets from the
+    /// center rotated by `rot`.
+    fn semiellipse(&mut self, x: i64, y: i64, r: i64, rot: impl Fn(i64, i64) -> (i64, i64)) {
+        let (line, fill) = (self.line_color, self.fill_color);
+        let r8 = r * 4 / 5;
+        let mut ei = EllipseInterpolator::new(r * 3 / 5, r + r8);
+        let (mut dx, mut dy) = (0, -r);
+        loop {
+            dx += ei.dx;
+            dy += ei.dy;
+            self.point(x, y, rot(dx, dy), line);
+            self.point(x, y, rot(-dx, dy), line);
+            iif![ei.dy != 0 && dx != 0; self.span(x, y, rot(-dx + 1, dy), rot(dx - 1, dy), fill)];
+            ei.inc();
+            iif![dy >= r8; break];
+        }
+        self.span(x, y, rot(-dx, dy + 1), rot(dx, dy + 1), line);
+    }
+
+    /// Draws a triangle pointing up, with its offsets from the center rotated by `rot`.
+    fn triangle(&mut self, x: i64, y: i64, r: i64, rot: impl Fn(i64, i64) -> (i64, i64)) {
+        let (line, fill) = (self.line_color, self.fill_color);
+        let r6 = r * 3 / 5;
+        let (mut dx, mut dy, mut flip) = (0, -r, 0);
+        loop {
+            self.point(x, y, rot(-dx, dy), line);
+            self.point(x, y, rot(dx, dy), line);
+            iif![dx != 0; self.span(x, y, rot(-dx + 1, dy), rot(dx - 1, dy), fill)];
+            dy += 1;
+            dx += flip;
+            flip ^= 1;
+            iif![dy >= r6; break];
+        }
+        self.span(x, y, rot(-dx, dy), rot(dx, dy), line);
+    }
+
+    fn four_rays(&mut self, x: i64, y: i64, r: i64) {
+        let (line, fill, cover) = (self.line_color, self.fill_color, T::cover_mask());
+        let r3 = -(r / 3);
+        let (mut dx, mut dy, mut flip) = (0, -r, 0);
+        loop {
+            for (px, py) in [(-dx, dy), (dx, dy), (-dx, -dy), (dx, -dy)] {
+                self.base.blend_pixel(x + px, y + py, line, cover);
+                self.base.blend_pixel(x + py, y + px, line, cover);
+            }
+            if dx != 0 {
+                self.base.blend_hline(x - dx + 1, y + dy, x + dx - 1, fill, cover);
+                self.base.blend_hline(x - dx + 1, y - dy, x + dx - 1, fill, cover);
+                self.base.blend_vline(x + dy, y - dx + 1, y + dx - 1, fill, cover);
+                self.base.blend_vline(x - dy, y - dx + 1, y + dx - 1, fill, cover);
+            }
+            dy += 1;
+            dx += flip;
+            flip ^= 1;
+            iif![dy > r3; break];
+        }
+        self.solid_rectangle(x + r3 + 1, y + r3 + 1, x - r3 - 1, y - r3 - 1);
+    }
+
+    /// Blends the pixel at the `offset` from `(x, y)`.
+    fn point(&mut self, x: i64, y: i64, offset: (i64, i64), c: Rgba8) {
+        self.base.blend_pixel(x + offset.0, y + offset.1, c, T::cover_mask());
+    }
+
+    /// Blends the horizontal or vertical line between the offsets `a` and `b` from `(x, y)`.
+    fn span(&mut self, x: i64, y: i64, a: (i64, i64), b: (i64, i64), c: Rgba8) {
+        if a.1 == b.1 {
+            self.base.blend_hline(x + a.0, y + a.1, x + b.0, c, T::cover_mask());
+        } else {
+            self.base.blend_vline(x + a.0, y + a.1, y + b.1, c, T::cover_mask());
+        }
+    }
+}
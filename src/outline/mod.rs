@@ -61,9 +61,10 @@
 
 mod aa_rast;
 mod aa_rend;
+mod markers;
 mod rast;
 mod rend;
-pub use {aa_rast::*, aa_rend::*, rast::*, rend::*};
+pub use {aa_rast::*, aa_rend::*, markers::*, rast::*, rend::*};
 
 use crate::{Color, LineParameters};
 
@@ -106,6 +107,8 @@ pub trait DrawOutline {
     where
         F: Fn(i64) -> bool;
     fn pie(&mut self, xc: i64, y: i64, x1: i64, y1: i64, x2: i64, y2: i64);
+    /// Advance `len` subpixels along the outline without drawing, over a dash gap
+    fn skip(&mut self, _len: i64) {}
 }
 
 /// TODO
@@ -114,3 +117,92 @@ pub(crate) trait RenderOutline {
     fn blend_solid_hspan(&mut self, x: i64, y: i64, len: i64, covers: &[u64]);
     fn blend_solid_vspan(&mut self, x: i64, y: i64, len: i64, covers: &[u64]);
 }
+
+/// Ellipse Interpolator
+///
+/// Steps along a quarter of an ellipse with the Bresenham algorithm, from
+/// the top, like AGG's `ellipse_bresenham_interpolator`.
+#[derive(Debug)]
+pub(crate) struct EllipseInterpolator {
+    rx2: i64,
+    ry2: i64,
+    two_rx2: i64,
+    two_ry2: i64,
+    /// The horizontal step of the last increment, 0 or 1.
+    pub(crate) dx: i64,
+    /// The vertical step of the last increment, 0 or 1.
+    pub(crate) dy: i64,
+    inc_x: i64,
+    inc_y: i64,
+    cur_f: i64,
+}
+
+impl EllipseInterpolator {
+    /// Create new Ellipse Interpolator with axes lenghts `rx` and `ry`
+    pub fn new(rx: i64, ry: i64) -> Self {
+        let rx2 = rx * rx;
+        let ry2 = ry * ry;
+        let two_rx2 = rx2 * 2;
+        let two_ry2 = ry2 * 2;
+        let dx = 0;
+        let dy = 0;
+        let inc_x = 0;
+        let inc_y = -ry * two_rx2;
+        let cur_f = 0;
+
+        Self { rx2, ry2, two_rx2, two_ry2, dx, dy, inc_x, inc_y, cur_f }
+    }
+
+    /// Increment the Interpolator
+    pub fn inc(&mut self) {
+        //
+        let mut mx = self.cur_f + self.inc_x + self.ry2;
+        let fx = mx;
+        if mx < 0 {
+            mx = -mx;
+        }
+
+        let mut my = self.cur_f + self.inc_y + self.rx2;
+        let fy = my;
+        if my < 0 {
+            my = -my;
+        }
+
+        let mut mxy = self.cur_f + self.inc_x + self.ry2 + self.inc_y + self.rx2;
+        let fxy = mxy;
+        if mxy < 0 {
+            mxy = -mxy;
+        }
+
+        let mut min_m = mx;
+
+        let flag = if min_m > my {
+            min_m = my;
+            false
+        } else {
+            true
+        };
+
+        self.dx = 0;
+        self.dy = 0;
+        if min_m > mxy {
+            self.inc_x += self.two_ry2;
+            self.inc_y += self.two_rx2;
+            self.cur_f = fxy;
+            self.dx = 1;
+            self.dy = 1;
+            return;
+        }
+
+        if flag {
+            self.inc_x += self.two_ry2;
+            self.cur_f = fx;
+            self.dx = 1;
+            return;
+        }
+
+        self.inc_y += self.two_rx2;
+        self.cur_f = fy;
+        self.dy = 1;
+    }
+}
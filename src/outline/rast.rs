@@ -23,12 +23,14 @@ impl<'a, T: Pixel> RasterizerOutline<'a, T> {
 
     /// Add a path and render
     pub fn add_path<VS: VertexSource>(&mut self, path: &VS) {
-        for v in path.xconvert().iter() {
+        for v in path.vertices() {
             match v.cmd {
                 PathCommand::MoveTo => self.move_to_d(v.x, v.y),
-                PathCommand::LineTo => self.line_to_d(v.x, v.y),
+                PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4 => {
+                    self.line_to_d(v.x, v.y)
+                }
                 PathCommand::Close => self.close(),
-                PathCommand::Stop => unimplemented!("stop encountered"),
+                PathCommand::Stop => break,
             }
         }
     }
@@ -1,20 +1,25 @@
 // agrega::outline::rend
 
 use crate::{
-    BresehamInterpolator, Color, Pixel, RenderingBase, Rgba8, Subpixel, POLY_SUBPIXEL_SCALE,
+    BresehamInterpolator, Color, EllipseInterpolator, Pixel, RenderingBase, Rgba8, Subpixel,
+    POLY_SUBPIXEL_SCALE,
 };
 #[allow(unused_imports)]
-use devela::ExtFloat;
+use devela::{iif, ExtFloat};
 
 /// Renderer for drawing primitive shapes on a pixel grid.
+///
+/// It draws aliased lines, rectangles, ellipses and [markers][Self::marker]
+/// directly on the pixels, without a rasterizer, like AGG's `renderer_primitives`.
+/// Coordinates of shapes are in whole pixels.
 #[derive(Debug)]
 pub struct RendererOutline<'a, T: 'a> {
     /// Reference to the base rendering engine.
-    base: &'a mut RenderingBase<T>,
+    pub(super) base: &'a mut RenderingBase<T>,
     /// Fill color used for filled shapes.
-    fill_color: Rgba8,
+    pub(super) fill_color: Rgba8,
     /// Line color used for outline strokes.
-    line_color: Rgba8,
+    pub(super) line_color: Rgba8,
     /// X coordinate in subpixel precision.
     x: Subpixel,
     /// Y coordinate in subpixel precision.
@@ -43,6 +48,89 @@ impl<'a, T: Pixel> RendererOutline<'a, T> {
         self.fill_color = Rgba8::from_trait(fill_color);
     }
 
+    /// Draws the outline of the rectangle from `(x1, y1)` to `(x2, y2)` with the line color.
+    pub fn rectangle(&mut self, x1: i64, y1: i64, x2: i64, y2: i64) {
+        let (c, cover) = (self.line_color, T::cover_mask());
+        self.base.blend_hline(x1, y1, x2 - 1, c, cover);
+        self.base.blend_vline(x2, y1, y2 - 1, c, cover);
+        self.base.blend_hline(x1 + 1, y2, x2, c, cover);
+        self.base.blend_vline(x1, y1 + 1, y2, c, cover);
+    }
+
+    /// Fills the rectangle from `(x1, y1)` to `(x2, y2)` with the fill color.
+    pub fn solid_rectangle(&mut self, x1: i64, y1: i64, x2: i64, y2: i64) {
+        self.base.blend_bar(x1, y1, x2, y2, self.fill_color, T::cover_mask());
+    }
+
+    /// Draws the rectangle from `(x1, y1)` to `(x2, y2)` with the line color,
+    /// filling its inside with the fill color.
+    pub fn outlined_rectangle(&mut self, x1: i64, y1: i64, x2: i64, y2: i64) {
+        self.rectangle(x1, y1, x2, y2);
+        self.base
+            .blend_bar(x1 + 1, y1 + 1, x2 - 1, y2 - 1, self.fill_color, T::cover_mask());
+    }
+
+    /// Draws the outline of the ellipse centered at `(x, y)` with radii `rx`
+    /// and `ry`, with the line color.
+    pub fn ellipse(&mut self, x: i64, y: i64, rx: i64, ry: i64) {
+        let (c, cover) = (self.line_color, T::cover_mask());
+        let mut ei = EllipseInterpolator::new(rx, ry);
+        let (mut dx, mut dy) = (0, -ry);
+        loop {
+            dx += ei.dx;
+            dy += ei.dy;
+            self.base.blend_pixel(x + dx, y + dy, c, cover);
+            self.base.blend_pixel(x + dx, y - dy, c, cover);
+            self.base.blend_pixel(x - dx, y - dy, c, cover);
+            self.base.blend_pixel(x - dx, y + dy, c, cover);
+            ei.inc();
+            iif![dy >= 0; break];
+        }
+    }
+
+    /// Fills the ellipse centered at `(x, y)` with radii `rx` and `ry`, with
+    /// the fill color.
+    pub fn solid_ellipse(&mut self, x: i64, y: i64, rx: i64, ry: i64) {
+        let (c, cover) = (self.fill_color, T::cover_mask());
+        let mut ei = EllipseInterpolator::new(rx, ry);
+        let (mut dx, mut dy) = (0, -ry);
+        let (mut dx0, mut dy0) = (dx, dy);
+        loop {
+            dx += ei.dx;
+            dy += ei.dy;
+            if dy != dy0 {
+                self.base.blend_hline(x - dx0, y + dy0, x + dx0, c, cover);
+                self.base.blend_hline(x - dx0, y - dy0, x + dx0, c, cover);
+            }
+            (dx0, dy0) = (dx, dy);
+            ei.inc();
+            iif![dy >= 0; break];
+        }
+        self.base.blend_hline(x - dx0, y + dy0, x + dx0, c, cover);
+    }
+
+    /// Draws the ellipse centered at `(x, y)` with radii `rx` and `ry`, with
+    /// the line color, filling its inside with the fill color.
+    pub fn outlined_ellipse(&mut self, x: i64, y: i64, rx: i64, ry: i64) {
+        let (line, fill, cover) = (self.line_color, self.fill_color, T::cover_mask());
+        let mut ei = EllipseInterpolator::new(rx, ry);
+        let (mut dx, mut dy) = (0, -ry);
+        loop {
+            dx += ei.dx;
+            dy += ei.dy;
+            self.base.blend_pixel(x + dx, y + dy, line, cover);
+            self.base.blend_pixel(x + dx, y - dy, line, cover);
+            self.base.blend_pixel(x - dx, y - dy, line, cover);
+            self.base.blend_pixel(x - dx, y + dy, line, cover);
+            if ei.dy != 0 && dx != 0 {
+                self.base.blend_hline(x - dx + 1, y + dy, x + dx - 1, fill, cover);
+                self.base.blend_hline(x - dx + 1, y - dy, x + dx - 1, fill, cover);
+            }
+            ei.inc();
+            iif![dy >= 0; break];
+        }
+    }
+
     /* private */
 
     /// Converts a floating-point coordinate to `Subpixel` units.
@@ -5,18 +5,35 @@
 // - struct Rectangle
 // - struct Clip
 
-use crate::cell::RasterizerCell;
+use crate::{cell::RasterizerCell, POLY_SUBPIXEL_SCALE};
 use core::cmp::PartialOrd;
 use devela::iif;
 #[allow(unused_imports)]
 use devela::ExtFloat;
 
-/// Multiplies `a` and `b`, then divides by `c`, rounding the result to `i64`.
+/// The largest coordinate in pixels that the rasterizer accepts, like AGG's
+/// `poly_max_coord`, so the subpixel cell math never overflows.
+pub const POLY_MAX_COORD: f64 = ((1 << 30) - 1) as f64 / POLY_SUBPIXEL_SCALE as f64;
+
+/// The region the rasterizer clips to when there's no clip box.
+const SAFE_BOX: Rectangle<f64> =
+    Rectangle { x1: -POLY_MAX_COORD, y1: -POLY_MAX_COORD, x2: POLY_MAX_COORD, y2: POLY_MAX_COORD };
+
+/// Returns the `b` coordinate where the segment from `(a1, b1)` to `(a2, b2)` crosses `a`.
+///
+/// Interpolates with a convex combination so huge finite coordinates don't overflow.
+#[inline]
+#[must_use]
+fn cross_at(a1: f64, b1: f64, a2: f64, b2: f64, a: f64) -> f64 {
+    let t = ((a - a1) / (a2 - a1)).clamp(0.0, 1.0);
+    b1 * (1.0 - t) + b2 * t
+}
+
+/// Converts a coordinate in pixels into subpixels.
 #[inline]
 #[must_use]
-fn mul_div(a: i64, b: i64, c: i64) -> i64 {
-    let (a, b, c) = (a as f64, b as f64, c as f64);
-    (a * b / c).round() as i64
+fn upscale(v: f64) -> i64 {
+    (v * POLY_SUBPIXEL_SCALE as f64).round() as i64
 }
 
 /// Represents the sides of a clipping region.
@@ -101,7 +118,7 @@ impl<T: PartialOrd + Copy> Rectangle<T> {
     #[must_use]
     pub fn new(x1: T, y1: T, x2: T, y2: T) -> Self {
         let (x1, x2) = if x1 > x2 { (x2, x1) } else { (x1, x2) };
-        let (y1, y2) = if y1 > x2 { (y2, y1) } else { (y1, y2) };
+        let (y1, y2) = if y1 > y2 { (y2, y1) } else { (y1, y2) };
         Self { x1, y1, x2, y2 }
     }
 
@@ -155,14 +172,19 @@ impl Rectangle<i64> {
 }
 
 /// A clipping region for rasterizers, defining boundaries for drawing.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+///
+/// Clips in double precision before converting the coordinates into
+/// subpixels, like AGG's `rasterizer_sl_clip_dbl`. Without a clip box it
+/// still clips to the [`POLY_MAX_COORD`] limits, which keeps the cell math
+/// from overflowing but doesn't bound the work to the visible area.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Clip {
     /// Current x-coordinate.
-    x1: i64,
+    x1: f64,
     /// Current y-coordinate.
-    y1: i64,
+    y1: f64,
     /// Rectangle defining the clipping area.
-    clip_box: Option<Rectangle<i64>>,
+    clip_box: Option<Rectangle<f64>>,
     /// Current clipping flag for the point `(x1, y1)`.
     clip_flag: u8,
 }
@@ -177,7 +199,20 @@ impl Clip {
     /// Creates a new `Clip` with no clipping box and default coordinates.
     #[inline]
     pub const fn new() -> Self {
-        Self { x1: 0, y1: 0, clip_box: None, clip_flag: ClipSide::INSIDE }
+        Self { x1: 0.0, y1: 0.0, clip_box: None, clip_flag: ClipSide::INSIDE }
+    }
+
+    /// Returns the clipping box, or the safe region if there's none.
+    #[inline]
+    #[must_use]
+    fn bounds(&self) -> Rectangle<f64> {
+        self.clip_box.unwrap_or(SAFE_BOX)
+    }
+
+    /// Draws a line in subpixels, converting its coordinates in pixels.
+    #[inline]
+    fn line(ras: &mut RasterizerCell, x1: f64, y1: f64, x2: f64, y2: f64) {
+        ras.line(upscale(x1), upscale(y1), upscale(x2), upscale(y2));
     }
 
     /// Clips a line along the top and bottom boundaries of the clipping box.
@@ -187,122 +222,110 @@ impl Clip {
     fn line_clip_y(
         &self,
         ras: &mut RasterizerCell,
-        x1: i64,
-        y1: i64,
-        x2: i64,
-        y2: i64,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
         f1: u8,
         f2: u8,
     ) {
-        let b = match self.clip_box {
-            None => return,
-            Some(ref b) => b,
-        };
+        let b = self.bounds();
         let f1 = f1 & (ClipSide::TOP | ClipSide::BOTTOM);
         let f2 = f2 & (ClipSide::TOP | ClipSide::BOTTOM);
         // Fully Visible in y
         if f1 == ClipSide::INSIDE && f2 == ClipSide::INSIDE {
-            ras.line(x1, y1, x2, y2);
+            Self::line(ras, x1, y1, x2, y2);
         } else {
             // Both points above or below clip box
             iif![f1 == f2; return];
             let (mut tx1, mut ty1, mut tx2, mut ty2) = (x1, y1, x2, y2);
             if f1 == ClipSide::BOTTOM {
-                tx1 = x1 + mul_div(b.y1 - y1, x2 - x1, y2 - y1);
+                tx1 = cross_at(y1, x1, y2, x2, b.y1);
                 ty1 = b.y1;
             }
             if f1 == ClipSide::TOP {
-                tx1 = x1 + mul_div(b.y2 - y1, x2 - x1, y2 - y1);
+                tx1 = cross_at(y1, x1, y2, x2, b.y2);
                 ty1 = b.y2;
             }
             if f2 == ClipSide::BOTTOM {
-                tx2 = x1 + mul_div(b.y1 - y1, x2 - x1, y2 - y1);
+                tx2 = cross_at(y1, x1, y2, x2, b.y1);
                 ty2 = b.y1;
             }
             if f2 == ClipSide::TOP {
-                tx2 = x1 + mul_div(b.y2 - y1, x2 - x1, y2 - y1);
+                tx2 = cross_at(y1, x1, y2, x2, b.y2);
                 ty2 = b.y2;
             }
-            ras.line(tx1, tx2, ty1, ty2);
+            Self::line(ras, tx1, ty1, tx2, ty2);
         }
     }
 
     /// Draws a line from `(x1, y1)` to `(x2, y2)` in the `RasterizerCell`.
     ///
     /// The endpoint `(x2, y2)` is saved internally as the new starting point `(x1, y1)`.
-    pub(crate) fn line_to(&mut self, ras: &mut RasterizerCell, x2: i64, y2: i64) {
-        if let Some(ref b) = self.clip_box {
-            let f2 = b.clip_flags_i64(x2, y2);
-            // Both points above or below clip box
-            let fy1 = (ClipSide::TOP | ClipSide::BOTTOM) & self.clip_flag;
-            let fy2 = (ClipSide::TOP | ClipSide::BOTTOM) & f2;
-            if fy1 != ClipSide::INSIDE && fy1 == fy2 {
-                self.x1 = x2;
-                self.y1 = y2;
-                self.clip_flag = f2;
-                return;
+    /// The coordinates are in pixels, and must be finite.
+    pub(crate) fn line_to(&mut self, ras: &mut RasterizerCell, x2: f64, y2: f64) {
+        let b = self.bounds();
+        let f2 = b.clip_flags(x2, y2);
+        // Both points above or below clip box
+        let fy1 = (ClipSide::TOP | ClipSide::BOTTOM) & self.clip_flag;
+        let fy2 = (ClipSide::TOP | ClipSide::BOTTOM) & f2;
+        if fy1 != ClipSide::INSIDE && fy1 == fy2 {
+            self.x1 = x2;
+            self.y1 = y2;
+            self.clip_flag = f2;
+            return;
+        }
+        let (x1, y1, f1) = (self.x1, self.y1, self.clip_flag);
+        match (f1 & (ClipSide::LEFT | ClipSide::RIGHT), f2 & (ClipSide::LEFT | ClipSide::RIGHT)) {
+            (ClipSide::INSIDE, ClipSide::INSIDE) => self.line_clip_y(ras, x1, y1, x2, y2, f1, f2),
+            (ClipSide::INSIDE, ClipSide::RIGHT) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x2);
+                let f3 = b.clip_flags(b.x2, y3);
+                self.line_clip_y(ras, x1, y1, b.x2, y3, f1, f3);
+                self.line_clip_y(ras, b.x2, y3, b.x2, y2, f3, f2);
             }
-            let (x1, y1, f1) = (self.x1, self.y1, self.clip_flag);
-            match (f1 & (ClipSide::LEFT | ClipSide::RIGHT), f2 & (ClipSide::LEFT | ClipSide::RIGHT))
-            {
-                (ClipSide::INSIDE, ClipSide::INSIDE) => {
-                    self.line_clip_y(ras, x1, y1, x2, y2, f1, f2)
-                }
-                (ClipSide::INSIDE, ClipSide::RIGHT) => {
-                    let y3 = y1 + mul_div(b.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x2, y3);
-                    self.line_clip_y(ras, x1, y1, b.x2, y3, f1, f3);
-                    self.line_clip_y(ras, b.x2, y3, b.x2, y2, f3, f2);
-                }
-                (ClipSide::RIGHT, ClipSide::INSIDE) => {
-                    let y3 = y1 + mul_div(b.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x2, y3);
-                    self.line_clip_y(ras, b.x2, y1, b.x2, y3, f1, f3);
-                    self.line_clip_y(ras, b.x2, y3, x2, y2, f3, f2);
-                }
-                (ClipSide::INSIDE, ClipSide::LEFT) => {
-                    let y3 = y1 + mul_div(b.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x1, y3);
-                    self.line_clip_y(ras, x1, y1, b.x1, y3, f1, f3);
-                    self.line_clip_y(ras, b.x1, y3, b.x1, y2, f3, f2);
-                }
-                (ClipSide::RIGHT, ClipSide::LEFT) => {
-                    let y3 = y1 + mul_div(b.x2 - x1, y2 - y1, x2 - x1);
-                    let y4 = y1 + mul_div(b.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x2, y3);
-                    let f4 = b.clip_flags_i64(b.x1, y4);
-                    self.line_clip_y(ras, b.x2, y1, b.x2, y3, f1, f3);
-                    self.line_clip_y(ras, b.x2, y3, b.x1, y4, f3, f4);
-                    self.line_clip_y(ras, b.x1, y4, b.x1, y2, f4, f2);
-                }
-                (ClipSide::LEFT, ClipSide::INSIDE) => {
-                    let y3 = y1 + mul_div(b.x1 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x1, y3);
-                    self.line_clip_y(ras, b.x1, y1, b.x1, y3, f1, f3);
-                    self.line_clip_y(ras, b.x1, y3, x2, y2, f3, f2);
-                }
-                (ClipSide::LEFT, ClipSide::RIGHT) => {
-                    let y3 = y1 + mul_div(b.x1 - x1, y2 - y1, x2 - x1);
-                    let y4 = y1 + mul_div(b.x2 - x1, y2 - y1, x2 - x1);
-                    let f3 = b.clip_flags_i64(b.x1, y3);
-                    let f4 = b.clip_flags_i64(b.x2, y4);
-                    self.line_clip_y(ras, b.x1, y1, b.x1, y3, f1, f3);
-                    self.line_clip_y(ras, b.x1, y3, b.x2, y4, f3, f4);
-                    self.line_clip_y(ras, b.x2, y4, b.x2, y2, f4, f2);
-                }
-                (ClipSide::LEFT, ClipSide::LEFT) => {
-                    self.line_clip_y(ras, b.x1, y1, b.x1, y2, f1, f2)
-                }
-                (ClipSide::RIGHT, ClipSide::RIGHT) => {
-                    self.line_clip_y(ras, b.x2, y1, b.x2, y2, f1, f2)
-                }
-
-                (_, _) => unreachable!("f1,f2 {:?} {:?}", f1, f2),
+            (ClipSide::RIGHT, ClipSide::INSIDE) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x2);
+                let f3 = b.clip_flags(b.x2, y3);
+                self.line_clip_y(ras, b.x2, y1, b.x2, y3, f1, f3);
+                self.line_clip_y(ras, b.x2, y3, x2, y2, f3, f2);
             }
-            self.clip_flag = f2;
-        } else {
-            ras.line(self.x1, self.y1, x2, y2);
+            (ClipSide::INSIDE, ClipSide::LEFT) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x1);
+                let f3 = b.clip_flags(b.x1, y3);
+                self.line_clip_y(ras, x1, y1, b.x1, y3, f1, f3);
+                self.line_clip_y(ras, b.x1, y3, b.x1, y2, f3, f2);
+            }
+            (ClipSide::RIGHT, ClipSide::LEFT) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x2);
+                let y4 = cross_at(x1, y1, x2, y2, b.x1);
+                let f3 = b.clip_flags(b.x2, y3);
+                let f4 = b.clip_flags(b.x1, y4);
+                self.line_clip_y(ras, b.x2, y1, b.x2, y3, f1, f3);
+                self.line_clip_y(ras, b.x2, y3, b.x1, y4, f3, f4);
+                self.line_clip_y(ras, b.x1, y4, b.x1, y2, f4, f2);
+            }
+            (ClipSide::LEFT, ClipSide::INSIDE) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x1);
+                let f3 = b.clip_flags(b.x1, y3);
+                self.line_clip_y(ras, b.x1, y1, b.x1, y3, f1, f3);
+                self.line_clip_y(ras, b.x1, y3, x2, y2, f3, f2);
+            }
+            (ClipSide::LEFT, ClipSide::RIGHT) => {
+                let y3 = cross_at(x1, y1, x2, y2, b.x1);
+                let y4 = cross_at(x1, y1, x2, y2, b.x2);
+                let f3 = b.clip_flags(b.x1, y3);
+                let f4 = b.clip_flags(b.x2, y4);
+                self.line_clip_y(ras, b.x1, y1, b.x1, y3, f1, f3);
+                self.line_clip_y(ras, b.x1, y3, b.x2, y4, f3, f4);
+                self.line_clip_y(ras, b.x2, y4, b.x2, y2, f4, f2);
+            }
+            (ClipSide::LEFT, ClipSide::LEFT) => self.line_clip_y(ras, b.x1, y1, b.x1, y2, f1, f2),
+            (ClipSide::RIGHT, ClipSide::RIGHT) => self.line_clip_y(ras, b.x2, y1, b.x2, y2, f1, f2),
+
+            (_, _) => unreachable!("f1,f2 {:?} {:?}", f1, f2),
         }
+        self.clip_flag = f2;
         self.x1 = x2;
         self.y1 = y2;
     }
@@ -310,18 +333,20 @@ impl Clip {
     /// Sets the current point to `(x2, y2)`.
     ///
     /// The point is saved as `(x1, y1)`, and the clip flag is updated accordingly.
+    /// The coordinates are in pixels, and must be finite.
     #[inline]
-    pub(crate) fn move_to(&mut self, x2: i64, y2: i64) {
+    pub(crate) fn move_to(&mut self, x2: f64, y2: f64) {
         self.x1 = x2;
         self.y1 = y2;
-        if let Some(ref b) = self.clip_box {
-            self.clip_flag = ClipSide::clip_flags_i64(x2, y2, b.x1, b.y1, b.x2, b.y2);
-        }
+        self.clip_flag = self.bounds().clip_flags(x2, y2);
     }
 
-    /// Defines the clipping region with the specified coordinates.
+    /// Defines the clipping region with the specified coordinates, in pixels.
+    ///
+    /// The region is limited to the [`POLY_MAX_COORD`] range.
     #[inline]
-    pub fn clip_box(&mut self, x1: i64, y1: i64, x2: i64, y2: i64) {
-        self.clip_box = Some(Rectangle::new(x1, y1, x2, y2));
+    pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let c = |v: f64| v.clamp(-POLY_MAX_COORD, POLY_MAX_COORD);
+        self.clip_box = Some(Rectangle::new(c(x1), c(y1), c(x2), c(y2)));
     }
 }
@@ -0,0 +1,419 @@
+// agrega::paths::curve
+//
+//! Bézier curves
+//!
+//! Flattening of quadratic and cubic Bézier curves into line segments,
+//! using recursive subdivision.
+//!
+//! # Examples
+//! ```
+//! use agrega::{Curve, Path, VertexSource};
+//!
+//! let mut path = Path::new();
+//! path.move_to(0.0, 0.0);
+//! path.curve4(0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+//!
+//! // Only MoveTo and LineTo commands remain
+//! let lines = Curve::new(path).xconvert();
+//! assert!(lines.len() > 4);
+//! ```
+//
+// TOC
+// - struct Curve3
+// - struct Curve4
+// - struct Curve
+// - fn curve3_flatten
+// - fn curve4_flatten
+
+use crate::paths::{PathCommand, Vertex, VertexSource};
+use alloc::{vec, vec::Vec};
+use core::iter::Peekable;
+#[allow(unused_imports)]
+use devela::ExtFloat;
+
+/// Maximum depth of the recursive subdivision.
+const CURVE_RECURSION_LIMIT: u32 = 32;
+/// Threshold under which control points are considered collinear.
+const CURVE_COLLINEARITY_EPSILON: f64 = 1e-30;
+
+/// A quadratic Bézier curve, approximated by line segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Curve3 {
+    points: [f64; 6],
+    approx_scale: f64,
+}
+
+impl VertexSource for Curve3 {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let [x1, y1, x2, y2, x3, y3] = self.points;
+        let mut out = vec![Vertex::move_to(x1, y1)];
+        curve3_flatten(&mut out, x1, y1, x2, y2, x3, y3, self.approx_scale);
+        out.into_iter()
+    }
+}
+
+impl Curve3 {
+    /// Creates a new curve from `(x1, y1)` to `(x3, y3)` with control point `(x2, y2)`.
+    #[inline]
+    #[must_use]
+    pub const fn new(x1: f64, y1: f64, x2: f64, y2: f64, x3: f64, y3: f64) -> Self {
+        Self { points: [x1, y1, x2, y2, x3, y3], approx_scale: 1.0 }
+    }
+    /// Set approximation scale
+    ///
+    /// Higher values produce more line segments, 1.0 is suitable for
+    /// coordinates in pixels.
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+}
+
+/// A cubic Bézier curve, approximated by line segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Curve4 {
+    points: [f64; 8],
+    approx_scale: f64,
+}
+
+impl VertexSource for Curve4 {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let [x1, y1, x2, y2, x3, y3, x4, y4] = self.points;
+        let mut out = vec![Vertex::move_to(x1, y1)];
+        curve4_flatten(&mut out, x1, y1, x2, y2, x3, y3, x4, y4, self.approx_scale);
+        out.into_iter()
+    }
+}
+
+impl Curve4 {
+    /// Creates a new curve from `(x1, y1)` to `(x4, y4)`
+    /// with control points `(x2, y2)` and `(x3, y3)`.
+    #[inline]
+    #[must_use]
+    #[expect(clippy::too_many_arguments)]
+    pub const fn new(
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        x3: f64,
+        y3: f64,
+        x4: f64,
+        y4: f64,
+    ) -> Self {
+        Self { points: [x1, y1, x2, y2, x3, y3, x4, y4], approx_scale: 1.0 }
+    }
+    /// Set approximation scale
+    ///
+    /// Higher values produce more line segments, 1.0 is suitable for
+    /// coordinates in pixels.
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+}
+
+/// Curve Converter for Paths and Vertex Sources
+///
+/// Replaces every [`Curve3`][PathCommand::Curve3] and
+/// [`Curve4`][PathCommand::Curve4] sequence in the source with
+/// [`LineTo`][PathCommand::LineTo] segments. Other commands pass through.
+#[derive(Debug)]
+pub struct Curve<S: VertexSource> {
+    source: S,
+    approx_scale: f64,
+}
+
+impl<S: VertexSource> VertexSource for Curve<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        FlattenCurves::new(self.source.vertices(), self.approx_scale)
+    }
+}
+
+impl<S: VertexSource> Curve<S> {
+    /// Create a new Curve converter from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, approx_scale: 1.0 }
+    }
+    /// Set approximation scale
+    ///
+    /// Higher values produce more line segments, 1.0 is suitable for
+    /// coordinates in pixels.
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+    /// Get the wrapped Vertex Source
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+}
+
+/// Iterator replacing the curve commands of a vertex stream with line segments.
+///
+/// A curve sequence that is cut short by another command is emitted as
+/// plain line segments through its control points.
+pub(crate) struct FlattenCurves<I: Iterator<Item = Vertex<f64>>> {
+    src: Peekable<I>,
+    /// Flattened vertices of the last curve, waiting to be emitted.
+    buf: Vec<Vertex<f64>>,
+    pos: usize,
+    last: (f64, f64),
+    approx_scale: f64,
+}
+
+impl<I: Iterator<Item = Vertex<f64>>> FlattenCurves<I> {
+    pub fn new(src: I, approx_scale: f64) -> Self {
+        Self { src: src.peekable(), buf: vec![], pos: 0, last: (0.0, 0.0), approx_scale }
+    }
+    fn next_if_cmd(&mut self, cmd: PathCommand) -> Option<Vertex<f64>> {
+        self.src.next_if(|v| v.cmd == cmd)
+    }
+}
+
+impl<I: Iterator<Item = Vertex<f64>>> Iterator for FlattenCurves<I> {
+    type Item = Vertex<f64>;
+    fn next(&mut self) -> Option<Vertex<f64>> {
+        if self.pos < self.buf.len() {
+            self.pos += 1;
+            return Some(self.buf[self.pos - 1]);
+        }
+        self.buf.clear();
+        self.pos = 0;
+        let v = self.src.next()?;
+        let (x0, y0) = self.last;
+        let scale = self.approx_scale;
+        match v.cmd {
+            PathCommand::Curve3 => {
+                if let Some(e) = self.next_if_cmd(PathCommand::Curve3) {
+                    curve3_flatten(&mut self.buf, x0, y0, v.x, v.y, e.x, e.y, scale);
+                } else {
+                    self.buf.push(Vertex::line_to(v.x, v.y));
+                }
+            }
+            PathCommand::Curve4 => match self.next_if_cmd(PathCommand::Curve4) {
+                Some(c) => match self.next_if_cmd(PathCommand::Curve4) {
+                    Some(e) => {
+                        curve4_flatten(&mut self.buf, x0, y0, v.x, v.y, c.x, c.y, e.x, e.y, scale)
+                    }
+                    None => {
+                        self.buf.push(Vertex::line_to(v.x, v.y));
+                        self.buf.push(Vertex::line_to(c.x, c.y));
+                    }
+                },
+                None => self.buf.push(Vertex::line_to(v.x, v.y)),
+            },
+            _ => {
+                self.last = (v.x, v.y);
+                return Some(v);
+            }
+        }
+        let end = self.buf[self.buf.len() - 1];
+        self.last = (end.x, end.y);
+        self.pos = 1;
+        Some(self.buf[0])
+    }
+}
+
+/// Appends the `LineTo` vertices approximating a quadratic curve,
+/// excluding the starting point `(x1, y1)` and including the end point.
+#[expect(clippy::too_many_arguments)]
+pub(crate) fn curve3_flatten(
+    out: &mut Vec<Vertex<f64>>,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    x3: f64,
+    y3: f64,
+    approx_scale: f64,
+) {
+    let tol = distance_tolerance_square(approx_scale);
+    curve3_recursive(out, tol, x1, y1, x2, y2, x3, y3, 0);
+    out.push(Vertex::line_to(x3, y3));
+}
+
+/// Appends the `LineTo` vertices approximating a cubic curve,
+/// excluding the starting point `(x1, y1)` and including the end point.
+#[expect(clippy::too_many_arguments)]
+pub(crate) fn curve4_flatten(
+    out: &mut Vec<Vertex<f64>>,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    x3: f64,
+    y3: f64,
+    x4: f64,
+    y4: f64,
+    approx_scale: f64,
+) {
+    let tol = distance_tolerance_square(approx_scale);
+    curve4_recursive(out, tol, x1, y1, x2, y2, x3, y3, x4, y4, 0);
+    out.push(Vertex::line_to(x4, y4));
+}
+
+#[inline]
+fn distance_tolerance_square(approx_scale: f64) -> f64 {
+    let tol = 0.5 / approx_scale;
+    tol * tol
+}
+
+#[inline]
+fn sq_distance(x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    (x2 - x1) * (x2 - x1) + (y2 - y1) * (y2 - y1)
+}
+
+#[expect(clippy::too_many_arguments)]
+fn curve3_recursive(
+    out: &mut Vec<Vertex<f64>>,
+    tol: f64,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    x3: f64,
+    y3: f64,
+    level: u32,
+) {
+    if level > CURVE_RECURSION_LIMIT {
+        return;
+    }
+    // Mid-points of the line segments
+    let (x12, y12) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+    let (x23, y23) = ((x2 + x3) / 2.0, (y2 + y3) / 2.0);
+    let (x123, y123) = ((x12 + x23) / 2.0, (y12 + y23) / 2.0);
+
+    let dx = x3 - x1;
+    let dy = y3 - y1;
+    let mut d = ((x2 - x3) * dy - (y2 - y3) * dx).abs();
+
+    if d > CURVE_COLLINEARITY_EPSILON {
+        // Regular case
+        if d * d <= tol * (dx * dx + dy * dy) {
+            out.push(Vertex::line_to(x123, y123));
+            return;
+        }
+    } else {
+        // Collinear case
+        let da = dx * dx + dy * dy;
+        if da == 0.0 {
+            d = sq_distance(x1, y1, x2, y2);
+        } else {
+            d = ((x2 - x1) * dx + (y2 - y1) * dy) / da;
+            if d > 0.0 && d < 1.0 {
+                // Simple collinear case, 1---2---3
+                return;
+            }
+            d = if d <= 0.0 {
+                sq_distance(x2, y2, x1, y1)
+            } else if d >= 1.0 {
+                sq_distance(x2, y2, x3, y3)
+            } else {
+                sq_distance(x2, y2, x1 + d * dx, y1 + d * dy)
+            };
+        }
+        if d < tol {
+            out.push(Vertex::line_to(x2, y2));
+            return;
+        }
+    }
+    // Continue subdivision
+    curve3_recursive(out, tol, x1, y1, x12, y12, x123, y123, level + 1);
+    curve3_recursive(out, tol, x123, y123, x23, y23, x3, y3, level + 1);
+}
+
+#[expect(clippy::too_many_arguments)]
+fn curve4_recursive(
+    out: &mut Vec<Vertex<f64>>,
+    tol: f64,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    x3: f64,
+    y3: f64,
+    x4: f64,
+    y4: f64,
+    level: u32,
+) {
+    if level > CURVE_RECURSION_LIMIT {
+        return;
+    }
+    // Mid-points of the line segments
+    let (x12, y12) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+    let (x23, y23) = ((x2 + x3) / 2.0, (y2 + y3) / 2.0);
+    let (x34, y34) = ((x3 + x4) / 2.0, (y3 + y4) / 2.0);
+    let (x123, y123) = ((x12 + x23) / 2.0, (y12 + y23) / 2.0);
+    let (x234, y234) = ((x23 + x34) / 2.0, (y23 + y34) / 2.0);
+    let (x1234, y1234) = ((x123 + x234) / 2.0, (y123 + y234) / 2.0);
+
+    // Try to approximate the full cubic curve by a single straight line
+    let dx = x4 - x1;
+    let dy = y4 - y1;
+    let mut d2 = ((x2 - x4) * dy - (y2 - y4) * dx).abs();
+    let mut d3 = ((x3 - x4) * dy - (y3 - y4) * dx).abs();
+
+    match (d2 > CURVE_COLLINEARITY_EPSILON, d3 > CURVE_COLLINEARITY_EPSILON) {
+        (false, false) => {
+            // All collinear OR p1 == p4
+            let k = dx * dx + dy * dy;
+            if k == 0.0 {
+                d2 = sq_distance(x1, y1, x2, y2);
+                d3 = sq_distance(x4, y4, x3, y3);
+            } else {
+                let k = 1.0 / k;
+                d2 = k * ((x2 - x1) * dx + (y2 - y1) * dy);
+                d3 = k * ((x3 - x1) * dx + (y3 - y1) * dy);
+                if d2 > 0.0 && d2 < 1.0 && d3 > 0.0 && d3 < 1.0 {
+                    // Simple collinear case, 1---2---3---4
+                    return;
+                }
+                d2 = if d2 <= 0.0 {
+                    sq_distance(x2, y2, x1, y1)
+                } else if d2 >= 1.0 {
+                    sq_distance(x2, y2, x4, y4)
+                } else {
+                    sq_distance(x2, y2, x1 + d2 * dx, y1 + d2 * dy)
+                };
+                d3 = if d3 <= 0.0 {
+                    sq_distance(x3, y3, x1, y1)
+                } else if d3 >= 1.0 {
+                    sq_distance(x3, y3, x4, y4)
+                } else {
+                    sq_distance(x3, y3, x1 + d3 * dx, y1 + d3 * dy)
+                };
+            }
+            if d2 > d3 {
+                if d2 < tol {
+                    out.push(Vertex::line_to(x2, y2));
+                    return;
+                }
+            } else if d3 < tol {
+                out.push(Vertex::line_to(x3, y3));
+                return;
+            }
+        }
+        // p1, p2, p4 are collinear, p3 is significant
+        (false, true) => {
+            if d3 * d3 <= tol * (dx * dx + dy * dy) {
+                out.push(Vertex::line_to(x23, y23));
+                return;
+            }
+        }
+        // p1, p3, p4 are collinear, p2 is significant
+        (true, false) => {
+            if d2 * d2 <= tol * (dx * dx + dy * dy) {
+                out.push(Vertex::line_to(x23, y23));
+                return;
+            }
+        }
+        // Regular case
+        (true, true) => {
+            if (d2 + d3) * (d2 + d3) <= tol * (dx * dx + dy * dy) {
+                out.push(Vertex::line_to(x23, y23));
+                return;
+            }
+        }
+    }
+    // Continue subdivision
+    curve4_recursive(out, tol, x1, y1, x12, y12, x123, y123, x1234, y1234, level + 1);
+    curve4_recursive(out, tol, x1234, y1234, x234, y234, x34, y34, x4, y4, level + 1);
+}
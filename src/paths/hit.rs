@@ -0,0 +1,161 @@
+// agrega::paths::hit
+//
+//! Hit testing
+//!
+//! Queries for picking vertex sources by position. Paths are tested on the
+//! geometry the rasterizer sees: curves are flattened and the coordinates
+//! are snapped to the subpixel grid.
+//!
+//! # Examples
+//! ```
+//! use agrega::{nearest_point, point_in_path, point_near_stroke, FillingRule, Path, Stroke};
+//!
+//! let mut path = Path::new();
+//! path.move_to( 0.0,  0.0);
+//! path.line_to(10.0,  0.0);
+//! path.line_to(10.0, 10.0);
+//! path.line_to( 0.0, 10.0);
+//! path.close_polygon();
+//!
+//! assert!(point_in_path(&path, 5.0, 5.0, FillingRule::NonZero));
+//! assert!(!point_in_path(&path, 15.0, 5.0, FillingRule::NonZero));
+//!
+//! let mut stroke = Stroke::new(path.clone());
+//! stroke.width(2.0);
+//! assert!(point_near_stroke(&stroke, 12.0, 5.0, 1.5));
+//! assert!(!point_near_stroke(&stroke, 13.0, 5.0, 1.5));
+//!
+//! let nearest = nearest_point(&path, 15.0, 5.0).unwrap();
+//! assert_eq!((nearest.x, nearest.y, nearest.distance), (10.0, 5.0, 5.0));
+//! ```
+//
+// TOC
+// - struct NearestPoint
+// - fn point_in_path
+// - fn point_near_stroke
+// - fn nearest_point
+
+use crate::{
+    paths::{contours, Contour, FlattenCurves, Stroke, Vertex, VertexSource},
+    FillingRule, POLY_SUBPIXEL_SCALE,
+};
+use alloc::vec::Vec;
+use devela::iif;
+#[allow(unused_imports)]
+use devela::ExtFloat;
+
+/// The point of a path nearest to a query point.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NearestPoint {
+    /// Position of the nearest point in the x-direction.
+    pub x: f64,
+    /// Position of the nearest point in the y-direction.
+    pub y: f64,
+    /// Distance between the query point and the nearest point.
+    pub distance: f64,
+    /// Distance along the path up to the nearest point,
+    /// as measured by [`PathMeasure`][crate::PathMeasure].
+    pub offset: f64,
+}
+
+/// Returns `true` if the point `(x, y)` is inside the filled area of `path`
+/// under the given filling `rule`.
+///
+/// Every subpath is implicitly closed, as a filled polygon.
+#[must_use]
+pub fn point_in_path<VS: VertexSource>(path: &VS, x: f64, y: f64, rule: FillingRule) -> bool {
+    contains(&raster_contours(path.vertices()), x, y, rule)
+}
+
+/// Returns `true` if the point `(x, y)` is within `distance` of the area
+/// covered by `stroke`.
+///
+/// The area is the filled outline of the stroke, so its width, joins and caps
+/// are taken into account. Points inside it are at distance 0.
+#[must_use]
+pub fn point_near_stroke<VS: VertexSource>(
+    stroke: &Stroke<VS>,
+    x: f64,
+    y: f64,
+    distance: f64,
+) -> bool {
+    // The stroke is generated and flattened only once for both tests
+    let raster = raster_contours(stroke.vertices());
+    contains(&raster, x, y, FillingRule::NonZero)
+        || nearest_on(&raster, x, y).is_some_and(|(p, ..)| p.distance <= distance)
+}
+
+/// Returns the point of the outline of `path` nearest to `(x, y)`.
+///
+/// The point is found on the segments snapped to the subpixel grid,
+/// while its offset is measured on the original ones, like [`PathMeasure`][crate::PathMeasure].
+///
+/// Returns `None` if the path has no segments.
+#[must_use]
+pub fn nearest_point<VS: VertexSource>(path: &VS, x: f64, y: f64) -> Option<NearestPoint> {
+    let vertices: Vec<_> = path.vertices().collect();
+    let (mut nearest, contour, i, t) =
+        nearest_on(&raster_contours(vertices.iter().copied()), x, y)?;
+    let measured = contours(vertices, 1.0);
+    let m = &measured[contour];
+    let offset: f64 = measured[..contour].iter().map(Contour::length).sum();
+    nearest.offset = offset + m.dists[i - 1] + (m.dists[i] - m.dists[i - 1]) * t;
+    Some(nearest)
+}
+
+/// Returns whether `(x, y)` is inside the `contours` under the filling `rule`.
+fn contains(contours: &[Contour], x: f64, y: f64, rule: FillingRule) -> bool {
+    let mut winding = 0_i64;
+    for c in contours {
+        let n = c.points.len();
+        // Closed contours already end on their first point
+        let edges = c.points.windows(2).map(|w| (w[0], w[1]));
+        let closing = (!c.closed).then(|| (c.points[n - 1], c.points[0]));
+        for ((x1, y1), (x2, y2)) in edges.chain(closing) {
+            let side = (x2 - x1) * (y - y1) - (x - x1) * (y2 - y1);
+            if y1 <= y {
+                if y2 > y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if y2 <= y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+    }
+    match rule {
+        FillingRule::NonZero => winding != 0,
+        FillingRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// Returns the point of the `contours` nearest to `(x, y)`, without its offset,
+/// with the index of its contour and segment, and its position on the segment.
+fn nearest_on(contours: &[Contour], x: f64, y: f64) -> Option<(NearestPoint, usize, usize, f64)> {
+    let mut nearest: Option<(NearestPoint, usize, usize, f64)> = None;
+    for (ci, c) in contours.iter().enumerate() {
+        for i in 1..c.points.len() {
+            let ((x1, y1), (x2, y2)) = (c.points[i - 1], c.points[i]);
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let len2 = dx * dx + dy * dy;
+            let t = iif![len2 > 0.0; ((x - x1) * dx + (y - y1) * dy) / len2; 0.0].clamp(0.0, 1.0);
+            let (px, py) = (x1 + dx * t, y1 + dy * t);
+            let distance = ((x - px).powi(2) + (y - py).powi(2)).sqrt();
+            if nearest.is_none_or(|(n, ..)| distance < n.distance) {
+                let point = NearestPoint { x: px, y: py, distance, offset: 0.0 };
+                nearest = Some((point, ci, i, t));
+            }
+        }
+    }
+    nearest
+}
+
+/// Returns the contours of the path snapped to the subpixel grid.
+fn raster_contours<I: Iterator<Item = Vertex<f64>>>(vertices: I) -> Vec<Contour> {
+    let scale = POLY_SUBPIXEL_SCALE as f64;
+    let snap = |mut v: Vertex<f64>| {
+        v.x = (v.x * scale).round() / scale;
+        v.y = (v.y * scale).round() / scale;
+        v
+    };
+    contours(FlattenCurves::new(vertices, 1.0).map(snap), 1.0)
+}
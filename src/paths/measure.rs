@@ -0,0 +1,194 @@
+// agrega::paths::measure
+//
+//! Path measurement
+//!
+//! # Examples
+//! ```
+//! use agrega::{Path, PathMeasure};
+//!
+//! let mut path = Path::new();
+//! path.move_to(  0.0, 0.0);
+//! path.line_to(100.0, 0.0);
+//! path.line_to(100.0, 50.0);
+//!
+//! let measure = PathMeasure::new(&path);
+//! assert_eq!(measure.total_length(), 150.0);
+//!
+//! // Point and tangent angle at the corner
+//! let (x, y, angle) = measure.point_at(100.0).unwrap();
+//! assert_eq!((x, y), (100.0, 0.0));
+//! assert_eq!(angle, core::f64::consts::FRAC_PI_2);
+//!
+//! // The last third of the path
+//! let tail = measure.segment(100.0, 150.0);
+//! assert_eq!(tail.vertices.len(), 2);
+//! ```
+//
+// TOC
+// - struct PathMeasure
+// - struct Contour
+// - fn contours
+
+use crate::paths::{subpaths, FlattenCurves, Path, PathCommand, Vertex, VertexSource};
+use alloc::{vec, vec::Vec};
+use devela::iif;
+#[allow(unused_imports)]
+use devela::ExtFloat;
+
+/// Measures the length of a [`VertexSource`] along its subpaths.
+///
+/// Curves are flattened before measuring, and closed subpaths include their
+/// closing segment. Distances run continuously over all the subpaths in order,
+/// without counting the moves between them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PathMeasure {
+    contours: Vec<Contour>,
+    /// Cumulative length at the start of each contour.
+    starts: Vec<f64>,
+    total: f64,
+}
+
+impl PathMeasure {
+    /// Measures the given vertex source.
+    #[must_use]
+    pub fn new<VS: VertexSource>(source: &VS) -> Self {
+        Self::with_approximation_scale(source, 1.0)
+    }
+
+    /// Measures the given vertex source, flattening curves with `scale`.
+    #[must_use]
+    pub fn with_approximation_scale<VS: VertexSource>(source: &VS, scale: f64) -> Self {
+        let contours = contours(source.vertices(), scale);
+        let mut starts = Vec::with_capacity(contours.len());
+        let mut total = 0.0;
+        for c in &contours {
+            starts.push(total);
+            total += c.length();
+        }
+        Self { contours, starts, total }
+    }
+
+    /// Returns the total length of all the subpaths.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn total_length(&self) -> f64 { self.total }
+
+    /// Returns the length of each subpath, in order.
+    #[must_use]
+    pub fn lengths(&self) -> Vec<f64> {
+        self.contours.iter().map(Contour::length).collect()
+    }
+
+    /// Returns the position `(x, y)` and the tangent angle in radians
+    /// at `distance` along the path.
+    ///
+    /// The distance is clamped to the length of the path.
+    /// Returns `None` if the path has no segments.
+    #[must_use]
+    pub fn point_at(&self, distance: f64) -> Option<(f64, f64, f64)> {
+        let (c, d) = self.locate(distance)?;
+        let c = &self.contours[c];
+        // Segment containing d, preferring the one that starts at d
+        let i = c.dists.partition_point(|&s| s <= d).clamp(1, c.dists.len() - 1);
+        let ((x1, y1), (x2, y2)) = (c.points[i - 1], c.points[i]);
+        let len = c.dists[i] - c.dists[i - 1];
+        let t = iif![len > 0.0; (d - c.dists[i - 1]) / len; 0.0];
+        Some((x1 + (x2 - x1) * t, y1 + (y2 - y1) * t, (y2 - y1).atan2(x2 - x1)))
+    }
+
+    /// Returns the sub-path between the distances `start` and `end`.
+    ///
+    /// Every subpath crossed by the range starts a new `MoveTo`, skipping the
+    /// ones it only touches, and the closed subpaths it covers whole are closed.
+    /// Returns an empty path if `end <= start`.
+    #[must_use]
+    pub fn segment(&self, start: f64, end: f64) -> Path {
+        let mut path = Path::new();
+        let (start, end) = (start.max(0.0), end.min(self.total));
+        iif![end <= start; return path];
+        for (c, &offset) in self.contours.iter().zip(&self.starts) {
+            let (s, e) = ((start - offset).max(0.0), (end - offset).min(c.length()));
+            iif![e <= s; continue];
+            if c.closed && s == 0.0 && e == c.length() {
+                // The closed contour already ends on its first point
+                let (&(x, y), rest) = c.points[..c.points.len() - 1].split_first().unwrap();
+                path.move_to(x, y);
+                rest.iter().for_each(|&(x, y)| path.line_to(x, y));
+                path.close_polygon();
+                continue;
+            }
+            let (x, y) = c.interpolate(s);
+            path.move_to(x, y);
+            for i in 0..c.points.len() {
+                if c.dists[i] > s && c.dists[i] < e {
+                    path.line_to(c.points[i].0, c.points[i].1);
+                }
+            }
+            let (x, y) = c.interpolate(e);
+            path.line_to(x, y);
+        }
+        path
+    }
+
+    /// Returns the contour index and the distance within it.
+    fn locate(&self, distance: f64) -> Option<(usize, f64)> {
+        iif![self.contours.is_empty(); return None];
+        let d = distance.clamp(0.0, self.total);
+        let c = self.starts.partition_point(|&s| s <= d).max(1) - 1;
+        Some((c, d - self.starts[c]))
+    }
+}
+
+/// A flattened subpath, with the cumulative distance at each point.
+///
+/// Closed subpaths repeat their first point at the end.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Contour {
+    pub points: Vec<(f64, f64)>,
+    pub dists: Vec<f64>,
+    pub closed: bool,
+}
+
+impl Contour {
+    #[inline]
+    pub fn length(&self) -> f64 {
+        self.dists[self.dists.len() - 1]
+    }
+    /// Returns the point at distance `d`, which must be within the contour.
+    fn interpolate(&self, d: f64) -> (f64, f64) {
+        let i = self.dists.partition_point(|&s| s < d).clamp(1, self.dists.len() - 1);
+        let ((x1, y1), (x2, y2)) = (self.points[i - 1], self.points[i]);
+        let len = self.dists[i] - self.dists[i - 1];
+        let t = iif![len > 0.0; (d - self.dists[i - 1]) / len; 0.0];
+        (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t)
+    }
+}
+
+/// Splits the vertices into flattened contours, skipping the empty ones.
+pub(crate) fn contours<I>(src: I, approx_scale: f64) -> Vec<Contour>
+where
+    I: IntoIterator<Item = Vertex<f64>>,
+{
+    let mut contours = vec![];
+    for sub in subpaths(FlattenCurves::new(src.into_iter(), approx_scale)) {
+        let mut c = Contour::default();
+        for v in &sub {
+            match v.cmd {
+                PathCommand::Close => c.closed = true,
+                PathCommand::Stop => {}
+                _ => c.points.push((v.x, v.y)),
+            }
+        }
+        if c.closed && !c.points.is_empty() {
+            c.points.push(c.points[0]);
+        }
+        iif![c.points.len() < 2; continue];
+        let mut total = 0.0;
+        c.dists.push(0.0);
+        for w in c.points.windows(2) {
+            total += ((w[1].0 - w[0].0).powi(2) + (w[1].1 - w[0].1).powi(2)).sqrt();
+            c.dists.push(total);
+        }
+        contours.push(c);
+    }
+    contours
+}
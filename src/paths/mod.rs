@@ -6,6 +6,7 @@
 // - trait VertexSource
 // - struct Vertex
 // - struct Path
+// - struct SelectedPath
 // - fn arrange_orientations
 // - fn invert_polygon
 // - fn perceive_polygon_orientation
@@ -17,14 +18,23 @@ use devela::iif;
 use devela::ExtFloat;
 
 mod clip;
+mod curve;
+mod simplify;
+mod spline;
 mod transform;
-pub use {clip::*, transform::*};
+pub use {clip::*, curve::*, simplify::*, spline::*, transform::*};
+
+#[cfg(test)]
+mod tests;
 
 #[cfg(any(feature = "std", feature = "no_std"))]
 crate::items! {
+    mod hit;
+    mod measure;
     mod shapes;
     mod stroke;
-    pub use {shapes::*, stroke::*};
+    mod var_stroke;
+    pub use {hit::*, measure::*, shapes::*, stroke::*, var_stroke::*};
 }
 
 /// Represents the orientation of a polygon path.
@@ -49,8 +59,18 @@ pub enum PathCommand {
 
     /// Closes the current path or subpath by connecting the last point to the first.
     Close,
-    //Curve3,
-    //Curve4,
+
+    /// Quadratic Bézier curve, as a control vertex followed by an end vertex.
+    ///
+    /// Consumers that don't flatten curves treat these vertices as [`LineTo`][Self::LineTo];
+    /// wrap the source in a [`Curve`] converter to get line segments.
+    Curve3,
+
+    /// Cubic Bézier curve, as two control vertices followed by an end vertex.
+    ///
+    /// Consumers that don't flatten curves treat these vertices as [`LineTo`][Self::LineTo];
+    /// wrap the source in a [`Curve`] converter to get line segments.
+    Curve4,
     //CurveN,
     //Catrom,
     //UBSpline,
@@ -58,15 +78,27 @@ pub enum PathCommand {
 }
 
 /// A source of vertex points.
+///
+/// Vertices are streamed through [`vertices`][Self::vertices], so chains of
+/// converters don't allocate the whole path at every stage.
 pub trait VertexSource {
-    // /// Rewind the vertex source (unused)
-    // fn rewind(&self) {}
-
-    /// Get the cloned values from the source.
+    /// Returns an iterator over the vertices of the source.
     ///
-    /// This could be turned into an iterator
+    /// Every call starts again from the first vertex, like AGG's `rewind`.
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>>;
+
+    /// Collects the vertices of the source into a vector.
     #[must_use]
-    fn xconvert(&self) -> alloc::vec::Vec<Vertex<f64>>;
+    fn xconvert(&self) -> alloc::vec::Vec<Vertex<f64>> {
+        self.vertices().collect()
+    }
+}
+
+impl<T: VertexSource> VertexSource for &T {
+    #[inline]
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        (**self).vertices()
+    }
 }
 
 /// A vertex in the path with coordinates `(x, y)` and an associated [`PathCommand`].
@@ -132,6 +164,8 @@ impl Vertex<f64> {
     }
 
     /// Split vertices of a path into individual segments at MoveTo boundaries.
+    ///
+    /// A `Stop` command ends the current segment, without being part of it.
     #[must_use]
     pub fn split(vertices: &[Vertex<f64>]) -> Vec<(usize, usize)> {
         let (mut start, mut end) = (None, None);
@@ -142,16 +176,21 @@ impl Vertex<f64> {
                     PathCommand::MoveTo => {
                         start = Some(i);
                     }
-                    PathCommand::LineTo | PathCommand::Close | PathCommand::Stop => {}
+                    PathCommand::LineTo
+                    | PathCommand::Curve3
+                    | PathCommand::Curve4
+                    | PathCommand::Close
+                    | PathCommand::Stop => {}
                 },
                 (Some(_), None) => match v.cmd {
                     PathCommand::MoveTo => {
                         start = Some(i);
                     }
-                    PathCommand::LineTo => {
+                    PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4 => {
                         end = Some(i);
                     }
-                    PathCommand::Close | PathCommand::Stop => end = Some(i),
+                    PathCommand::Close => end = Some(i),
+                    PathCommand::Stop => start = None,
                 },
                 (Some(s), Some(e)) => match v.cmd {
                     PathCommand::MoveTo => {
@@ -159,7 +198,14 @@ impl Vertex<f64> {
                         start = Some(i);
                         end = None;
                     }
-                    PathCommand::LineTo | PathCommand::Close | PathCommand::Stop => end = Some(i),
+                    PathCommand::LineTo
+                    | PathCommand::Curve3
+                    | PathCommand::Curve4
+                    | PathCommand::Close => end = Some(i),
+                    PathCommand::Stop => {
+                        pairs.push((s, e));
+                        (start, end) = (None, None);
+                    }
                 },
                 (None, Some(_)) => unreachable!("oh on bad state!"),
             }
@@ -169,6 +215,26 @@ impl Vertex<f64> {
     }
 }
 
+/// Groups a stream of vertices into subpaths, one at a time.
+///
+/// Yields the same subpaths as [`Vertex::split`], each starting with a `MoveTo`.
+pub(crate) fn subpaths<I>(vertices: I) -> impl Iterator<Item = Vec<Vertex<f64>>>
+where
+    I: IntoIterator<Item = Vertex<f64>>,
+{
+    let mut src = vertices.into_iter().peekable();
+    core::iter::from_fn(move || loop {
+        let start = src.by_ref().find(|v| v.cmd == PathCommand::MoveTo)?;
+        let mut sub = vec![start];
+        while let Some(v) =
+            src.next_if(|v| !matches![v.cmd, PathCommand::MoveTo | PathCommand::Stop])
+        {
+            sub.push(v);
+        }
+        iif![sub.len() > 1; return Some(sub)];
+    })
+}
+
 /// Represents a path of connected vertices, each with an associated command.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Path {
@@ -179,8 +245,8 @@ pub struct Path {
 
 impl VertexSource for Path {
     #[inline]
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.vertices.clone()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.vertices.iter().copied()
     }
 }
 
@@ -217,12 +283,57 @@ impl Path {
         self.vertices.push(Vertex::line_to(x, y));
     }
 
+    /// Draws a quadratic Bézier curve from the current position to `(x, y)`,
+    /// using `(x_ctrl, y_ctrl)` as the control point.
+    #[inline]
+    pub fn curve3(&mut self, x_ctrl: f64, y_ctrl: f64, x: f64, y: f64) {
+        self.vertices.push(Vertex::new(x_ctrl, y_ctrl, PathCommand::Curve3));
+        self.vertices.push(Vertex::new(x, y, PathCommand::Curve3));
+    }
+
+    /// Draws a cubic Bézier curve from the current position to `(x, y)`,
+    /// using `(x_ctrl1, y_ctrl1)` and `(x_ctrl2, y_ctrl2)` as the control points.
+    #[inline]
+    pub fn curve4(
+        &mut self,
+        x_ctrl1: f64,
+        y_ctrl1: f64,
+        x_ctrl2: f64,
+        y_ctrl2: f64,
+        x: f64,
+        y: f64,
+    ) {
+        self.vertices.push(Vertex::new(x_ctrl1, y_ctrl1, PathCommand::Curve4));
+        self.vertices.push(Vertex::new(x_ctrl2, y_ctrl2, PathCommand::Curve4));
+        self.vertices.push(Vertex::new(x, y, PathCommand::Curve4));
+    }
+
+    /// Starts a new path in the storage, and returns its id for [`select`][Self::select].
+    ///
+    /// Ends the previous path with a `Stop` command, so the whole storage
+    /// is read as its first path alone.
+    pub fn start_new_path(&mut self) -> usize {
+        if self.vertices.last().is_some_and(|v| v.cmd != PathCommand::Stop) {
+            self.vertices.push(Vertex::new(0.0, 0.0, PathCommand::Stop));
+        }
+        self.vertices.len()
+    }
+
+    /// Returns the path with the given `path_id`, from [`start_new_path`][Self::start_new_path].
+    ///
+    /// Its vertices go from `path_id` up to the next `Stop` command.
+    #[inline]
+    #[must_use]
+    pub fn select(&self, path_id: usize) -> SelectedPath<'_> {
+        SelectedPath { path: self, path_id }
+    }
+
     /// Closes the current polygon, connecting the last point to the starting point.
     pub fn close_polygon(&mut self) {
         iif![self.vertices.is_empty(); return];
         let n = self.vertices.len();
         let last = self.vertices[n - 1];
-        if last.cmd == PathCommand::LineTo {
+        if matches![last.cmd, PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4] {
             self.vertices.push(Vertex::close_polygon(last.x, last.y));
         }
     }
@@ -266,6 +377,25 @@ impl Path {
     }
 }
 
+/// One of the paths of a [`Path`] storage, selected by its id.
+///
+/// Returned by [`Path::select`].
+#[derive(Clone, Copy, Debug)]
+pub struct SelectedPath<'a> {
+    path: &'a Path,
+    path_id: usize,
+}
+
+impl VertexSource for SelectedPath<'_> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let start = self.path_id.min(self.path.vertices.len());
+        self.path.vertices[start..]
+            .iter()
+            .copied()
+            .take_while(|v| v.cmd != PathCommand::Stop)
+    }
+}
+
 // Adjusts the orientation of all polygons in the path to match the specified direction.
 //
 // This function detects the orientation of each polygon in the path and
@@ -313,16 +443,17 @@ pub fn perceive_polygon_orientation(vertices: &[Vertex<f64>]) -> PathOrientation
 ///
 /// This function iterates over all vertices in the path to find the minimum
 /// and maximum coordinates, returning the smallest rectangle that contains all vertices.
+///
+/// The `Close` and `Stop` commands are not vertices, and their coordinates are skipped.
 #[must_use]
 pub fn bounding_rect<VS: VertexSource>(path: &VS) -> Option<Rectangle<f64>> {
-    let pts = path.xconvert();
-    if pts.is_empty() {
-        None
-    } else {
-        let mut r = Rectangle::new(pts[0].x, pts[0].y, pts[0].x, pts[0].y);
-        for p in pts {
-            r.expand(p.x, p.y);
-        }
-        Some(r)
+    let mut pts = path
+        .vertices()
+        .filter(|v| !matches![v.cmd, PathCommand::Close | PathCommand::Stop]);
+    let first = pts.next()?;
+    let mut r = Rectangle::new(first.x, first.y, first.x, first.y);
+    for p in pts {
+        r.expand(p.x, p.y);
     }
+    Some(r)
 }
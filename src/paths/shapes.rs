@@ -22,8 +22,8 @@ pub struct Ellipse {
 }
 
 impl VertexSource for Ellipse {
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.path.vertices.clone()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.path.vertices.iter().copied()
     }
 }
 
@@ -91,8 +91,8 @@ pub struct RoundedRect {
 
 impl VertexSource for RoundedRect {
     #[inline]
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.path.vertices.clone()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.path.vertices.iter().copied()
     }
 }
 
@@ -182,8 +182,8 @@ pub struct Arc {
 
 impl VertexSource for Arc {
     #[inline]
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.path.vertices.clone()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.path.vertices.iter().copied()
     }
 }
 
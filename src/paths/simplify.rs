@@ -0,0 +1,310 @@
+// agrega::paths::simplify
+//
+//! Path simplification
+//!
+//! Converters that reduce the number of vertices of a source. Each one can
+//! report how many vertices it removes through its `removed` method.
+//!
+//! Subpaths containing curve commands are passed through unchanged.
+//!
+//! # Examples
+//! ```
+//! use agrega::{DouglasPeucker, Path, VertexSource};
+//!
+//! // A noisy, almost straight line
+//! let mut path = Path::new();
+//! path.move_to(0.0, 0.0);
+//! for i in 1..=100 {
+//!     path.line_to(i as f64, if i % 2 == 0 { 0.05 } else { -0.05 });
+//! }
+//!
+//! let mut simple = DouglasPeucker::new(path);
+//! simple.tolerance(0.25);
+//! assert_eq!(simple.xconvert().len(), 2);
+//! assert_eq!(simple.removed(), 99);
+//! ```
+//
+// TOC
+// - struct DouglasPeucker
+// - struct Visvalingam
+// - struct VertexFilter
+// - struct ClosePolygon
+
+use crate::paths::{subpaths, PathCommand, Vertex, VertexSource};
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Reverse;
+use devela::iif;
+#[allow(unused_imports)]
+use devela::ExtFloat;
+
+/// Douglas-Peucker simplification converter.
+///
+/// Removes the vertices that deviate less than a tolerance distance
+/// from the simplified line.
+#[derive(Debug)]
+pub struct DouglasPeucker<S: VertexSource> {
+    source: S,
+    tolerance: f64,
+}
+
+impl<S: VertexSource> VertexSource for DouglasPeucker<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.convert(&sub).0)
+    }
+}
+
+impl<S: VertexSource> DouglasPeucker<S> {
+    /// Create a new Douglas-Peucker converter from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, tolerance: 0.5 }
+    }
+    /// Set the maximum distance of the removed vertices to the result, 0.5
+    pub fn tolerance(&mut self, tolerance: f64) {
+        self.tolerance = tolerance;
+    }
+    /// Returns the number of vertices removed from the source.
+    #[must_use]
+    pub fn removed(&self) -> usize {
+        subpaths(self.source.vertices()).map(|sub| self.convert(&sub).1).sum()
+    }
+    fn convert(&self, sub: &[Vertex<f64>]) -> (Vec<Vertex<f64>>, usize) {
+        map_polyline(sub, |pts, closed| {
+            // Closed polylines are simplified as a ring ending on its first point
+            let ring: Vec<_> = pts.iter().chain(iif![closed; pts.first(); None]).copied().collect();
+            let mut keep = vec![false; ring.len()];
+            keep[0] = true;
+            keep[ring.len() - 1] = true;
+            douglas_peucker(&ring, self.tolerance, &mut keep);
+            let mut out: Vec<_> =
+                ring.iter().zip(keep).filter(|(_, k)| *k).map(|(p, _)| *p).collect();
+            iif![closed; { out.pop(); }];
+            out
+        })
+    }
+}
+
+fn douglas_peucker(pts: &[(f64, f64)], tolerance: f64, keep: &mut [bool]) {
+    // Explicit stack of ranges to avoid deep recursion on long inputs
+    let mut stack = vec![(0, pts.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        iif![last <= first + 1; continue];
+        let (mut max_dist, mut index) = (-1.0, first);
+        for (i, &p) in pts.iter().enumerate().take(last).skip(first + 1) {
+            let d = segment_distance(p, pts[first], pts[last]);
+            iif![d > max_dist; (max_dist, index) = (d, i)];
+        }
+        if max_dist > tolerance {
+            keep[index] = true;
+            stack.push((first, index));
+            stack.push((index, last));
+        }
+    }
+}
+
+/// Visvalingam-Whyatt simplification converter.
+///
+/// Repeatedly removes the vertex forming the smallest triangle with its
+/// neighbours, while that area is below a threshold.
+#[derive(Debug)]
+pub struct Visvalingam<S: VertexSource> {
+    source: S,
+    min_area: f64,
+}
+
+impl<S: VertexSource> VertexSource for Visvalingam<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.convert(&sub).0)
+    }
+}
+
+impl<S: VertexSource> Visvalingam<S> {
+    /// Create a new Visvalingam converter from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, min_area: 0.25 }
+    }
+    /// Set the area under which vertices are removed, 0.25
+    pub fn min_area(&mut self, area: f64) {
+        self.min_area = area;
+    }
+    /// Returns the number of vertices removed from the source.
+    #[must_use]
+    pub fn removed(&self) -> usize {
+        subpaths(self.source.vertices()).map(|sub| self.convert(&sub).1).sum()
+    }
+    fn convert(&self, sub: &[Vertex<f64>]) -> (Vec<Vertex<f64>>, usize) {
+        map_polyline(sub, |pts, closed| visvalingam(pts, closed, self.min_area))
+    }
+}
+
+fn visvalingam(pts: &[(f64, f64)], closed: bool, min_area: f64) -> Vec<(f64, f64)> {
+    let n = pts.len();
+    let min_len = iif![closed; 3; 2];
+    iif![n <= min_len; return pts.to_vec()];
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let mut removed = vec![false; n];
+    // Bumped each time the area of a vertex changes, to skip stale entries
+    let mut version = vec![0_u32; n];
+    let area = |i: usize, prev: &[usize], next: &[usize]| {
+        let ((x1, y1), (x2, y2), (x3, y3)) = (pts[prev[i]], pts[i], pts[next[i]]);
+        ((x2 - x1) * (y3 - y1) - (x3 - x1) * (y2 - y1)).abs() / 2.0
+    };
+    let movable = |i: usize| closed || (i != 0 && i != n - 1);
+    let mut heap = BinaryHeap::new();
+    for i in (0..n).filter(|&i| movable(i)) {
+        // The bits of non-negative floats sort in the same order as their values
+        heap.push(Reverse((area(i, &prev, &next).to_bits(), i, 0)));
+    }
+    let mut remaining = n;
+    while let Some(Reverse((bits, i, ver))) = heap.pop() {
+        iif![removed[i] || ver != version[i]; continue];
+        iif![f64::from_bits(bits) >= min_area || remaining <= min_len; break];
+        removed[i] = true;
+        remaining -= 1;
+        let (p, nx) = (prev[i], next[i]);
+        next[p] = nx;
+        prev[nx] = p;
+        for j in [p, nx] {
+            if movable(j) {
+                version[j] += 1;
+                heap.push(Reverse((area(j, &prev, &next).to_bits(), j, version[j])));
+            }
+        }
+    }
+    pts.iter().zip(removed).filter(|(_, r)| !r).map(|(p, _)| *p).collect()
+}
+
+/// Coincident vertex filter.
+///
+/// Removes the vertices closer than a minimum distance to the previous kept
+/// one, like AGG's `vertex_dist` filtering. Closed subpaths also lose a last
+/// vertex coinciding with the first.
+#[derive(Debug)]
+pub struct VertexFilter<S: VertexSource> {
+    source: S,
+    min_distance: f64,
+}
+
+impl<S: VertexSource> VertexSource for VertexFilter<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.convert(&sub).0)
+    }
+}
+
+impl<S: VertexSource> VertexFilter<S> {
+    /// Create a new vertex filter from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, min_distance: 1e-14 }
+    }
+    /// Set the distance under which vertices are considered coincident, 1e-14
+    pub fn min_distance(&mut self, distance: f64) {
+        self.min_distance = distance;
+    }
+    /// Returns the number of vertices removed from the source.
+    #[must_use]
+    pub fn removed(&self) -> usize {
+        subpaths(self.source.vertices()).map(|sub| self.convert(&sub).1).sum()
+    }
+    fn convert(&self, sub: &[Vertex<f64>]) -> (Vec<Vertex<f64>>, usize) {
+        map_polyline(sub, |pts, closed| {
+            let near = |a: (f64, f64), b: (f64, f64)| {
+                ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt() <= self.min_distance
+            };
+            let mut out: Vec<(f64, f64)> = Vec::with_capacity(pts.len());
+            for &p in pts {
+                iif![out.last().is_none_or(|&l| !near(l, p)); out.push(p)];
+            }
+            while closed && out.len() > 1 && near(out[0], out[out.len() - 1]) {
+                out.pop();
+            }
+            out
+        })
+    }
+}
+
+/// Polygon closing normalization converter.
+///
+/// Either closes every subpath, or removes every `Close` command.
+/// When closing, a last vertex equal to the first one is dropped,
+/// since the `Close` command already goes back to it.
+#[derive(Debug)]
+pub struct ClosePolygon<S: VertexSource> {
+    source: S,
+    close: bool,
+}
+
+impl<S: VertexSource> VertexSource for ClosePolygon<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.convert(&sub).0)
+    }
+}
+
+impl<S: VertexSource> ClosePolygon<S> {
+    /// Create a new converter closing every subpath of a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, close: true }
+    }
+    /// Set whether subpaths are closed (default) or left open
+    pub fn close(&mut self, close: bool) {
+        self.close = close;
+    }
+    /// Returns the number of vertices removed from the source,
+    /// including the removed `Close` commands.
+    #[must_use]
+    pub fn removed(&self) -> usize {
+        subpaths(self.source.vertices()).map(|sub| self.convert(&sub).1).sum()
+    }
+    fn convert(&self, sub: &[Vertex<f64>]) -> (Vec<Vertex<f64>>, usize) {
+        let closes = sub.iter().filter(|v| v.cmd == PathCommand::Close).count();
+        let mut verts: Vec<_> =
+            sub.iter().filter(|v| v.cmd != PathCommand::Close).copied().collect();
+        if !self.close {
+            return (verts, closes);
+        }
+        let mut removed = closes.saturating_sub(1);
+        let (first, last) = (verts[0], verts[verts.len() - 1]);
+        if verts.len() > 2 && (first.x, first.y) == (last.x, last.y) {
+            verts.pop();
+            removed += 1;
+        }
+        let last = verts[verts.len() - 1];
+        verts.push(Vertex::close_polygon(last.x, last.y));
+        (verts, removed)
+    }
+}
+
+/// Applies `f` to the points of a subpath, unless it contains curves.
+///
+/// Returns the new vertices and the number of points removed.
+fn map_polyline<F>(sub: &[Vertex<f64>], f: F) -> (Vec<Vertex<f64>>, usize)
+where
+    F: FnOnce(&[(f64, f64)], bool) -> Vec<(f64, f64)>,
+{
+    if sub.iter().any(|v| matches![v.cmd, PathCommand::Curve3 | PathCommand::Curve4]) {
+        return (sub.to_vec(), 0);
+    }
+    let close = sub.iter().find(|v| v.cmd == PathCommand::Close);
+    let pts: Vec<_> = sub
+        .iter()
+        .filter(|v| matches![v.cmd, PathCommand::MoveTo | PathCommand::LineTo])
+        .map(|v| (v.x, v.y))
+        .collect();
+    let new = f(&pts, close.is_some());
+    let removed = pts.len() - new.len();
+    let mut out = Vec::with_capacity(new.len() + 1);
+    for (i, &(x, y)) in new.iter().enumerate() {
+        out.push(iif![i == 0; Vertex::move_to(x, y); Vertex::line_to(x, y)]);
+    }
+    if let (Some(_), Some(&(x, y))) = (close, new.last()) {
+        out.push(Vertex::close_polygon(x, y));
+    }
+    (out, removed)
+}
+
+/// Distance from `p` to the segment `a`-`b`.
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len2 = dx * dx + dy * dy;
+    let t = iif![len2 > 0.0; ((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len2; 0.0].clamp(0.0, 1.0);
+    ((p.0 - a.0 - dx * t).powi(2) + (p.1 - a.1 - dy * t).powi(2)).sqrt()
+}
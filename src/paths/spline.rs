@@ -0,0 +1,336 @@
+// agrega::paths::spline
+//
+//! Spline and smooth polygon generators
+//!
+//! Vertex generators that turn the vertices of a source, taken as control
+//! polygons, into smooth curves. Open subpaths produce open curves while
+//! closed subpaths produce closed ones.
+//!
+//! The output is flattened into line segments by default, or emitted as
+//! [`Curve3`][PathCommand::Curve3] and [`Curve4`][PathCommand::Curve4]
+//! commands with `emit_curves(true)`.
+//!
+//! # Examples
+//! ```
+//! use agrega::{Path, PathCommand, SmoothPoly, VertexSource};
+//!
+//! let mut data = Path::new();
+//! data.move_to(  0.0, 50.0);
+//! data.line_to( 40.0, 10.0);
+//! data.line_to( 80.0, 70.0);
+//! data.line_to(120.0, 30.0);
+//!
+//! let mut smooth = SmoothPoly::new(data);
+//! smooth.smooth_value(0.8);
+//! let lines = smooth.xconvert();
+//! assert_eq!(lines[0].cmd, PathCommand::MoveTo);
+//! assert_eq!((lines[lines.len() - 1].x, lines[lines.len() - 1].y), (120.0, 30.0));
+//!
+//! smooth.emit_curves(true);
+//! let curves = smooth.xconvert();
+//! assert_eq!(curves[1].cmd, PathCommand::Curve3);
+//! ```
+//
+// TOC
+// - struct BSpline
+// - struct Catrom
+// - struct SmoothPoly
+
+use crate::paths::{curve3_flatten, curve4_flatten, subpaths, PathCommand, Vertex, VertexSource};
+use alloc::{vec, vec::Vec};
+use devela::iif;
+#[allow(unused_imports)]
+use devela::ExtFloat;
+
+/// Distance under which consecutive control points are merged.
+const VERTEX_DIST_EPSILON: f64 = 1e-14;
+
+/// Uniform cubic B-spline generator.
+///
+/// The curve is approximating: it doesn't go through the control points,
+/// except for the end points of open subpaths, which are clamped.
+#[derive(Debug)]
+pub struct BSpline<S: VertexSource> {
+    source: S,
+    approx_scale: f64,
+    emit_curves: bool,
+}
+
+impl<S: VertexSource> VertexSource for BSpline<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| {
+            let (pts, closed) = control_polygon(&sub);
+            let mut out = vec![];
+            if pts.len() < 3 {
+                polyline(&mut out, &pts, closed);
+                return out;
+            }
+            let n = pts.len();
+            let p = |i: isize| -> (f64, f64) {
+                if closed {
+                    pts[i.rem_euclid(n as isize) as usize]
+                } else {
+                    pts[i.clamp(0, n as isize - 1) as usize]
+                }
+            };
+            // Open curves repeat their end points so they start and end on them
+            let first = iif![closed; 0; -1];
+            let mut emitter = Emitter::new(&mut out, self.emit_curves, self.approx_scale);
+            for i in first..n as isize {
+                let (p0, p1, p2, p3) = (p(i - 1), p(i), p(i + 1), p(i + 2));
+                let b0 = ((p0.0 + 4.0 * p1.0 + p2.0) / 6.0, (p0.1 + 4.0 * p1.1 + p2.1) / 6.0);
+                let b1 = ((2.0 * p1.0 + p2.0) / 3.0, (2.0 * p1.1 + p2.1) / 3.0);
+                let b2 = ((p1.0 + 2.0 * p2.0) / 3.0, (p1.1 + 2.0 * p2.1) / 3.0);
+                let b3 = ((p1.0 + 4.0 * p2.0 + p3.0) / 6.0, (p1.1 + 4.0 * p2.1 + p3.1) / 6.0);
+                iif![i == first; emitter.start(b0)];
+                emitter.curve4(b1, b2, b3);
+            }
+            emitter.end(closed);
+            out
+        })
+    }
+}
+
+impl<S: VertexSource> BSpline<S> {
+    /// Create a new B-spline generator from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, approx_scale: 1.0, emit_curves: false }
+    }
+    /// Set approximation scale used to flatten the curves
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+    /// Emit curve commands instead of flattened line segments
+    pub fn emit_curves(&mut self, curves: bool) {
+        self.emit_curves = curves;
+    }
+}
+
+/// Catmull-Rom spline generator.
+///
+/// The curve is interpolating: it goes through every control point.
+#[derive(Debug)]
+pub struct Catrom<S: VertexSource> {
+    source: S,
+    approx_scale: f64,
+    emit_curves: bool,
+}
+
+impl<S: VertexSource> VertexSource for Catrom<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| {
+            let (pts, closed) = control_polygon(&sub);
+            let mut out = vec![];
+            if pts.len() < 3 {
+                polyline(&mut out, &pts, closed);
+                return out;
+            }
+            let n = pts.len();
+            let p = |i: isize| -> (f64, f64) {
+                if closed {
+                    pts[i.rem_euclid(n as isize) as usize]
+                } else {
+                    pts[i.clamp(0, n as isize - 1) as usize]
+                }
+            };
+            let last = if closed { n } else { n - 1 };
+            let mut emitter = Emitter::new(&mut out, self.emit_curves, self.approx_scale);
+            for i in 0..last as isize {
+                let (p0, p1, p2, p3) = (p(i - 1), p(i), p(i + 1), p(i + 2));
+                let b1 = (p1.0 + (p2.0 - p0.0) / 6.0, p1.1 + (p2.1 - p0.1) / 6.0);
+                let b2 = (p2.0 - (p3.0 - p1.0) / 6.0, p2.1 - (p3.1 - p1.1) / 6.0);
+                iif![i == 0; emitter.start(p1)];
+                emitter.curve4(b1, b2, p2);
+            }
+            emitter.end(closed);
+            out
+        })
+    }
+}
+
+impl<S: VertexSource> Catrom<S> {
+    /// Create a new Catmull-Rom generator from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, approx_scale: 1.0, emit_curves: false }
+    }
+    /// Set approximation scale used to flatten the curves
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+    /// Emit curve commands instead of flattened line segments
+    pub fn emit_curves(&mut self, curves: bool) {
+        self.emit_curves = curves;
+    }
+}
+
+/// Smooth polygon generator, from AGG's `vcgen_smooth_poly1`.
+///
+/// Every edge of the control polygon is replaced by a curve through its
+/// end points, with control points derived from the neighbouring edges.
+/// The first and last edges of open subpaths become quadratic curves.
+#[derive(Debug)]
+pub struct SmoothPoly<S: VertexSource> {
+    source: S,
+    smooth_value: f64,
+    approx_scale: f64,
+    emit_curves: bool,
+}
+
+impl<S: VertexSource> VertexSource for SmoothPoly<S> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| {
+            let (pts, closed) = control_polygon(&sub);
+            let mut out = vec![];
+            if pts.len() < 3 {
+                polyline(&mut out, &pts, closed);
+                return out;
+            }
+            let n = pts.len();
+            let p = |i: usize| pts[i % n];
+            let dist = |i: usize| {
+                let (a, b) = (p(i), p(i + 1));
+                ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+            };
+            let last = if closed { n } else { n - 1 };
+            let mut emitter = Emitter::new(&mut out, self.emit_curves, self.approx_scale);
+            emitter.start(pts[0]);
+            for i in 0..last {
+                // calculate(prev, curr, next, next + 1)
+                let (v0, v1, v2, v3) = (p(i + n - 1), p(i), p(i + 1), p(i + 2));
+                let k1 = dist(i + n - 1) / (dist(i + n - 1) + dist(i));
+                let k2 = dist(i) / (dist(i) + dist(i + 1));
+                let xm1 = v0.0 + (v2.0 - v0.0) * k1;
+                let ym1 = v0.1 + (v2.1 - v0.1) * k1;
+                let xm2 = v1.0 + (v3.0 - v1.0) * k2;
+                let ym2 = v1.1 + (v3.1 - v1.1) * k2;
+                let ctrl1 = (
+                    v1.0 + self.smooth_value * (v2.0 - xm1),
+                    v1.1 + self.smooth_value * (v2.1 - ym1),
+                );
+                let ctrl2 = (
+                    v2.0 + self.smooth_value * (v1.0 - xm2),
+                    v2.1 + self.smooth_value * (v1.1 - ym2),
+                );
+                match (closed, i) {
+                    (false, 0) => emitter.curve3(ctrl2, v2),
+                    (false, i) if i == last - 1 => emitter.curve3(ctrl1, v2),
+                    _ => emitter.curve4(ctrl1, ctrl2, v2),
+                }
+            }
+            emitter.end(closed);
+            out
+        })
+    }
+}
+
+impl<S: VertexSource> SmoothPoly<S> {
+    /// Create a new smooth polygon generator from a Vertex Source
+    pub fn new(source: S) -> Self {
+        Self { source, smooth_value: 0.5, approx_scale: 1.0, emit_curves: false }
+    }
+    /// Set the smoothing factor, which is 1.0 by default
+    ///
+    /// A value of 0.0 produces the control polygon itself, while higher values
+    /// produce rounder curves. Values above 1.0 may produce loops.
+    pub fn smooth_value(&mut self, value: f64) {
+        self.smooth_value = value * 0.5;
+    }
+    /// Get the smoothing factor
+    #[must_use]
+    pub fn get_smooth_value(&self) -> f64 {
+        self.smooth_value * 2.0
+    }
+    /// Set approximation scale used to flatten the curves
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+    /// Emit curve commands instead of flattened line segments
+    pub fn emit_curves(&mut self, curves: bool) {
+        self.emit_curves = curves;
+    }
+}
+
+/// Returns the control polygon of a subpath and its closed state,
+/// merging coincident consecutive points.
+fn control_polygon(sub: &[Vertex<f64>]) -> (Vec<(f64, f64)>, bool) {
+    let mut closed = false;
+    let mut pts: Vec<(f64, f64)> = vec![];
+    for v in sub {
+        match v.cmd {
+            PathCommand::Close => closed = true,
+            PathCommand::Stop => {}
+            _ => {
+                let coincident = pts.last().is_some_and(|&(x, y)| {
+                    (v.x - x).powi(2) + (v.y - y).powi(2) <= VERTEX_DIST_EPSILON.powi(2)
+                });
+                iif![!coincident; pts.push((v.x, v.y))];
+            }
+        }
+    }
+    if closed && pts.len() > 1 {
+        let (f, l) = (pts[0], pts[pts.len() - 1]);
+        if (f.0 - l.0).powi(2) + (f.1 - l.1).powi(2) <= VERTEX_DIST_EPSILON.powi(2) {
+            pts.pop();
+        }
+    }
+    (pts, closed)
+}
+
+/// Emits a control polygon unchanged, for subpaths too short to smooth.
+fn polyline(out: &mut Vec<Vertex<f64>>, pts: &[(f64, f64)], closed: bool) {
+    for (i, &(x, y)) in pts.iter().enumerate() {
+        out.push(if i == 0 {
+            Vertex::move_to(x, y)
+        } else {
+            Vertex::line_to(x, y)
+        });
+    }
+    if closed && pts.len() > 1 {
+        let (x, y) = pts[pts.len() - 1];
+        out.push(Vertex::close_polygon(x, y));
+    }
+}
+
+/// Writes curves either as curve commands or as flattened line segments.
+struct Emitter<'a> {
+    out: &'a mut Vec<Vertex<f64>>,
+    curves: bool,
+    approx_scale: f64,
+    last: (f64, f64),
+}
+
+impl<'a> Emitter<'a> {
+    fn new(out: &'a mut Vec<Vertex<f64>>, curves: bool, approx_scale: f64) -> Self {
+        Self { out, curves, approx_scale, last: (0.0, 0.0) }
+    }
+    fn start(&mut self, p: (f64, f64)) {
+        self.out.push(Vertex::move_to(p.0, p.1));
+        self.last = p;
+    }
+    fn curve3(&mut self, c: (f64, f64), e: (f64, f64)) {
+        if self.curves {
+            self.out.push(Vertex::new(c.0, c.1, PathCommand::Curve3));
+            self.out.push(Vertex::new(e.0, e.1, PathCommand::Curve3));
+        } else {
+            let (x, y) = self.last;
+            curve3_flatten(self.out, x, y, c.0, c.1, e.0, e.1, self.approx_scale);
+        }
+        self.last = e;
+    }
+    fn curve4(&mut self, c1: (f64, f64), c2: (f64, f64), e: (f64, f64)) {
+        if self.curves {
+            self.out.push(Vertex::new(c1.0, c1.1, PathCommand::Curve4));
+            self.out.push(Vertex::new(c2.0, c2.1, PathCommand::Curve4));
+            self.out.push(Vertex::new(e.0, e.1, PathCommand::Curve4));
+        } else {
+            let (x, y) = self.last;
+            curve4_flatten(self.out, x, y, c1.0, c1.1, c2.0, c2.1, e.0, e.1, self.approx_scale);
+        }
+        self.last = e;
+    }
+    fn end(&mut self, closed: bool) {
+        if closed {
+            self.out.push(Vertex::close_polygon(self.last.0, self.last.1));
+        }
+    }
+}
@@ -26,7 +26,7 @@
 //! ```
 
 use crate::{
-    paths::{PathCommand, Vertex},
+    paths::{subpaths, PathCommand, Vertex},
     VertexSource,
 };
 use alloc::{vec, vec::Vec};
@@ -91,8 +91,8 @@ pub struct Stroke<T: VertexSource> {
 }
 
 impl<T: VertexSource> VertexSource for Stroke<T> {
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.stroke()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.stroke(&sub))
     }
 }
 
@@ -619,76 +619,66 @@ where
         }
         out
     }
-    /// Stroke the Vertex Source
+    /// Stroke a subpath of the Vertex Source
     ///
     /// There is lots of logic here and probably overly complex
     ///
-    fn stroke(&self) -> Vec<Vertex<f64>> {
-        let mut all_out = vec![];
-        // Get verticies from Vertex Source
-        let v0 = &self.source.xconvert();
-        // Split and loop along unique paths, ended by MoveTo's
-        let pairs = Vertex::split(v0);
-        for (m1, m2) in pairs {
-            let mut outf = vec![];
-            // Clean the current path, return new path
-            let v = clean_path(&v0[m1..=m2]);
-            if v.len() <= 1 {
-                continue;
-            }
-            // Check for Closed Path Element
-            let closed = is_path_closed(&v);
-            // Ignore Closed Tag Element
-            let n = if closed { v.len() - 1 } else { v.len() };
-            let (n1, n2) = if closed { (0, n) } else { (1, n - 1) };
+    fn stroke(&self, sub: &[Vertex<f64>]) -> Vec<Vertex<f64>> {
+        let mut outf = vec![];
+        // Clean the current path, return new path
+        let v = clean_path(sub);
+        if v.len() <= 1 {
+            return outf;
+        }
+        // Check for Closed Path Element
+        let closed = is_path_closed(&v);
+        // Ignore Closed Tag Element
+        let n = if closed { v.len() - 1 } else { v.len() };
+        let (n1, n2) = if closed { (0, n) } else { (1, n - 1) };
 
+        // Forward Path
+        if !closed {
+            outf.extend(self.calc_cap(&v[0], &v[1]));
+        }
+        for i in n1..n2 {
             // Forward Path
-            if !closed {
-                outf.extend(self.calc_cap(&v[0], &v[1]));
-            }
-            for i in n1..n2 {
-                // Forward Path
-                outf.extend(self.calc_join(&v[prev!(i, n)], &v[curr!(i, n)], &v[next!(i, n)]));
-            }
-            if closed {
-                // Close the polygon
-                let n = outf.len();
-                let last = outf[n - 1];
-                outf.push(Vertex::close_polygon(last.x, last.y));
-            }
+            outf.extend(self.calc_join(&v[prev!(i, n)], &v[curr!(i, n)], &v[next!(i, n)]));
+        }
+        if closed {
+            // Close the polygon
+            let n = outf.len();
+            let last = outf[n - 1];
+            outf.push(Vertex::close_polygon(last.x, last.y));
+        }
 
+        // Backward Path
+        let mut outb = vec![];
+        if !closed {
+            outb.extend(self.calc_cap(&v[n - 1], &v[n - 2])); // End Cap
+        }
+        for i in (n1..n2).rev() {
             // Backward Path
-            let mut outb = vec![];
-            if !closed {
-                outb.extend(self.calc_cap(&v[n - 1], &v[n - 2])); // End Cap
-            }
-            for i in (n1..n2).rev() {
-                // Backward Path
-                outb.extend(self.calc_join(&v[next!(i, n)], &v[curr!(i, n)], &v[prev!(i, n)]));
-            }
-            if closed {
-                // Set first point as a MoveTo
-                outb[0].cmd = PathCommand::MoveTo;
-                // Close the polygon, using the last point
-                let n = outb.len();
-                let last = outb[n - 1];
-                outb.push(Vertex::close_polygon(last.x, last.y));
-            } else {
-                // Close the polygon, using the last point
-                let n = outb.len();
-                let last = outb[n - 1];
-                outb.push(Vertex::close_polygon(last.x, last.y));
-            }
-
-            // Set First point as MoveTo
-            outf[0].cmd = PathCommand::MoveTo;
-            // Combine Forward and Backward Paths
-            outf.extend(outb);
-
-            // Add to Path Collection
-            all_out.extend(outf);
+            outb.extend(self.calc_join(&v[next!(i, n)], &v[curr!(i, n)], &v[prev!(i, n)]));
+        }
+        if closed {
+            // Set first point as a MoveTo
+            outb[0].cmd = PathCommand::MoveTo;
+            // Close the polygon, using the last point
+            let n = outb.len();
+            let last = outb[n - 1];
+            outb.push(Vertex::close_polygon(last.x, last.y));
+        } else {
+            // Close the polygon, using the last point
+            let n = outb.len();
+            let last = outb[n - 1];
+            outb.push(Vertex::close_polygon(last.x, last.y));
         }
-        all_out
+
+        // Set First point as MoveTo
+        outf[0].cmd = PathCommand::MoveTo;
+        // Combine Forward and Backward Paths
+        outf.extend(outb);
+        outf
     }
 }
 
@@ -702,8 +692,8 @@ pub struct Dash<S: VertexSource> {
 }
 
 impl<S: VertexSource> VertexSource for Dash<S> {
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.draw()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        subpaths(self.source.vertices()).flat_map(|sub| self.draw(&sub))
     }
 }
 
@@ -760,9 +750,9 @@ impl<S: VertexSource> Dash<S> {
         self.dashes.clear();
         self.closed = false;
     }
-    fn draw(&self) -> Vec<Vertex<f64>> {
+    /// Dash a subpath, starting the pattern again from `dash_start`
+    fn draw(&self, src: &[Vertex<f64>]) -> Vec<Vertex<f64>> {
         let mut out = vec![];
-        let src = self.source.xconvert();
 
         if src.len() < 2 || self.dashes.len() < 2 {
             return out;
@@ -852,7 +842,7 @@ fn clean_path(v: &[Vertex<f64>]) -> Vec<Vertex<f64>> {
     //  All other vertices are included
     for i in 1..v.len() {
         match v[i].cmd {
-            PathCommand::LineTo => {
+            PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4 => {
                 if Vertex::len(&v[i - 1], &v[i]) >= 1e-6 {
                     mark.push(i);
                 }
@@ -893,7 +883,7 @@ fn clean_path(v: &[Vertex<f64>]) -> Vec<Vertex<f64>> {
 fn last_line_to(v: &[Vertex<f64>]) -> Option<usize> {
     let mut i = v.len() - 1;
     while i > 0 {
-        if v[i].cmd == PathCommand::LineTo {
+        if matches![v[i].cmd, PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4] {
             return Some(i);
         }
         i -= 1;
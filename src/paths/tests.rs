@@ -0,0 +1,459 @@
+// agrega::paths::tests
+
+use super::{
+    bounding_rect, BSpline, Catrom, ClipSide, ClosePolygon, Curve, Curve4, DouglasPeucker, Path,
+    PathCommand, PathOrientation, Rectangle, SmoothPoly, Vertex, VertexFilter, VertexSource,
+    Visvalingam,
+};
+use alloc::vec::Vec;
+
+fn polygon(points: &[(f64, f64)], closed: bool) -> Path {
+    let mut path = Path::new();
+    for (i, &(x, y)) in points.iter().enumerate() {
+        if i == 0 {
+            path.move_to(x, y);
+        } else {
+            path.line_to(x, y);
+        }
+    }
+    if closed {
+        path.close_polygon();
+    }
+    path
+}
+
+#[test]
+fn curve4_flattening() {
+    let mut curve = Curve4::new(0.0, 0.0, 0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+    let v = curve.xconvert();
+    assert_eq!(v[0], Vertex::move_to(0.0, 0.0));
+    assert_eq!(v[v.len() - 1], Vertex::line_to(100.0, 0.0));
+    assert!(v[1..].iter().all(|v| v.cmd == PathCommand::LineTo));
+    // The curve stays within the convex hull of its control points
+    assert!(v.iter().all(|v| (0.0..=100.0).contains(&v.x) && (0.0..=75.0).contains(&v.y)));
+
+    let coarse = v.len();
+    curve.approximation_scale(4.0);
+    assert!(curve.xconvert().len() > coarse);
+}
+
+#[test]
+fn curve_converter() {
+    let mut path = Path::new();
+    path.move_to(0.0, 0.0);
+    path.curve3(50.0, 50.0, 100.0, 0.0);
+    path.curve4(100.0, -50.0, 0.0, -50.0, 0.0, 0.0);
+    path.close_polygon();
+
+    let v = Curve::new(path).xconvert();
+    assert!(v.len() > 6);
+    assert!(v.iter().all(|v| !matches![v.cmd, PathCommand::Curve3 | PathCommand::Curve4]));
+    assert!(v.contains(&Vertex::line_to(100.0, 0.0)));
+    assert_eq!(v[v.len() - 1].cmd, PathCommand::Close);
+}
+
+#[test]
+fn catrom_interpolates_control_points() {
+    let pts = [(0.0, 0.0), (10.0, 20.0), (30.0, 10.0), (40.0, 30.0)];
+    let mut spline = Catrom::new(polygon(&pts, false));
+    spline.emit_curves(true);
+    let v = spline.xconvert();
+    assert_eq!(v.len(), 1 + 3 * 3);
+    assert_eq!(v[0], Vertex::move_to(0.0, 0.0));
+    for (i, &(x, y)) in pts[1..].iter().enumerate() {
+        assert_eq!(v[3 * (i + 1)], Vertex::new(x, y, PathCommand::Curve4));
+    }
+
+    let mut closed = Catrom::new(polygon(&pts, true));
+    closed.emit_curves(true);
+    let v = closed.xconvert();
+    assert_eq!(v.len(), 1 + 4 * 3 + 1);
+    assert_eq!((v[12].x, v[12].y), (0.0, 0.0));
+    assert_eq!(v[13].cmd, PathCommand::Close);
+}
+
+#[test]
+fn bspline_clamps_open_ends() {
+    let pts = [(0.0, 0.0), (10.0, 20.0), (30.0, 10.0), (40.0, 30.0)];
+    let v = BSpline::new(polygon(&pts, false)).xconvert();
+    let (first, last) = (v[0], v[v.len() - 1]);
+    assert_eq!((first.x, first.y), (0.0, 0.0));
+    assert!((last.x - 40.0).abs() < 1e-9 && (last.y - 30.0).abs() < 1e-9);
+
+    // A closed B-spline doesn't go through its control points
+    let v = BSpline::new(polygon(&pts, true)).xconvert();
+    assert_eq!(v[v.len() - 1].cmd, PathCommand::Close);
+    assert!(!v.iter().any(|v| (v.x, v.y) == (0.0, 0.0)));
+}
+
+#[test]
+fn smooth_poly() {
+    let pts = [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+    let mut smooth = SmoothPoly::new(polygon(&pts, true));
+    smooth.smooth_value(1.0);
+    smooth.emit_curves(true);
+    let v = smooth.xconvert();
+    // One cubic curve per edge
+    assert_eq!(v.len(), 1 + 4 * 3 + 1);
+    assert!(v[1..13].iter().all(|v| v.cmd == PathCommand::Curve4));
+    // The curve still goes through every vertex
+    for (i, &(x, y)) in pts.iter().cycle().skip(1).take(4).enumerate() {
+        assert_eq!((v[3 * (i + 1)].x, v[3 * (i + 1)].y), (x, y));
+    }
+
+    // Open polygons start and end with quadratic curves
+    let mut smooth = SmoothPoly::new(polygon(&pts, false));
+    smooth.emit_curves(true);
+    let cmds: Vec<_> = smooth.xconvert().iter().map(|v| v.cmd).collect();
+    assert_eq!(cmds[1], PathCommand::Curve3);
+    assert_eq!(cmds[3], PathCommand::Curve4);
+    assert_eq!(cmds[cmds.len() - 1], PathCommand::Curve3);
+
+    // Without smoothing the control polygon is preserved
+    let mut flat = SmoothPoly::new(polygon(&pts, false));
+    flat.smooth_value(0.0);
+    assert!(flat.xconvert().iter().all(|v| pts.contains(&(v.x, v.y))));
+}
+
+#[test]
+fn douglas_peucker() {
+    // A noisy tent shape
+    let pts: Vec<_> = (0..=20_i32)
+        .map(|i| (i as f64, (10 - (i - 10).abs()) as f64 + 0.01 * (i % 3) as f64))
+        .collect();
+    let mut dp = DouglasPeucker::new(polygon(&pts, false));
+    dp.tolerance(0.1);
+    let v = dp.xconvert();
+    assert_eq!(
+        v,
+        [
+            Vertex::move_to(0.0, 0.0),
+            Vertex::line_to(10.0, 10.01),
+            Vertex::line_to(20.0, 0.02)
+        ]
+    );
+    assert_eq!(dp.removed(), 18);
+
+    // Closed polygons keep at least their significant corners
+    let square = [(0.0, 0.0), (5.0, 0.01), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+    let mut dp = DouglasPeucker::new(polygon(&square, true));
+    dp.tolerance(0.1);
+    let v = dp.xconvert();
+    assert_eq!(v.len(), 5);
+    assert_eq!(v[4].cmd, PathCommand::Close);
+    assert_eq!(dp.removed(), 1);
+}
+
+#[test]
+fn visvalingam() {
+    let pts = [
+        (0.0, 0.0),
+        (1.0, 0.1),
+        (2.0, 0.0),
+        (3.0, 4.0),
+        (4.0, 0.0),
+        (5.0, 0.05),
+        (6.0, 0.0),
+    ];
+    let mut vw = Visvalingam::new(polygon(&pts, false));
+    vw.min_area(0.5);
+    let v = vw.xconvert();
+    assert_eq!(
+        v.iter().map(|v| (v.x, v.y)).collect::<Vec<_>>(),
+        [(0.0, 0.0), (2.0, 0.0), (3.0, 4.0), (4.0, 0.0), (6.0, 0.0)]
+    );
+    assert_eq!(vw.removed(), 2);
+
+    // Closed polygons never go below a triangle
+    let mut vw = Visvalingam::new(polygon(&pts, true));
+    vw.min_area(1000.0);
+    assert_eq!(vw.xconvert().len(), 3 + 1);
+}
+
+#[test]
+fn vertex_filter_and_close_polygon() {
+    let pts = [(0.0, 0.0), (0.0, 0.0), (10.0, 0.0), (10.0, 1e-3), (10.0, 10.0), (0.0, 0.0)];
+    let mut filter = VertexFilter::new(polygon(&pts, true));
+    assert_eq!(filter.removed(), 2);
+    filter.min_distance(0.01);
+    assert_eq!(filter.removed(), 3);
+    assert_eq!(
+        filter.xconvert(),
+        [
+            Vertex::move_to(0.0, 0.0),
+            Vertex::line_to(10.0, 0.0),
+            Vertex::line_to(10.0, 10.0),
+            Vertex::close_polygon(10.0, 10.0),
+        ]
+    );
+
+    let open = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 0.0)], false);
+    let closed = ClosePolygon::new(open.clone());
+    assert_eq!(closed.removed(), 1);
+    assert_eq!(closed.xconvert()[3], Vertex::close_polygon(10.0, 10.0));
+    let mut unclosed = ClosePolygon::new(ClosePolygon::new(open));
+    unclosed.close(false);
+    assert_eq!(unclosed.removed(), 1);
+    assert!(unclosed.xconvert().iter().all(|v| v.cmd != PathCommand::Close));
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn path_measure() {
+    use super::PathMeasure;
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    let mut path = polygon(&[(0.0, 0.0), (30.0, 0.0), (30.0, 40.0)], true);
+    path.move_to(100.0, 100.0);
+    path.line_to(100.0, 90.0);
+    let m = PathMeasure::new(&path);
+    assert_eq!(m.lengths(), [120.0, 10.0]);
+    assert_eq!(m.total_length(), 130.0);
+
+    assert_eq!(m.point_at(15.0), Some((15.0, 0.0, 0.0)));
+    assert_eq!(m.point_at(30.0), Some((30.0, 0.0, FRAC_PI_2)));
+    // Along the closing segment, going back to the origin
+    let (x, y, a) = m.point_at(95.0).unwrap();
+    assert!((x - 15.0).abs() < 1e-9 && (y - 20.0).abs() < 1e-9);
+    assert!((a - (-40.0f64).atan2(-30.0)).abs() < 1e-12);
+    // Clamped to the ends
+    assert_eq!(m.point_at(-5.0), Some((0.0, 0.0, 0.0)));
+    assert_eq!(m.point_at(500.0), Some((100.0, 90.0, -FRAC_PI_2)));
+
+    // A range crossing two subpaths
+    let seg = m.segment(110.0, 125.0);
+    assert_eq!(
+        seg.vertices,
+        [
+            Vertex::move_to(6.0, 8.0),
+            Vertex::line_to(0.0, 0.0),
+            Vertex::move_to(100.0, 100.0),
+            Vertex::line_to(100.0, 95.0),
+        ]
+    );
+    assert!(m.segment(50.0, 40.0).vertices.is_empty());
+
+    // Ranges ending or starting on a subpath boundary don't touch the other one
+    assert_eq!(
+        m.segment(100.0, 120.0).vertices,
+        [Vertex::move_to(12.0, 16.0), Vertex::line_to(0.0, 0.0)]
+    );
+    assert_eq!(
+        m.segment(120.0, 125.0).vertices,
+        [Vertex::move_to(100.0, 100.0), Vertex::line_to(100.0, 95.0)]
+    );
+
+    // A range covering a whole closed subpath closes it
+    let closed = [
+        Vertex::move_to(0.0, 0.0),
+        Vertex::line_to(30.0, 0.0),
+        Vertex::line_to(30.0, 40.0),
+    ];
+    let seg = m.segment(0.0, 125.0);
+    assert_eq!(seg.vertices[..3], closed);
+    assert_eq!(seg.vertices[3].cmd, PathCommand::Close);
+    assert_eq!(seg.vertices[4..], [Vertex::move_to(100.0, 100.0), Vertex::line_to(100.0, 95.0)]);
+    assert_eq!(m.segment(-10.0, 120.0).vertices.len(), 4);
+    assert!(m.segment(0.0, 119.0).vertices.iter().all(|v| v.cmd != PathCommand::Close));
+
+    // Curves are measured once flattened
+    let mut arc = Path::new();
+    arc.move_to(0.0, 0.0);
+    arc.curve4(0.0, 55.228, 44.772, 100.0, 100.0, 100.0);
+    let len = PathMeasure::with_approximation_scale(&arc, 10.0).total_length();
+    assert!((len - PI * 50.0).abs() < 0.1);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn hit_testing() {
+    use super::{nearest_point, point_in_path, point_near_stroke, LineCap, LineJoin, Stroke};
+    use super::{PathMeasure, Transform};
+    use crate::FillingRule::{EvenOdd, NonZero};
+
+    // Two nested squares with the same orientation
+    let mut path = polygon(&[(0.0, 0.0), (30.0, 0.0), (30.0, 30.0), (0.0, 30.0)], true);
+    path.move_to(10.0, 10.0);
+    path.line_to(20.0, 10.0);
+    path.line_to(20.0, 20.0);
+    path.line_to(10.0, 20.0);
+    assert!(point_in_path(&path, 5.0, 5.0, NonZero));
+    assert!(point_in_path(&path, 15.0, 15.0, NonZero));
+    assert!(point_in_path(&path, 5.0, 5.0, EvenOdd));
+    assert!(!point_in_path(&path, 15.0, 15.0, EvenOdd));
+    assert!(!point_in_path(&path, 35.0, 15.0, NonZero));
+
+    // The open inner square is only near its drawn segments
+    let near = |x, y| nearest_point(&path, x, y).unwrap().distance <= 1.0;
+    assert!(near(15.0, 21.0));
+    assert!(near(21.0, 15.0));
+    assert!(!near(9.0, 15.0));
+
+    let p = nearest_point(&path, 40.0, 15.0).unwrap();
+    assert_eq!((p.x, p.y, p.distance, p.offset), (30.0, 15.0, 10.0, 45.0));
+    assert!(nearest_point(&Path::new(), 0.0, 0.0).is_none());
+
+    // A stroked line is hit within its width
+    let mut line = Path::new();
+    line.move_to(0.0, 50.0);
+    line.line_to(100.0, 50.0);
+    let mut stroke = Stroke::new(line);
+    stroke.width(4.0);
+    assert!(point_in_path(&stroke, 50.0, 51.5, NonZero));
+    assert!(!point_in_path(&stroke, 50.0, 53.0, NonZero));
+    assert!(point_near_stroke(&stroke, 50.0, 51.5, 0.0));
+    assert!(point_near_stroke(&stroke, 50.0, 53.0, 1.0));
+    assert!(!point_near_stroke(&stroke, 50.0, 53.0, 0.5));
+
+    // The caps extend the ends
+    assert!(!point_near_stroke(&stroke, 101.5, 50.0, 0.0));
+    stroke.line_cap(LineCap::Square);
+    assert!(point_near_stroke(&stroke, 101.5, 50.0, 0.0));
+
+    // And the joins the corners
+    let mut corner = Path::new();
+    corner.move_to(0.0, 0.0);
+    corner.line_to(20.0, 0.0);
+    corner.line_to(20.0, 20.0);
+    let mut stroke = Stroke::new(corner);
+    stroke.width(4.0);
+    stroke.line_join(LineJoin::Miter);
+    assert!(point_near_stroke(&stroke, 21.8, -1.8, 0.0));
+    stroke.line_join(LineJoin::Round);
+    assert!(!point_near_stroke(&stroke, 21.8, -1.8, 0.0));
+    assert!(point_near_stroke(&stroke, 21.8, -1.8, 0.7));
+
+    // The offsets agree with the measured lengths off the subpixel grid
+    let mut line = Path::new();
+    line.move_to(0.1, 0.1);
+    line.line_to(10.0, 3.3);
+    let line = line.transformed(&Transform::new().rotate(0.3));
+    let length = PathMeasure::new(&line).total_length();
+    let p = nearest_point(&line, 100.0, 100.0).unwrap();
+    assert_eq!(p.offset, length);
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "no_std"))]
+fn variable_width_stroke() {
+    use super::{point_in_path, LineCap, LineJoin, VarStroke};
+    use crate::FillingRule::NonZero;
+    use alloc::vec;
+
+    let line = polygon(&[(0.0, 50.0), (100.0, 50.0)], false);
+
+    // The width is interpolated between the vertices
+    let stroke = VarStroke::new(&line, vec![2.0, 10.0]);
+    assert!(point_in_path(&stroke, 10.0, 51.3, NonZero));
+    assert!(!point_in_path(&stroke, 10.0, 51.5, NonZero));
+    assert!(point_in_path(&stroke, 90.0, 45.5, NonZero));
+    assert!(!point_in_path(&stroke, 90.0, 45.3, NonZero));
+
+    // A width over the distance, tapering at the ends
+    let mut stroke =
+        VarStroke::new(&line, |d: f64, len: f64| if d < len / 2.0 { 4.0 } else { 8.0 });
+    stroke.taper(20.0, 0.0);
+    assert!(!point_in_path(&stroke, 5.0, 51.0, NonZero));
+    assert!(point_in_path(&stroke, 5.0, 50.4, NonZero));
+    assert!(point_in_path(&stroke, 30.0, 51.9, NonZero));
+    assert!(!point_in_path(&stroke, 30.0, 52.1, NonZero));
+    assert!(point_in_path(&stroke, 70.0, 53.9, NonZero));
+
+    // Caps extend the ends by their half width
+    let mut stroke = VarStroke::new(&line, vec![4.0, 8.0]);
+    assert!(!point_in_path(&stroke, -1.0, 50.0, NonZero));
+    stroke.line_cap(LineCap::Square);
+    assert!(point_in_path(&stroke, -1.9, 51.9, NonZero));
+    assert!(point_in_path(&stroke, 103.9, 53.9, NonZero));
+    stroke.line_cap(LineCap::Round);
+    assert!(point_in_path(&stroke, 103.5, 50.0, NonZero));
+    assert!(!point_in_path(&stroke, 103.5, 53.5, NonZero));
+
+    // Closed paths are stroked as a ring, with the outer joins
+    let square = polygon(&[(0.0, 0.0), (40.0, 0.0), (40.0, 40.0), (0.0, 40.0)], true);
+    let mut stroke = VarStroke::new(&square, vec![4.0, 4.0, 8.0, 8.0]);
+    assert!(point_in_path(&stroke, 20.0, 1.5, NonZero));
+    assert!(!point_in_path(&stroke, 20.0, 20.0, NonZero));
+    assert!(point_in_path(&stroke, 43.5, 43.5, NonZero));
+    stroke.line_join(LineJoin::Bevel);
+    assert!(!point_in_path(&stroke, 43.5, 43.5, NonZero));
+    assert!(point_in_path(&stroke, 41.5, 41.5, NonZero));
+}
+
+/// An endless source of horizontal line segments, one per subpath.
+struct Hatching;
+
+impl VertexSource for Hatching {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        (0..).flat_map(|i| {
+            let y = f64::from(i);
+            [Vertex::move_to(0.0, y), Vertex::line_to(10.0, y)]
+        })
+    }
+}
+
+#[test]
+fn streaming_converters() {
+    // Converters only pull the subpaths they need from the source
+    let smooth = Catrom::new(Hatching);
+    let v: Vec<_> = smooth.vertices().take(4).collect();
+    assert_eq!(v[2], Vertex::move_to(0.0, 1.0));
+    assert_eq!(
+        VertexFilter::new(&Hatching).vertices().nth(101),
+        Some(Vertex::line_to(10.0, 50.0))
+    );
+
+    // Every call starts again from the first vertex
+    let path = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], true);
+    let curve = Curve::new(&path);
+    assert_eq!(curve.xconvert(), curve.vertices().collect::<Vec<_>>());
+    assert_eq!(curve.xconvert(), path.vertices);
+}
+
+#[test]
+fn path_storage_ids() {
+    let mut storage = Path::new();
+    let first = storage.start_new_path();
+    storage.move_to(0.0, 0.0);
+    storage.line_to(10.0, 0.0);
+    let second = storage.start_new_path();
+    assert_eq!(second, storage.start_new_path());
+    storage.move_to(5.0, 5.0);
+    storage.line_to(5.0, 10.0);
+    storage.close_polygon();
+
+    assert_eq!((first, second), (0, 3));
+    assert_eq!(storage.vertices[2].cmd, PathCommand::Stop);
+    assert_eq!(storage.select(first).xconvert(), storage.vertices[..2]);
+    assert_eq!(storage.select(second).xconvert(), storage.vertices[3..]);
+    assert_eq!(storage.select(100).vertices().count(), 0);
+
+    // The Stop between the paths is neither a vertex nor part of a subpath
+    let mut storage = Path::new();
+    for (x1, x2) in [(100.0, 110.0), (120.0, 130.0)] {
+        storage.start_new_path();
+        storage.move_to(x1, x1);
+        storage.line_to(x2, x1);
+        storage.line_to(x2, x2);
+        storage.close_polygon();
+    }
+    storage.start_new_path();
+    let r = bounding_rect(&storage).unwrap();
+    assert_eq!((r.x1(), r.y1(), r.x2(), r.y2()), (100.0, 100.0, 130.0, 130.0));
+    assert_eq!(storage.split(), [(0, 3), (5, 8)]);
+    storage.arrange_orientations(PathOrientation::Clockwise);
+    assert_eq!(storage.vertices[4].cmd, PathCommand::Stop);
+    let subpaths: Vec<_> = super::subpaths(storage.vertices()).collect();
+    assert_eq!(subpaths, [&storage.vertices[0..4], &storage.vertices[5..9]]);
+}
+
+#[test]
+fn rectangle_normalized() {
+    let r = Rectangle::new(10.0, 8.0, 0.0, 2.0);
+    assert_eq!((r.x1(), r.y1(), r.x2(), r.y2()), (0.0, 2.0, 10.0, 8.0));
+    // y is sorted on its own, whatever the x coordinates are
+    let r = Rectangle::new(0, 8, 10, 2);
+    assert_eq!((r.y1(), r.y2()), (2, 8));
+    assert_eq!(r.clip_flags(5, 5), ClipSide::INSIDE);
+    assert_eq!(r.clip_flags(-1, 9), ClipSide::LEFT | ClipSide::TOP);
+}
@@ -0,0 +1,377 @@
+// agrega::paths::var_stroke
+//
+//! Variable-width path stroking
+//!
+//! # Examples
+//! ```
+//! use agrega::{LineCap, Path, RasterizerScanline, VarStroke};
+//!
+//! let mut path = Path::new();
+//! path.move_to(10.0, 10.0);
+//! path.line_to(100.0, 60.0);
+//! path.line_to(190.0, 10.0);
+//!
+//! // A width for each vertex
+//! let mut stroke = VarStroke::new(&path, vec![2.0, 12.0, 2.0]);
+//! stroke.line_cap(LineCap::Round);
+//!
+//! // A width over the distance along the path, tapering at both ends
+//! let mut brush = VarStroke::new(&path, |dist: f64, len: f64| 4.0 + 8.0 * dist / len);
+//! brush.taper(20.0, 40.0);
+//!
+//! let mut ras = RasterizerScanline::new();
+//! ras.add_path(&stroke);
+//! ras.add_path(&brush);
+//! ```
+//
+// TOC
+// - trait StrokeWidth
+// - struct VarStroke
+
+use crate::{
+    paths::{PathCommand, Vertex},
+    LineCap, LineJoin, VertexSource,
+};
+use alloc::{vec, vec::Vec};
+use core::f64::consts::PI;
+#[allow(unused_imports)]
+use devela::{iif, ExtFloat};
+
+/// Points closer than this are considered the same.
+const EPSILON: f64 = 1e-6;
+
+/// The width of a [`VarStroke`] along its path.
+///
+/// It is implemented for a width per vertex, as a `Vec<f64>` or `&[f64]`,
+/// and for functions of the distance along a subpath and its length.
+pub trait StrokeWidth {
+    /// Returns the full width at the vertex `index` of the source, which is
+    /// at `distance` along a subpath of the given `length`.
+    fn width(&self, index: usize, distance: f64, length: f64) -> f64;
+
+    /// Returns whether the width changes along the segments, so they must be
+    /// sampled, instead of interpolating the widths of their vertices.
+    fn is_continuous(&self) -> bool {
+        false
+    }
+}
+
+impl StrokeWidth for Vec<f64> {
+    /// Returns the width of the vertex, or the last one for extra vertices.
+    fn width(&self, index: usize, _distance: f64, _length: f64) -> f64 {
+        self.as_slice().width(index, _distance, _length)
+    }
+}
+impl StrokeWidth for &[f64] {
+    /// Returns the width of the vertex, or the last one for extra vertices.
+    fn width(&self, index: usize, _distance: f64, _length: f64) -> f64 {
+        self.get(index).or(self.last()).copied().unwrap_or(0.0)
+    }
+}
+impl<F: Fn(f64, f64) -> f64> StrokeWidth for F {
+    fn width(&self, _index: usize, distance: f64, length: f64) -> f64 {
+        self(distance, length)
+    }
+    fn is_continuous(&self) -> bool {
+        true
+    }
+}
+
+/// A point of the outline skeleton, with its half width.
+#[derive(Debug, Copy, Clone)]
+struct Sample {
+    x: f64,
+    y: f64,
+    w: f64,
+}
+
+impl Sample {
+    /// Returns the offset of length `w` to the side of the segment to `next`
+    /// that is stroked first, the right side with the y axis pointing up.
+    fn normal(&self, next: &Sample, w: f64) -> (f64, f64) {
+        let len = self.dist(next);
+        (w * (next.y - self.y) / len, -w * (next.x - self.x) / len)
+    }
+    fn dist(&self, other: &Sample) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+    fn vertex(&self) -> Vertex<f64> {
+        Vertex::line_to(self.x, self.y)
+    }
+}
+
+/// Strokes a path with a width that changes along it.
+///
+/// The width is given per vertex, or as a function of the distance along
+/// each subpath, through the [`StrokeWidth`] trait. The sides of the stroke
+/// are straight between the samples, so the width is interpolated linearly
+/// along the segments.
+#[derive(Debug)]
+pub struct VarStroke<S: VertexSource, W: StrokeWidth> {
+    source: S,
+    widths: W,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    miter_limit: f64,
+    approx_scale: f64,
+    /// Lengths of the tapers at the start and the end of open subpaths.
+    taper: (f64, f64),
+    step: f64,
+}
+
+impl<S: VertexSource, W: StrokeWidth> VertexSource for VarStroke<S, W> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let mut src = self.source.vertices().enumerate().peekable();
+        core::iter::from_fn(move || loop {
+            let start = src.by_ref().find(|(_, v)| v.cmd == PathCommand::MoveTo)?;
+            let mut sub = vec![start];
+            while let Some(v) = src.next_if(|(_, v)| v.cmd != PathCommand::MoveTo) {
+                sub.push(v);
+            }
+            let out = self.stroke(&sub);
+            iif![!out.is_empty(); return Some(out)];
+        })
+        .flatten()
+    }
+}
+
+impl<S: VertexSource, W: StrokeWidth> VarStroke<S, W> {
+    /// Creates a new `VarStroke` of the `source` with the given `widths`.
+    pub fn new(source: S, widths: W) -> Self {
+        Self {
+            source,
+            widths,
+            line_cap: LineCap::Butt,
+            line_join: LineJoin::Miter,
+            miter_limit: 4.0,
+            approx_scale: 1.0,
+            taper: (0.0, 0.0),
+            step: 2.0,
+        }
+    }
+    /// Sets the style of the ends of open subpaths.
+    pub fn line_cap(&mut self, line_cap: LineCap) {
+        self.line_cap = line_cap;
+    }
+    /// Sets the style of the outer corners.
+    ///
+    /// `MiterAccurate` and `None` are reset to `Miter`, like in [`Stroke`][crate::Stroke].
+    pub fn line_join(&mut self, line_join: LineJoin) {
+        self.line_join = match line_join {
+            LineJoin::MiterAccurate | LineJoin::None => LineJoin::Miter,
+            join => join,
+        };
+    }
+    /// Sets the miter limit, relative to the width at the corner.
+    pub fn miter_limit(&mut self, miter_limit: f64) {
+        self.miter_limit = miter_limit;
+    }
+    /// Sets the approximation scale of round caps and joins.
+    pub fn approximation_scale(&mut self, scale: f64) {
+        self.approx_scale = scale;
+    }
+    /// Narrows the width down to zero over the `start` and `end` lengths of
+    /// each open subpath.
+    pub fn taper(&mut self, start: f64, end: f64) {
+        self.taper = (start.max(0.0), end.max(0.0));
+    }
+    /// Sets the maximum distance between the samples of a continuous width
+    /// along the segments. The default is 2.0.
+    pub fn step(&mut self, step: f64) {
+        self.step = step;
+    }
+
+    /// Returns the position, width and source index of the vertices of a
+    /// subpath, without repeated points, and whether it is closed.
+    fn points(&self, sub: &[(usize, Vertex<f64>)]) -> (Vec<(Sample, usize)>, bool) {
+        let mut closed = false;
+        let mut points: Vec<(Sample, usize)> = vec![];
+        for &(i, v) in sub {
+            match v.cmd {
+                PathCommand::Close => closed = true,
+                PathCommand::MoveTo
+                | PathCommand::LineTo
+                | PathCommand::Curve3
+                | PathCommand::Curve4 => {
+                    let s = Sample { x: v.x, y: v.y, w: 0.0 };
+                    iif![points.last().is_some_and(|(p, _)| p.dist(&s) < EPSILON); continue];
+                    points.push((s, i));
+                }
+                PathCommand::Stop => {}
+            }
+        }
+        while closed && points.len() > 1 && points[0].0.dist(&points[points.len() - 1].0) < EPSILON
+        {
+            points.pop();
+        }
+        let closed = closed && points.len() > 2;
+        (points, closed)
+    }
+
+    /// Returns the samples of a subpath, with their half widths.
+    fn samples(&self, points: &[(Sample, usize)], closed: bool) -> Vec<Sample> {
+        let n = points.len();
+        let segments = iif![closed; n; n - 1];
+        let length: f64 = (0..segments).map(|i| points[i].0.dist(&points[(i + 1) % n].0)).sum();
+        let (start, end) = iif![closed; (0.0, 0.0); self.taper];
+        let taper = |d: f64| {
+            let s = iif![start > 0.0; (d / start).min(1.0); 1.0];
+            let e = iif![end > 0.0; ((length - d) / end).min(1.0); 1.0];
+            (s * e).max(0.0)
+        };
+        let half = |w: f64, d: f64| w.max(0.0) * taper(d) / 2.0;
+
+        let mut out = Vec::with_capacity(n);
+        let mut dist = 0.0;
+        for i in 0..segments {
+            let ((a, ia), (b, ib)) = (points[i], points[(i + 1) % n]);
+            let len = a.dist(&b);
+            let (wa, wb) =
+                (self.widths.width(ia, dist, length), self.widths.width(ib, dist + len, length));
+            out.push(Sample { w: half(wa, dist), ..a });
+
+            // Split the segment at the tapers, and along continuous widths
+            let mut splits = vec![start, length - end];
+            if self.widths.is_continuous() && self.step > 0.0 {
+                let pieces = (len / self.step).ceil() as usize;
+                splits.extend((1..pieces).map(|k| dist + len * k as f64 / pieces as f64));
+            }
+            splits.retain(|&d| d > dist + EPSILON && d < dist + len - EPSILON);
+            splits.sort_by(f64::total_cmp);
+            for d in splits {
+                let t = (d - dist) / len;
+                let w = iif![self.widths.is_continuous();
+                    self.widths.width(ia, d, length); wa + (wb - wa) * t];
+                let (x, y) = (a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t);
+                out.push(Sample { x, y, w: half(w, d) });
+            }
+            dist += len;
+            iif![!closed && i + 1 == segments; out.push(Sample { w: half(wb, dist), ..b })];
+        }
+        out
+    }
+
+    /// Strokes a subpath, as one polygon for open subpaths, and as the outer
+    /// and inner polygons for closed ones.
+    fn stroke(&self, sub: &[(usize, Vertex<f64>)]) -> Vec<Vertex<f64>> {
+        let (points, closed) = self.points(sub);
+        iif![points.len() < 2; return vec![]];
+        let s = self.samples(&points, closed);
+        let n = s.len();
+        let (n1, n2) = iif![closed; (0, n); (1, n - 1)];
+
+        let mut outf = vec![];
+        iif![!closed; outf.extend(self.cap(&s[0], &s[1]))];
+        for i in n1..n2 {
+            outf.extend(self.join(&s[(i + n - 1) % n], &s[i], &s[(i + 1) % n]));
+        }
+        let mut outb = vec![];
+        iif![!closed; outb.extend(self.cap(&s[n - 1], &s[n - 2]))];
+        for i in (n1..n2).rev() {
+            outb.extend(self.join(&s[(i + 1) % n], &s[i], &s[(i + n - 1) % n]));
+        }
+
+        outf[0].cmd = PathCommand::MoveTo;
+        let mut out = outf;
+        if closed {
+            let last = out[out.len() - 1];
+            out.push(Vertex::close_polygon(last.x, last.y));
+            outb[0].cmd = PathCommand::MoveTo;
+        }
+        out.extend(outb);
+        let last = out[out.len() - 1];
+        out.push(Vertex::close_polygon(last.x, last.y));
+        out
+    }
+
+    /// Returns the cap at `a`, for the segment going to `b`.
+    fn cap(&self, a: &Sample, b: &Sample) -> Vec<Vertex<f64>> {
+        let (nx, ny) = a.normal(b, a.w);
+        let (ux, uy) = (ny, -nx); // backwards, along the segment
+        match self.line_cap {
+            LineCap::Butt => {
+                vec![Vertex::line_to(a.x - nx, a.y - ny), Vertex::line_to(a.x + nx, a.y + ny)]
+            }
+            LineCap::Square => vec![
+                Vertex::line_to(a.x - nx + ux, a.y - ny + uy),
+                Vertex::line_to(a.x + nx + ux, a.y + ny + uy),
+            ],
+            LineCap::Round => {
+                let a1 = (-ny).atan2(-nx);
+                self.arc(a, a1, a1 + PI)
+            }
+        }
+    }
+
+    /// Returns the join at `p1`, between the segments from `p0` and to `p2`.
+    fn join(&self, p0: &Sample, p1: &Sample, p2: &Sample) -> Vec<Vertex<f64>> {
+        let (n1x, n1y) = p0.normal(p1, 1.0);
+        let (n2x, n2y) = p1.normal(p2, 1.0);
+        let a1 = (p0.x + n1x * p0.w, p0.y + n1y * p0.w);
+        let b1 = (p1.x + n1x * p1.w, p1.y + n1y * p1.w);
+        let a2 = (p1.x + n2x * p1.w, p1.y + n2y * p1.w);
+        let b2 = (p2.x + n2x * p2.w, p2.y + n2y * p2.w);
+        let bevel = vec![Vertex::line_to(b1.0, b1.1), Vertex::line_to(a2.0, a2.1)];
+        let hit = intersection(a1, b1, a2, b2);
+
+        if Vertex::cross(&p0.vertex(), &p1.vertex(), &p2.vertex()) > 0.0 {
+            // Inner join, at the intersection of the sides while it is near
+            let limit = p0.dist(p1).min(p1.dist(p2)) + p1.w;
+            return match hit {
+                Some((x, y)) if (x - p1.x).hypot(y - p1.y) <= limit => vec![Vertex::line_to(x, y)],
+                _ => vec![Vertex::line_to(b1.0, b1.1), p1.vertex(), Vertex::line_to(a2.0, a2.1)],
+            };
+        }
+        let round = |s: &Self| {
+            let (start, mut end) = (n1y.atan2(n1x), n2y.atan2(n2x));
+            iif![end < start; end += 2.0 * PI];
+            s.arc(p1, start, end)
+        };
+        match self.line_join {
+            LineJoin::Bevel => bevel,
+            LineJoin::Round => round(self),
+            _ => {
+                let limit = self.miter_limit * p1.w;
+                match hit {
+                    Some((x, y)) if (x - p1.x).hypot(y - p1.y) <= limit => {
+                        vec![Vertex::line_to(x, y)]
+                    }
+                    // Nearly collinear segments
+                    None if n1x * n2x + n1y * n2y > 0.0 => vec![Vertex::line_to(b1.0, b1.1)],
+                    _ if self.line_join == LineJoin::MiterRound => round(self),
+                    Some((x, y)) if self.line_join == LineJoin::Miter => {
+                        // Cut the miter at the limit
+                        let di = (x - p1.x).hypot(y - p1.y);
+                        let mid = ((b1.0 + a2.0) / 2.0 - p1.x).hypot((b1.1 + a2.1) / 2.0 - p1.y);
+                        let t = (limit - mid) / (di - mid);
+                        vec![
+                            Vertex::line_to(b1.0 + (x - b1.0) * t, b1.1 + (y - b1.1) * t),
+                            Vertex::line_to(a2.0 + (x - a2.0) * t, a2.1 + (y - a2.1) * t),
+                        ]
+                    }
+                    _ => bevel,
+                }
+            }
+        }
+    }
+
+    /// Returns the arc around `c` with its half width, counter-clockwise from
+    /// angle `a1` to `a2`, including both ends.
+    fn arc(&self, c: &Sample, a1: f64, a2: f64) -> Vec<Vertex<f64>> {
+        let point = |a: f64| Vertex::line_to(c.x + a.cos() * c.w, c.y + a.sin() * c.w);
+        iif![c.w < EPSILON; return vec![c.vertex()]];
+        let da = 2.0 * (c.w / (c.w + 0.125 / self.approx_scale)).acos();
+        let n = ((a2 - a1) / da) as usize + 1;
+        (0..=n).map(|k| point(a1 + (a2 - a1) * k as f64 / n as f64)).collect()
+    }
+}
+
+/// Returns the intersection of the line through `a` and `b` with the line
+/// through `c` and `d`, or `None` if they are parallel.
+fn intersection(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> Option<(f64, f64)> {
+    let num = (a.1 - c.1) * (d.0 - c.0) - (a.0 - c.0) * (d.1 - c.1);
+    let den = (b.0 - a.0) * (d.1 - c.1) - (b.1 - a.1) * (d.0 - c.0);
+    iif![den.abs() < 1.0e-30; return None];
+    let r = num / den;
+    Some((a.0 + r * (b.0 - a.0), a.1 + r * (b.1 - a.1)))
+}
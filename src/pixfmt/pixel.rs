@@ -526,3 +526,34 @@ impl Pixel for Pixfmt<Gray8> {
         write_file(self.as_bytes(), self.width(), self.height(), filename, image::ColorType::L8)
     }
 }
+
+/// A pixel format that blends each color channel with its own coverage,
+/// for LCD subpixel rendering.
+pub trait LcdPixel: Pixel {
+    /// Blends the given `color` into the pixel at `id`, with the `covers` of
+    /// its red, green and blue channels.
+    fn blend_pix_lcd<C: Color>(&mut self, id: (usize, usize), color: C, covers: [u64; 3]);
+}
+
+impl LcdPixel for Pixfmt<Rgb8> {
+    fn blend_pix_lcd<C: Color>(&mut self, id: (usize, usize), color: C, covers: [u64; 3]) {
+        let alpha = color.alpha8();
+        let (p, c) = (self.raw(id).into_array3(), Rgb8::from_trait(color).into_array3());
+        let pix =
+            core::array::from_fn(|i| lerp_u8(p[i], c[i], multiply_u8(alpha, covers[i] as u8)));
+        self.set(id, Rgb8::from_array3(pix));
+    }
+}
+
+impl LcdPixel for Pixfmt<Rgba8> {
+    /// The alpha channel is blended with the highest of the `covers`.
+    fn blend_pix_lcd<C: Color>(&mut self, id: (usize, usize), color: C, covers: [u64; 3]) {
+        let alpha = color.alpha8();
+        let (p, c) = (self.get(id), Rgba8::from_trait(color));
+        let (p3, c3) = (p.into_array3(), c.into_array3());
+        let [r, g, b] =
+            core::array::from_fn(|i| lerp_u8(p3[i], c3[i], multiply_u8(alpha, covers[i] as u8)));
+        let a = multiply_u8(alpha, covers.iter().copied().max().unwrap_or(0) as u8);
+        self.set(id, Rgba8::new(r, g, b, prelerp_u8(p.a, a, a)));
+    }
+}
@@ -3,7 +3,7 @@
 use super::RenderingBuffer;
 #[cfg(feature = "std")]
 use crate::read_file;
-use crate::{color::*, util::*, Color, Pixel, PixelSource};
+use crate::{color::*, util::*, Color, GammaLut, Pixel, PixelSource};
 use devela::{iif, PhantomData, Vec};
 
 /// Pixel format wrapper around raw pixel component data.
@@ -82,6 +82,27 @@ where
         self.rbuf.clear();
     }
 
+    /// Creates a new `Pixfmt` from its pixel `data`, in row-major order.
+    ///
+    /// # Panics
+    /// Panics if `data.len()` does not equal `width * height * bpp`.
+    #[inline]
+    #[cfg(feature = "std")]
+    pub(crate) fn from_bytes(data: Vec<u8>, width: usize, height: usize) -> Self {
+        Self {
+            rbuf: RenderingBuffer::from_vec(data, width, height, Self::bpp()),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the pixel data as a mutable byte slice.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "std")]
+    pub(crate) fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.rbuf.data
+    }
+
     //pub fn from(rbuf: RenderingBuffer) -> Self {
     //    Self { rbuf, phantom: PhantomData }
     //}
@@ -168,6 +189,35 @@ where
     }
 }
 
+/// Implements the gamma correction of the first `$channels` bytes of each pixel.
+macro_rules! impl_apply_gamma {
+    ($($t:ty: $channels:literal),+) => { $(
+        impl Pixfmt<$t> {
+            /// Applies the direct gamma of `lut` to the color components, leaving the alpha.
+            pub fn apply_gamma_dir(&mut self, lut: &GammaLut) {
+                self.apply_gamma_table(lut.dir_table(), $channels);
+            }
+            /// Applies the inverse gamma of `lut` to the color components, leaving the alpha.
+            pub fn apply_gamma_inv(&mut self, lut: &GammaLut) {
+                self.apply_gamma_table(lut.inv_table(), $channels);
+            }
+        }
+    )+ };
+}
+impl_apply_gamma![Gray8: 1, Rgb8: 3, Rgba8: 3, Rgba8pre: 3];
+
+impl<T> Pixfmt<T>
+where
+    Pixfmt<T>: Pixel,
+{
+    /// Maps the first `channels` bytes of each pixel through `table`.
+    fn apply_gamma_table(&mut self, table: &[u8; 256], channels: usize) {
+        for pix in self.rbuf.data.chunks_exact_mut(Self::bpp()) {
+            pix[..channels].iter_mut().for_each(|v| *v = table[*v as usize]);
+        }
+    }
+}
+
 impl Pixfmt<Gray8> {
     /// Mixes the given grayscale color `c` with the pixel at `id`, using `alpha` for blending.
     ///
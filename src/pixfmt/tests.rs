@@ -1,4 +1,6 @@
-use crate::{Pixel, PixelSource, Pixfmt, Rgb8, Rgba32, Rgba8, Rgba8pre, Srgba8};
+use crate::{
+    GammaLut, Gray8, LcdPixel, Pixel, PixelSource, Pixfmt, Rgb8, Rgba32, Rgba8, Rgba8pre, Srgba8,
+};
 
 #[test]
 fn pixfmt_test() {
@@ -135,6 +137,22 @@ fn pixfmt_rgb8_test() {
     assert_eq!(pix.get((0, 0)), Rgba8::new(191, 191, 191, 255));
 }
 
+#[test]
+fn pixfmt_lcd_test() {
+    let mut pix = Pixfmt::<Rgb8>::new(1, 1);
+    pix.copy_pixel(0, 0, Rgb8::white());
+    pix.blend_pix_lcd((0, 0), Rgba8::black(), [255, 128, 0]);
+    assert_eq!(pix.get((0, 0)), Rgba8::new(0, 127, 255, 255));
+    pix.copy_pixel(0, 0, Rgb8::white());
+    pix.blend_pix_lcd((0, 0), Rgba8::new(0, 0, 0, 128), [255, 255, 0]);
+    assert_eq!(pix.get((0, 0)), Rgba8::new(127, 127, 255, 255));
+
+    let mut pix = Pixfmt::<Rgba8>::new(1, 1);
+    pix.copy_pixel(0, 0, Rgba8::new(255, 255, 255, 0));
+    pix.blend_pix_lcd((0, 0), Rgba8::black(), [0, 128, 255]);
+    assert_eq!(pix.get((0, 0)), Rgba8::new(255, 127, 0, 255));
+}
+
 #[test]
 fn pixfmt_rgba8_test() {
     let mut pix = Pixfmt::<Rgba8>::new(1, 1);
@@ -228,3 +246,21 @@ fn pixfmt_rgba8pre_test() {
     pix.copy_or_blend_pix_with_cover((0, 0), Rgba8::new(0, 0, 0, beta), cover);
     assert_eq!(pix.get((0, 0)), Rgba8::new(191, 191, 191, 160));
 }
+
+#[test]
+fn pixfmt_apply_gamma() {
+    let lut = GammaLut::new(2.0);
+    let mut p = Pixfmt::<Rgba8>::new(2, 1);
+    p.copy_pixel(0, 0, Rgba8::new(128, 255, 0, 128));
+    p.copy_pixel(1, 0, Rgba8::new(64, 32, 16, 255));
+    p.apply_gamma_dir(&lut);
+    assert_eq!(p.get((0, 0)), Rgba8::new(64, 255, 0, 128));
+    assert_eq!(p.get((1, 0)), Rgba8::new(lut.dir(64), lut.dir(32), lut.dir(16), 255));
+    p.apply_gamma_inv(&lut);
+    assert_eq!(p.get((0, 0)), Rgba8::new(128, 255, 0, 128));
+
+    let mut g = Pixfmt::<Gray8>::new(1, 1);
+    g.copy_pixel(0, 0, Gray8::new_with_alpha(128, 100));
+    g.apply_gamma_dir(&lut);
+    assert_eq!(g.raw((0, 0)), Gray8::new_with_alpha(64, 100));
+}
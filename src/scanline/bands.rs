@@ -0,0 +1,75 @@
+// agrega::scanline::bands
+//
+//! Rendering in bands.
+//!
+//! [`render_bands`] splits an image into bands of rows rendered in parallel,
+//! each one in its own thread with its own rasterizer.
+//!
+//! # Examples
+//! ```
+//! use agrega::{
+//!     render_all_paths, render_bands, Ellipse, Pixfmt, RenderingBase,
+//!     RenderingScanlineAASolid, Rgb8,
+//! };
+//!
+//! let shapes: Vec<_> = (0..10)
+//!     .map(|i| (Ellipse::new(50.0 + 20.0 * i as f64, 100.0, 40.0, 80.0, 64), Rgb8::new(0, 0, 25 * i)))
+//!     .collect();
+//!
+//! let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(300, 200));
+//! ren_base.clear(Rgb8::white());
+//! render_bands(&mut ren_base, 4, |ras, band| {
+//!     let mut ren = RenderingScanlineAASolid::with_base(band);
+//!     render_all_paths(ras, &mut ren, shapes.iter().map(|(path, color)| (path, color)));
+//! });
+//! ```
+//
+// TOC
+// - fn render_bands
+
+use crate::{Pixel, Pixfmt, RasterizerScanline, RenderingBase};
+use std::thread;
+
+/// Renders into `ren_base` in parallel, split into `bands` bands of rows.
+///
+/// Calls `draw` in a thread for each band, with a new rasterizer and a
+/// rendering base holding the rows of the band, to render the whole scene
+/// with the rasterizer. The result is the same as rendering it sequentially,
+/// byte for byte.
+///
+/// Each rasterizer skips the lines outside of its band and sweeps only
+/// its rows, which the renderers receive relative to the band, with the
+/// offset in [`RenderData::y_offset`][crate::RenderData::y_offset].
+/// Anything drawn in `draw` without the rasterizer is also relative to the band.
+///
+/// Like a rasterizer, paths are only clipped to the
+/// [`POLY_MAX_COORD`][crate::POLY_MAX_COORD] limits, so set a
+/// [`clip_box`][RasterizerScanline::clip_box] to the image in `draw` to bound the
+/// work of huge paths to their visible part. The result is still the same as
+/// rendering sequentially with that clip box.
+///
+/// There's a band for each row at most, and one at least.
+/// [`available_parallelism`][thread::available_parallelism] is a good number of bands.
+pub fn render_bands<T, F>(ren_base: &mut RenderingBase<Pixfmt<T>>, bands: usize, draw: F)
+where
+    Pixfmt<T>: Pixel,
+    F: Fn(&mut RasterizerScanline, &mut RenderingBase<Pixfmt<T>>) + Sync,
+{
+    let (width, height) = (ren_base.pixf.width(), ren_base.pixf.height());
+    let rows = height.div_ceil(bands.clamp(1, height));
+    let row_bytes = width * Pixfmt::<T>::bpp();
+    let draw = &draw;
+    thread::scope(|s| {
+        for (i, band) in ren_base.pixf.as_bytes_mut().chunks_mut(rows * row_bytes).enumerate() {
+            s.spawn(move || {
+                let (y1, num_rows) = (i * rows, band.len() / row_bytes);
+                let pixf = Pixfmt::<T>::from_bytes(band.to_vec(), width, num_rows);
+                let mut band_base = RenderingBase::new(pixf);
+                let mut ras = RasterizerScanline::new();
+                ras.set_band(y1 as i64, (y1 + num_rows) as i64);
+                draw(&mut ras, &mut band_base);
+                band.copy_from_slice(band_base.as_bytes());
+            });
+        }
+    });
+}
@@ -42,5 +42,12 @@ items! {
     mod gradient;
     mod rast;
     mod rend;
-    pub use {gradient::*, rast::*, rend::*};
+    mod storage;
+    pub use {gradient::*, rast::*, rend::*, storage::*};
+
+    #[cfg(feature = "std")]
+    mod bands;
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
+    pub use bands::*;
 }
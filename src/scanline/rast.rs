@@ -3,7 +3,7 @@
 //! Rasterizer
 
 use crate::{
-    Clip, PathCommand, RasterizerCell, ScanlineU8, Vertex, VertexSource, POLY_SUBPIXEL_SCALE,
+    CellOverflow, Clip, Gamma, PathCommand, RasterizerCell, Scanline, Vertex, VertexSource,
     POLY_SUBPIXEL_SHIFT,
 };
 use alloc::vec::Vec;
@@ -11,13 +11,6 @@ use core::cmp::{max, min};
 #[allow(unused_imports)]
 use devela::ExtFloat;
 
-struct RasConvInt;
-impl RasConvInt {
-    pub fn upscale(v: f64) -> i64 {
-        (v * POLY_SUBPIXEL_SCALE as f64).round() as i64
-    }
-}
-
 /// Winding / Filling Rule.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub enum FillingRule {
@@ -46,9 +39,15 @@ pub struct RasterizerScanline {
     /// Status of Path
     pub(crate) status: PathStatus,
     /// Current x position
-    pub(crate) x0: i64,
+    pub(crate) x0: f64,
     /// Current y position
-    pub(crate) y0: i64,
+    pub(crate) y0: f64,
+    /// Number of non-finite vertices dropped
+    dropped: usize,
+    /// Rows swept, from the first included to the last excluded
+    band: (i64, i64),
+    /// Whether the last move to was dropped, so the next line to moves instead
+    move_dropped: bool,
 
     /// Collection of Rasterizing Cells
     outline: RasterizerCell,
@@ -73,8 +72,11 @@ impl RasterizerScanline {
             clipper: Clip::new(),
             status: PathStatus::Initial,
             outline: RasterizerCell::new(),
-            x0: 0,
-            y0: 0,
+            x0: 0.0,
+            y0: 0.0,
+            dropped: 0,
+            band: (0, i64::MAX),
+            move_dropped: false,
             scan_y: 0,
             filling_rule: FillingRule::NonZero,
             gamma: (0..256).collect(),
@@ -96,27 +98,45 @@ impl RasterizerScanline {
 
     /// Resets the rasterizer.
     ///
-    /// Reset the RasterizerCell and set PathStatus to Initial
+    /// Reset the RasterizerCell, keeping its allocated memory, set PathStatus to Initial
+    /// and the count of [`dropped_vertices`][Self::dropped_vertices] to 0.
     #[inline]
     pub fn reset(&mut self) {
         self.outline.reset();
         self.status = PathStatus::Initial;
+        self.dropped = 0;
+        self.move_dropped = false;
     }
 
     /// Add a Path
     ///
-    /// Walks the path from the VertexSource and rasterizes it
+    /// Walks the path from the VertexSource and rasterizes it, until its end
+    /// or a `Stop` command.
+    ///
+    /// Resets the rasterizer first if it has already been swept.
+    /// Use [`add_path_to`][Self::add_path_to] to keep the previous paths.
     pub fn add_path<VS: VertexSource>(&mut self, path: &VS) {
-        //path.rewind();
-        if !self.outline.sorted_y.is_empty() {
+        if self.outline.is_sorted() {
             self.reset();
         }
-        for seg in path.xconvert() {
+        self.add_path_to(path);
+    }
+
+    /// Add a Path to the ones already in the rasterizer
+    ///
+    /// Like [`add_path`][Self::add_path] but it never resets the rasterizer,
+    /// so the next sweep renders this path together with the previous ones.
+    pub fn add_path_to<VS: VertexSource>(&mut self, path: &VS) {
+        // the cells are sorted again on the next sweep
+        self.outline.unsort();
+        for seg in path.vertices() {
             match seg.cmd {
-                PathCommand::LineTo => self.line_to(seg.x, seg.y),
+                PathCommand::LineTo | PathCommand::Curve3 | PathCommand::Curve4 => {
+                    self.line_to(seg.x, seg.y)
+                }
                 PathCommand::MoveTo => self.move_to(seg.x, seg.y),
                 PathCommand::Close => self.close_polygon(),
-                PathCommand::Stop => unimplemented!("stop encountered"),
+                PathCommand::Stop => break,
             }
         }
     }
@@ -138,7 +158,7 @@ impl RasterizerScanline {
         if self.outline.total_cells() == 0 {
             false
         } else {
-            self.scan_y = self.outline.min_y;
+            self.scan_y = self.outline.min_y.max(self.band.0);
             true
         }
     }
@@ -148,18 +168,18 @@ impl RasterizerScanline {
     /// For individual y rows adding any to the input Scanline
     ///
     /// Returns true if data exists in the input Scanline
-    pub(crate) fn sweep_scanline(&mut self, sl: &mut ScanlineU8) -> bool {
+    pub(crate) fn sweep_scanline<SL: Scanline>(&mut self, sl: &mut SL) -> bool {
         loop {
             if self.scan_y < 0 {
                 self.scan_y += 1;
                 continue;
             }
-            if self.scan_y > self.outline.max_y {
+            if self.scan_y > self.outline.max_y || self.scan_y >= self.band.1 {
                 return false;
             }
             sl.reset_spans();
-            let mut num_cells = self.outline.scanline_num_cells(self.scan_y);
             let cells = self.outline.scanline_cells(self.scan_y);
+            let mut num_cells = cells.len();
 
             let mut cover = 0;
 
@@ -203,7 +223,7 @@ impl RasterizerScanline {
             }
             self.scan_y += 1;
         }
-        sl.finalize(self.scan_y);
+        sl.finalize(self.scan_y - self.band.0);
         self.scan_y += 1;
         true
     }
@@ -229,41 +249,126 @@ impl RasterizerScanline {
     /// gamma = gfunc( v / mask ) * mask
     /// where v = 0 to 255
     /// ```
+    #[inline]
     pub fn gamma<F: Fn(f64) -> f64>(&mut self, gfunc: F) {
+        self.set_gamma(&gfunc);
+    }
+
+    /// Sets the gamma function from any [`Gamma`], like [`GammaPower`][crate::GammaPower]
+    ///
+    /// Borrows it so the same curve can be applied elsewhere, like a
+    /// [`GammaLut`][crate::GammaLut] also used to correct the pixel buffer.
+    /// See [`gamma`][Self::gamma] for description.
+    pub fn set_gamma<G: Gamma>(&mut self, gamma: &G) {
         let aa_shift = 8;
         let aa_scale = 1 << aa_shift;
         let aa_mask = f64::from(aa_scale - 1);
 
         self.gamma = (0..256)
-            .map(|i| gfunc(f64::from(i) / aa_mask))
+            .map(|i| gamma.apply(f64::from(i) / aa_mask))
             .map(|v| (v * aa_mask).round() as u64)
             .collect();
     }
 
     /// Set Clip Box
+    ///
+    /// Paths are clipped to it in double precision before rasterizing.
+    /// Without a clip box they're only clipped to the [`POLY_MAX_COORD`][crate::POLY_MAX_COORD]
+    /// limits, about 4.19 million pixels, and every pixel crossed by an edge
+    /// up to them stores a cell, so a single huge path can reach the
+    /// [cell limit][Self::set_cell_limit]. Set it to the visible area when
+    /// drawing paths much larger than the image, like map data at high zoom
+    /// levels, to rasterize only the parts that can be seen.
     pub fn clip_box(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
-        self.clipper.clip_box(
-            RasConvInt::upscale(x1),
-            RasConvInt::upscale(y1),
-            RasConvInt::upscale(x2),
-            RasConvInt::upscale(y2),
-        );
+        self.clipper.clip_box(x1, y1, x2, y2);
+    }
+
+    /// Sets the maximum number of cells, rounded up to blocks of [`CELL_BLOCK_SIZE`][crate::CELL_BLOCK_SIZE]
+    ///
+    /// A cell is stored for each pixel crossed by an edge, and takes 32 bytes.
+    /// The default limit is [`CELL_LIMIT`][crate::CELL_LIMIT]. Lower it to bound
+    /// the memory used by untrusted paths, and choose what happens to a path
+    /// that reaches it with [`set_cell_overflow`][Self::set_cell_overflow].
+    ///
+    /// Frees the allocated cells beyond the new limit that aren't in use.
+    #[inline]
+    pub fn set_cell_limit(&mut self, max_cells: usize) {
+        self.outline.set_limit(max_cells);
     }
 
+    /// Returns the maximum number of cells.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn cell_limit(&self) -> usize { self.outline.limit() }
+
+    /// Sets what happens to a path once the cell limit is reached.
+    #[inline]
+    pub fn set_cell_overflow(&mut self, overflow: CellOverflow) {
+        self.outline.set_overflow(overflow);
+    }
+
+    /// Returns whether the cell limit has been reached since the last [`reset`][Self::reset].
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn cell_limit_reached(&self) -> bool { self.outline.limit_reached() }
+
+    /// Returns the bytes allocated for the cells.
+    ///
+    /// The allocation doubles as needed, up to the cell limit, and is kept across resets.
+    #[inline]
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.outline.memory_usage()
+    }
+
+    /// Sweeps only the rows from `y1` included to `y2` excluded,
+    /// with the scanlines starting at row 0, for rendering in bands.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_band(&mut self, y1: i64, y2: i64) {
+        self.band = (y1, y2);
+        self.outline.set_band(y1, y2);
+    }
+
+    /// Returns the row of the image where the swept band starts.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub(crate) const fn band_start(&self) -> i64 { self.band.0 }
+
+    /// Returns the number of vertices with NaN or infinite coordinates dropped
+    /// since the last [`reset`][Self::reset].
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn dropped_vertices(&self) -> usize { self.dropped }
+
     /// Moves to point (x,y).
     ///
     /// Sets point as the initial point.
+    ///
+    /// A point with a NaN or infinite coordinate is dropped,
+    /// and the next [`line_to`][Self::line_to] point is moved to instead.
     pub fn move_to(&mut self, x: f64, y: f64) {
-        self.x0 = RasConvInt::upscale(x);
-        self.y0 = RasConvInt::upscale(y);
-        self.clipper.move_to(self.x0, self.y0);
+        if !(x.is_finite() && y.is_finite()) {
+            self.dropped += 1;
+            self.move_dropped = true;
+            self.status = PathStatus::Initial;
+            return;
+        }
+        self.move_dropped = false;
+        self.x0 = x;
+        self.y0 = y;
+        self.clipper.move_to(x, y);
         self.status = PathStatus::MoveTo;
     }
 
     /// Draws a line from previous point to new point (x,y).
+    ///
+    /// A point with a NaN or infinite coordinate is dropped,
+    /// and the line goes from the previous point to the next one.
     pub fn line_to(&mut self, x: f64, y: f64) {
-        let x = RasConvInt::upscale(x);
-        let y = RasConvInt::upscale(y);
+        if !(x.is_finite() && y.is_finite()) {
+            self.dropped += 1;
+            return;
+        }
+        if self.move_dropped {
+            self.move_to(x, y);
+            return;
+        }
         self.clipper.line_to(&mut self.outline, x, y);
         self.status = PathStatus::LineTo;
     }
@@ -5,8 +5,8 @@
 use crate::{
     len_i64_xy, ClipSide, Color, DistanceInterpolator4, DrawOutline, LineInterpolator,
     LineParameters, Pixel, PixelSource, Pixfmt, RasterizerScanline, Rectangle, RenderingBase,
-    Rgba8, ScanlineU8, SpanGradient, Subpixel, VertexSource, MAX_HALF_WIDTH, POLY_SUBPIXEL_MASK,
-    POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT,
+    Rgba8, Scanline, ScanlineBin, ScanlineP8, ScanlineU8, SpanGradient, Subpixel, VertexSource,
+    MAX_HALF_WIDTH, POLY_SUBPIXEL_MASK, POLY_SUBPIXEL_SCALE, POLY_SUBPIXEL_SHIFT,
 };
 use alloc::{vec, vec::Vec};
 use devela::iif;
@@ -27,8 +27,10 @@ pub(crate) const LINE_MAX_LENGTH: i64 = 1 << (POLY_SUBPIXEL_SHIFT + 10);
     doc(cfg(any(feature = "std", all(feature = "no_std", feature = "alloc"))))
 )]
 pub trait Render {
+    /// The scanline the renderer reads
+    type Scanline: Scanline;
     /// Render a single scanlines to the image
-    fn render(&mut self, data: &RenderData);
+    fn render(&mut self, data: &RenderData<Self::Scanline>);
     /// Set the Color of the Renderer
     fn color<C: Color>(&mut self, color: C);
     /// Prepare the Renderer
@@ -55,42 +57,45 @@ pub struct RenderingScanlineAA<'a, T> {
 }
 
 /// Render a single Scanline (y-row) without Anti-Aliasing (Binary?)
-fn render_scanline_bin_solid<T: Pixel, C: Color>(
-    sl: &ScanlineU8,
+fn render_scanline_bin_solid<SL: Scanline, T: Pixel, C: Color>(
+    sl: &SL,
     ren: &mut RenderingBase<T>,
     color: C,
 ) {
     let cover_full = 255;
-    for span in &sl.spans {
-        ren.blend_hline(span.x, sl.y, span.x - 1 + span.len.abs(), color, cover_full);
+    for span in sl.spans() {
+        ren.blend_hline(span.x, sl.y(), span.x - 1 + span.len.abs(), color, cover_full);
     }
 }
 
 /// Render a single Scanline (y-row) with Anti Aliasing
-fn render_scanline_aa_solid<T: Pixel, C: Color>(
-    sl: &ScanlineU8,
+fn render_scanline_aa_solid<SL: Scanline, T: Pixel, C: Color>(
+    sl: &SL,
     ren: &mut RenderingBase<T>,
     color: C,
 ) {
-    let y = sl.y;
-    for span in &sl.spans {
+    let y = sl.y();
+    for span in sl.spans() {
         let x = span.x;
         if span.len > 0 {
             ren.blend_solid_hspan(x, y, span.len, color, &span.covers);
         } else {
-            ren.blend_hline(x, y, x - span.len - 1, color, span.covers[0]);
+            ren.blend_hline(x, y, x - span.len - 1, color, span.cover());
         }
     }
 }
 
 /// Render a single Scanline (y-row) with Anti-Aliasing
-fn render_scanline_aa<T: Pixel>(
-    sl: &ScanlineU8,
+///
+/// The colors are generated at the rows of the image, `y_offset` rows below the buffer ones.
+fn render_scanline_aa<SL: Scanline, T: Pixel>(
+    sl: &SL,
     ren: &mut RenderingBase<T>,
     span_gen: &SpanGradient,
+    y_offset: i64,
 ) {
-    let y = sl.y;
-    for span in &sl.spans {
+    let y = sl.y();
+    for span in sl.spans() {
         let x = span.x;
         let mut len = span.len;
         let covers = &span.covers;
@@ -100,7 +105,7 @@ fn render_scanline_aa<T: Pixel>(
         //dbg!(x);
         //dbg!(y);
         //dbg!(len);
-        let colors = span_gen.generate(x, y, len as usize);
+        let colors = span_gen.generate(x, y + y_offset, len as usize);
         //dbg!(&colors);
         ren.blend_color_hspan(
             x,
@@ -108,33 +113,46 @@ fn render_scanline_aa<T: Pixel>(
             len,
             &colors,
             if span.len < 0 { &[] } else { covers },
-            covers[0],
+            span.cover(),
         );
     }
 }
 
-/// TODO
+/// The scanline passed to a [`Render`]er.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct RenderData {
-    sl: ScanlineU8,
+pub struct RenderData<SL: Scanline = ScanlineU8> {
+    pub(crate) sl: SL,
+    pub(crate) y_offset: i64,
 }
-impl RenderData {
-    /// TODO
+impl<SL: Scanline> RenderData<SL> {
+    /// Creates new data with an empty scanline.
     #[inline]
     pub fn new() -> Self {
-        Self { sl: ScanlineU8::new() }
-    }
+        Self { sl: SL::default(), y_offset: 0 }
+    }
+    /// Returns the scanline.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn scanline(&self) -> &SL { &self.sl }
+
+    /// Returns the row of the image where the rendered buffer starts.
+    ///
+    /// It's 0 unless rendering with [`render_bands`][crate::render_bands],
+    /// where the scanline rows are relative to the band.
+    /// Add it to get the row of the image.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn y_offset(&self) -> i64 { self.y_offset }
 }
-impl Default for RenderData {
+impl<SL: Scanline> Default for RenderData<SL> {
     fn default() -> Self {
         Self::new()
     }
 }
 
 impl<T: Pixel> Render for RenderingScanlineAASolid<'_, T> {
+    type Scanline = ScanlineP8;
     /// Render a single Scanline Row
     #[inline]
-    fn render(&mut self, data: &RenderData) {
+    fn render(&mut self, data: &RenderData<ScanlineP8>) {
         render_scanline_aa_solid(&data.sl, self.base, self.color);
     }
     /// Set the current color
@@ -144,9 +162,10 @@ impl<T: Pixel> Render for RenderingScanlineAASolid<'_, T> {
     }
 }
 impl<T: Pixel> Render for RenderingScanlineBinSolid<'_, T> {
+    type Scanline = ScanlineBin;
     /// Render a single Scanline Row
     #[inline]
-    fn render(&mut self, data: &RenderData) {
+    fn render(&mut self, data: &RenderData<ScanlineBin>) {
         render_scanline_bin_solid(&data.sl, self.base, self.color);
     }
     /// Set the current Color
@@ -156,10 +175,11 @@ impl<T: Pixel> Render for RenderingScanlineBinSolid<'_, T> {
     }
 }
 impl<T: Pixel> Render for RenderingScanlineAA<'_, T> {
+    type Scanline = ScanlineU8;
     /// Render a single Scanline Row
     #[inline]
-    fn render(&mut self, data: &RenderData) {
-        render_scanline_aa(&data.sl, self.base, &self.span);
+    fn render(&mut self, data: &RenderData<ScanlineU8>) {
+        render_scanline_aa(&data.sl, self.base, &self.span, data.y_offset);
     }
     /// Set the current Color
     #[inline]
@@ -221,7 +241,7 @@ pub fn render_scanlines_bin_solid<C: Color, T: Pixel>(
     ren: &mut RenderingBase<T>,
     color: C,
 ) {
-    let mut sl = ScanlineU8::new();
+    let mut sl = ScanlineBin::new();
     if ras.rewind_scanlines() {
         sl.reset(ras.min_x(), ras.max_x());
         while ras.sweep_scanline(&mut sl) {
@@ -236,7 +256,7 @@ pub fn render_scanlines_aa_solid<C: Color, T: Pixel>(
     ren: &mut RenderingBase<T>,
     color: C,
 ) {
-    let mut sl = ScanlineU8::new();
+    let mut sl = ScanlineP8::new();
     if ras.rewind_scanlines() {
         sl.reset(ras.min_x(), ras.max_x());
         while ras.sweep_scanline(&mut sl) {
@@ -246,11 +266,14 @@ pub fn render_scanlines_aa_solid<C: Color, T: Pixel>(
 }
 
 /// Render rasterized data to an image using the current color
+///
+/// The scanline type is the one chosen by the renderer.
 pub fn render_scanlines<REN>(ras: &mut RasterizerScanline, ren: &mut REN)
 where
     REN: Render,
 {
-    let mut data = RenderData::new();
+    let mut data = RenderData::<REN::Scanline>::new();
+    data.y_offset = ras.band_start();
     if ras.rewind_scanlines() {
         data.sl.reset(ras.min_x(), ras.max_x());
         ren.prepare();
@@ -261,20 +284,38 @@ where
 }
 
 /// Render paths after rasterizing to an image using a set of colors
-pub fn render_all_paths<REN, VS, C>(
-    ras: &mut RasterizerScanline,
-    ren: &mut REN,
-    paths: &[VS],
-    colors: &[C],
-) where
-    C: Color,
+///
+/// Takes pairs of paths and colors, like the ones zipped from two slices:
+/// ```
+/// # use agrega::{render_all_paths, Path, Pixfmt, RasterizerScanline,
+/// #     RenderingBase, RenderingScanlineAASolid, Rgb8};
+/// # let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(40, 40));
+/// # let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+/// # let mut ras = RasterizerScanline::new();
+/// let mut storage = Path::new();
+/// let mut ids = vec![];
+/// for i in 0..3 {
+///     ids.push(storage.start_new_path());
+///     let x = 10.0 * i as f64;
+///     storage.move_to(x, 0.0);
+///     storage.line_to(x + 10.0, 40.0);
+///     storage.line_to(x, 40.0);
+///     storage.close_polygon();
+/// }
+/// let colors = [Rgb8::new(255, 0, 0), Rgb8::new(0, 255, 0), Rgb8::new(0, 0, 255)];
+/// let paths = ids.iter().map(|&id| storage.select(id));
+/// render_all_paths(&mut ras, &mut ren, paths.zip(&colors));
+/// ```
+pub fn render_all_paths<'a, REN, VS, C, I>(ras: &mut RasterizerScanline, ren: &mut REN, paths: I)
+where
+    C: Color + 'a,
     REN: Render,
     VS: VertexSource,
+    I: IntoIterator<Item = (VS, &'a C)>,
 {
-    debug_assert!(paths.len() == colors.len());
-    for (path, color) in paths.iter().zip(colors.iter()) {
+    for (path, color) in paths {
         ras.reset();
-        ras.add_path(path);
+        ras.add_path(&path);
         ren.color(*color);
         render_scanlines(ras, ren);
     }
@@ -510,6 +551,9 @@ impl<T: Pixel> DrawOutline for RendererOutlineImg<'_, T> {
     fn semidot<F: Fn(i64) -> bool>(&mut self, _cmp: F, _xc1: i64, _yc1: i64, _xc2: i64, _yc2: i64) {
     }
     fn pie(&mut self, _xc: i64, _y: i64, _x1: i64, _y1: i64, _x2: i64, _y2: i64) {}
+    fn skip(&mut self, len: i64) {
+        self.start += (len as f64 / self.scale_x).round() as i64;
+    }
 }
 
 impl<'a, T: Pixel> RendererOutlineImg<'a, T> {
@@ -5,57 +5,128 @@
 //! This module provides structures and functions for working with scanlines,
 //! representing contiguous rows of image data in the form of spans. Each span
 //! defines a continuous region along a scanline with its own coverage values.
+//!
+//! Renderers choose the scanline they read with [`Render::Scanline`][crate::Render::Scanline]:
+//! - [`ScanlineU8`] keeps a cover for every pixel.
+//! - [`ScanlineP8`] packs runs of pixels with the same cover into solid spans,
+//!   so large fills are blended as horizontal lines.
+//! - [`ScanlineBin`] keeps no covers, for aliased rendering.
+//!
+//! AGG also has 32-bit variants because its spans use 16-bit coordinates.
+//! The coordinates here are always `i64`, so [`Scanline32U8`], [`Scanline32P8`]
+//! and [`Scanline32Bin`] are just aliases.
 //
+// TOC
+// - trait Scanline
+// - struct Span
+// - struct ScanlineU8
+// - struct ScanlineP8
+// - struct ScanlineBin
+// - aliases Scanline32*
 
 use alloc::{vec, vec::Vec};
 
 const LAST_X: i64 = 0x7FFF_FFF0;
 
+/// The cover of a fully covered pixel.
+const COVER_FULL: u64 = 255;
+
+/// A row of spans produced by a rasterizer and read by a renderer.
+pub trait Scanline: Default {
+    /// Reset values and clear spans, setting min value.
+    fn reset(&mut self, min_x: i64, max_x: i64);
+
+    /// Resets the scanline by clearing all spans.
+    fn reset_spans(&mut self);
+
+    /// Adds a single pixel at `x` with a coverage value.
+    fn add_cell(&mut self, x: i64, cover: u64);
+
+    /// Adds `len` pixels starting at `x` with the same coverage value.
+    fn add_span(&mut self, x: i64, len: i64, cover: u64);
+
+    /// Sets the current row (y-coordinate) to the specified value.
+    fn finalize(&mut self, y: i64);
+
+    /// Returns the current row.
+    #[must_use]
+    fn y(&self) -> i64;
+
+    /// Returns the spans.
+    #[must_use]
+    fn spans(&self) -> &[Span];
+
+    /// Returns the total number of spans within the scanline.
+    #[inline]
+    #[must_use]
+    fn num_spans(&self) -> usize {
+        self.spans().len()
+    }
+}
+
 /// Represents a contiguous area of data along a scanline.
 ///
 /// A `Span` includes a starting `x` position, a `len` specifying the
 /// number of pixels covered, and a `covers` vector containing coverage
 /// values for each pixel in the span.
+///
+/// A negative `len` marks a solid span of `-len` pixels sharing the single
+/// value in `covers`, or fully covered if `covers` is empty.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub(crate) struct Span {
+pub struct Span {
     /// Starting x-coordinate of the span.
     pub x: i64,
-    /// Length of the span in pixels.
+    /// Length of the span in pixels, negative for solid spans.
     pub len: i64,
     /// Coverage values for each pixel in the span.
     pub covers: Vec<u64>,
 }
 
+impl Span {
+    /// Returns the cover of the first pixel, or the full cover if there are no covers.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn cover(&self) -> u64 { self.covers.first().copied().unwrap_or(COVER_FULL) }
+}
+
 /// Represents an unpacked scanline for a single row of an image.
 ///
 /// `ScanlineU8` is used to store spans and manage their properties within
 /// an image row. The scanline maintains state variables to track
 /// horizontal and vertical positions, as well as a collection of spans.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
-pub(crate) struct ScanlineU8 {
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScanlineU8 {
     /// Last x-coordinate used in the scanline, acting as a state variable.
     last_x: i64,
     /// Minimum x-coordinate for this scanline. This is optional.
     min_x: i64,
     /// Current y-coordinate for the scanline, representing the row being processed.
-    pub y: i64,
+    y: i64,
     /// Collection of spans that make up the scanline.
-    pub spans: Vec<Span>,
+    spans: Vec<Span>,
     // /// Collection of covers (RETHINK: needed?)
     // covers: HashMap<i64, u64>,
 }
 
+impl Default for ScanlineU8 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ScanlineU8 {
     /// Creates a new, empty scanline with a pre-allocated capacity for spans.
     #[inline]
+    #[must_use]
     pub fn new() -> Self {
         Self { last_x: LAST_X, min_x: 0, y: 0, spans: Vec::with_capacity(256) }
         //covers: HashMap::new() }
     }
+}
 
+impl Scanline for ScanlineU8 {
     /// Resets the scanline by clearing all spans and setting the x-coordinate state variable.
     #[inline]
-    pub fn reset_spans(&mut self) {
+    fn reset_spans(&mut self) {
         self.last_x = LAST_X;
         self.spans.clear();
         //self.covers.clear();
@@ -63,7 +134,7 @@ impl ScanlineU8 {
 
     /// Reset values and clear spans, setting min value
     #[inline]
-    pub fn reset(&mut self, min_x: i64, _max_x: i64) {
+    fn reset(&mut self, min_x: i64, _max_x: i64) {
         self.last_x = LAST_X;
         self.min_x = min_x;
         self.spans.clear();
@@ -72,15 +143,18 @@ impl ScanlineU8 {
 
     /// Sets the current row (y-coordinate) to the specified value.
     #[inline]
-    pub fn finalize(&mut self, y: i64) {
+    fn finalize(&mut self, y: i64) {
         self.y = y;
     }
 
-    /// Returns the total number of spans within the scanline.
     #[inline]
-    #[must_use]
-    pub fn num_spans(&self) -> usize {
-        self.spans.len()
+    fn y(&self) -> i64 {
+        self.y
+    }
+
+    #[inline]
+    fn spans(&self) -> &[Span] {
+        &self.spans
     }
 
     /// Adds a span to the scanline.
@@ -90,7 +164,7 @@ impl ScanlineU8 {
     ///
     /// If the `x` value is contiguous with the last span, the last span's length
     /// is increased instead of creating a new one.
-    pub fn add_span(&mut self, x: i64, len: i64, cover: u64) {
+    fn add_span(&mut self, x: i64, len: i64, cover: u64) {
         let x = x - self.min_x;
         //self.covers.insert( x, cover );
         if x == self.last_x + 1 {
@@ -108,7 +182,7 @@ impl ScanlineU8 {
     ///
     /// If the new cell is contiguous with the last span, it extends that span
     /// instead of creating a new one.
-    pub fn add_cell(&mut self, x: i64, cover: u64) {
+    fn add_cell(&mut self, x: i64, cover: u64) {
         let x = x - self.min_x;
         //self.covers.insert( x, cover );
         if x == self.last_x + 1 {
@@ -123,3 +197,199 @@ impl ScanlineU8 {
         self.last_x = x;
     }
 }
+
+/// Represents a packed scanline for a single row of an image, like AGG's `scanline_p8`.
+///
+/// Consecutive pixels with the same cover are stored as a single solid span,
+/// which renderers blend as a horizontal line.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScanlineP8 {
+    /// Last x-coordinate used in the scanline.
+    last_x: i64,
+    /// Current y-coordinate for the scanline.
+    y: i64,
+    /// Collection of spans that make up the scanline.
+    spans: Vec<Span>,
+}
+
+impl Default for ScanlineP8 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanlineP8 {
+    /// Creates a new, empty scanline with a pre-allocated capacity for spans.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { last_x: LAST_X, y: 0, spans: Vec::with_capacity(256) }
+    }
+}
+
+impl Scanline for ScanlineP8 {
+    #[inline]
+    fn reset(&mut self, _min_x: i64, _max_x: i64) {
+        self.reset_spans();
+    }
+
+    #[inline]
+    fn reset_spans(&mut self) {
+        self.last_x = LAST_X;
+        self.spans.clear();
+    }
+
+    /// Extends the last span if it isn't solid and `x` is contiguous with it.
+    fn add_cell(&mut self, x: i64, cover: u64) {
+        match self.spans.last_mut() {
+            Some(cur) if x == self.last_x + 1 && cur.len > 0 => {
+                cur.len += 1;
+                cur.covers.push(cover);
+            }
+            _ => self.spans.push(Span { x, len: 1, covers: vec![cover] }),
+        }
+        self.last_x = x;
+    }
+
+    /// Extends the last span if it's solid with the same cover and `x` is contiguous with it.
+    fn add_span(&mut self, x: i64, len: i64, cover: u64) {
+        match self.spans.last_mut() {
+            Some(cur) if x == self.last_x + 1 && cur.len < 0 && cur.covers[0] == cover => {
+                cur.len -= len;
+            }
+            _ => self.spans.push(Span { x, len: -len, covers: vec![cover] }),
+        }
+        self.last_x = x + len - 1;
+    }
+
+    #[inline]
+    fn finalize(&mut self, y: i64) {
+        self.y = y;
+    }
+
+    #[inline]
+    fn y(&self) -> i64 {
+        self.y
+    }
+
+    #[inline]
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
+
+/// Represents a binary scanline for a single row of an image, like AGG's `scanline_bin`.
+///
+/// Keeps only the solid spans of covered pixels, without their covers,
+/// for aliased rendering.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScanlineBin {
+    /// Last x-coordinate used in the scanline.
+    last_x: i64,
+    /// Current y-coordinate for the scanline.
+    y: i64,
+    /// Collection of spans that make up the scanline.
+    spans: Vec<Span>,
+}
+
+impl Default for ScanlineBin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanlineBin {
+    /// Creates a new, empty scanline with a pre-allocated capacity for spans.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self { last_x: LAST_X, y: 0, spans: Vec::with_capacity(256) }
+    }
+}
+
+impl Scanline for ScanlineBin {
+    #[inline]
+    fn reset(&mut self, _min_x: i64, _max_x: i64) {
+        self.reset_spans();
+    }
+
+    #[inline]
+    fn reset_spans(&mut self) {
+        self.last_x = LAST_X;
+        self.spans.clear();
+    }
+
+    #[inline]
+    fn add_cell(&mut self, x: i64, cover: u64) {
+        self.add_span(x, 1, cover);
+    }
+
+    /// Extends the last span if `x` is contiguous with it. The cover is ignored.
+    fn add_span(&mut self, x: i64, len: i64, _cover: u64) {
+        match self.spans.last_mut() {
+            Some(cur) if x == self.last_x + 1 => cur.len -= len,
+            _ => self.spans.push(Span { x, len: -len, covers: Vec::new() }),
+        }
+        self.last_x = x + len - 1;
+    }
+
+    #[inline]
+    fn finalize(&mut self, y: i64) {
+        self.y = y;
+    }
+
+    #[inline]
+    fn y(&self) -> i64 {
+        self.y
+    }
+
+    #[inline]
+    fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+}
+
+/// An unpacked scanline with 32-bit coordinates, the same as [`ScanlineU8`].
+pub type Scanline32U8 = ScanlineU8;
+/// A packed scanline with 32-bit coordinates, the same as [`ScanlineP8`].
+pub type Scanline32P8 = ScanlineP8;
+/// A binary scanline with 32-bit coordinates, the same as [`ScanlineBin`].
+pub type Scanline32Bin = ScanlineBin;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans<SL: Scanline>(sl: &SL) -> Vec<(i64, i64, Vec<u64>)> {
+        sl.spans().iter().map(|s| (s.x, s.len, s.covers.clone())).collect()
+    }
+    fn fill<SL: Scanline>(mut sl: SL) -> SL {
+        sl.reset(0, 20);
+        sl.add_cell(1, 10);
+        sl.add_span(2, 5, 255);
+        sl.add_span(7, 3, 255);
+        sl.add_cell(10, 20);
+        sl.add_span(14, 2, 30);
+        sl.finalize(3);
+        sl
+    }
+
+    #[test]
+    fn scanline_spans() {
+        let u8 = fill(ScanlineU8::new());
+        assert_eq!(u8.y(), 3);
+        assert_eq!(u8.spans()[0].covers.len(), 10);
+        assert_eq!(spans(&u8)[1], (14, 2, vec![30, 30]));
+
+        let p8 = fill(ScanlineP8::new());
+        let expected =
+            [(1, 1, vec![10]), (2, -8, vec![255]), (10, 1, vec![20]), (14, -2, vec![30])];
+        assert_eq!(spans(&p8), expected);
+
+        let mut bin = fill(ScanlineBin::new());
+        assert_eq!(spans(&bin), [(1, -10, vec![]), (14, -2, vec![])]);
+        assert_eq!(bin.spans()[0].cover(), COVER_FULL);
+        bin.reset_spans();
+        assert_eq!(bin.num_spans(), 0);
+    }
+}
@@ -0,0 +1,306 @@
+// agrega::scanline::storage
+//
+//! Scanline storage.
+//!
+//! A [`ScanlineStorage`] records the scanlines of a rasterized shape, like AGG's
+//! `scanline_storage_aa`, to render them again later at any offset without
+//! rasterizing the shape again. It can be serialized to bytes, like the data read
+//! by AGG's `serialized_scanlines_adaptor_aa`, to pre-rasterize shapes at build time.
+//!
+//! # Examples
+//! ```
+//! use agrega::{
+//!     Ellipse, Pixfmt, RasterizerScanline, Render, RenderingBase,
+//!     RenderingScanlineAASolid, Rgb8, ScanlineStorage,
+//! };
+//!
+//! // rasterize an icon once
+//! let mut ras = RasterizerScanline::new();
+//! ras.add_path(&Ellipse::new(8.0, 8.0, 6.0, 6.0, 32));
+//! let icon = ScanlineStorage::from_rasterizer(&mut ras);
+//! let bytes = icon.to_bytes();
+//!
+//! // and stamp it many times
+//! let icon = ScanlineStorage::from_bytes(&bytes).unwrap();
+//! let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 20));
+//! let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+//! ren.color(Rgb8::black());
+//! for i in 0..5 {
+//!     icon.replay(&mut ren, 20 * i, 0);
+//! }
+//! ```
+//
+// TOC
+// - struct ScanlineStorage
+
+use crate::{
+    render_scanlines, Color, RasterizerScanline, Render, RenderData, Scanline, ScanlineP8,
+};
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+/// A recorded span, with its covers stored in the storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct StoredSpan {
+    /// Starting x-coordinate of the span.
+    x: i64,
+    /// Length of the span in pixels, negative for solid spans.
+    len: i64,
+    /// Index of the first cover.
+    covers: usize,
+}
+
+/// A recorded scanline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct StoredRow {
+    /// The y-coordinate of the row.
+    y: i64,
+    /// Index of the first span.
+    spans: usize,
+    /// Number of spans.
+    num_spans: usize,
+}
+
+/// Stored scanlines that can be rendered again at any offset.
+///
+/// Records the scanlines as a [`Render`]er, or from a rasterizer with
+/// [`from_rasterizer`][Self::from_rasterizer]. Solid spans keep a single cover.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ScanlineStorage {
+    rows: Vec<StoredRow>,
+    spans: Vec<StoredSpan>,
+    covers: Vec<u8>,
+    min_x: i64,
+    min_y: i64,
+    max_x: i64,
+    max_y: i64,
+}
+
+impl Default for ScanlineStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScanlineStorage {
+    /// Creates a new, empty storage.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            spans: Vec::new(),
+            covers: Vec::new(),
+            min_x: i64::MAX,
+            min_y: i64::MAX,
+            max_x: i64::MIN,
+            max_y: i64::MIN,
+        }
+    }
+
+    /// Creates a new storage with the scanlines swept from `ras`.
+    #[must_use]
+    pub fn from_rasterizer(ras: &mut RasterizerScanline) -> Self {
+        let mut storage = Self::new();
+        render_scanlines(ras, &mut storage);
+        storage
+    }
+
+    /// Removes all the scanlines, keeping the allocated memory.
+    pub fn reset(&mut self) {
+        self.rows.clear();
+        self.spans.clear();
+        self.covers.clear();
+        (self.min_x, self.min_y) = (i64::MAX, i64::MAX);
+        (self.max_x, self.max_y) = (i64::MIN, i64::MIN);
+    }
+
+    /// Returns the number of scanlines.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn num_scanlines(&self) -> usize { self.rows.len() }
+
+    /// Returns whether there are no scanlines.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn is_empty(&self) -> bool { self.rows.is_empty() }
+
+    /// Returns the minimum x of the stored pixels.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn min_x(&self) -> i64 { self.min_x }
+
+    /// Returns the minimum y of the stored pixels.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn min_y(&self) -> i64 { self.min_y }
+
+    /// Returns the maximum x of the stored pixels.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn max_x(&self) -> i64 { self.max_x }
+
+    /// Returns the maximum y of the stored pixels.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn max_y(&self) -> i64 { self.max_y }
+
+    /// Renders the stored scanlines with `ren`, translated by `(dx, dy)`.
+    pub fn replay<REN: Render>(&self, ren: &mut REN, dx: i64, dy: i64) {
+        if self.is_empty() {
+            return;
+        }
+        let mut data = RenderData::<REN::Scanline>::new();
+        data.sl.reset(self.min_x + dx, self.max_x + dx);
+        ren.prepare();
+        for row in &self.rows {
+            data.sl.reset_spans();
+            for span in &self.spans[row.spans..row.spans + row.num_spans] {
+                let x = span.x + dx;
+                if span.len < 0 {
+                    data.sl.add_span(x, -span.len, u64::from(self.covers[span.covers]));
+                } else {
+                    let covers = &self.covers[span.covers..span.covers + span.len as usize];
+                    for (i, &cover) in covers.iter().enumerate() {
+                        data.sl.add_cell(x + i as i64, u64::from(cover));
+                    }
+                }
+            }
+            data.sl.finalize(row.y + dy);
+            ren.render(&data);
+        }
+    }
+
+    /* serialization */
+
+    /// Returns the size in bytes of the serialized scanlines.
+    #[must_use]
+    pub fn byte_size(&self) -> usize {
+        16 + self.rows.iter().map(|row| self.row_byte_size(row)).sum::<usize>()
+    }
+
+    /// Serializes the scanlines, in the format of AGG's `scanline_storage_aa`.
+    ///
+    /// The bounds come first, followed by each scanline with its size in bytes,
+    /// its y, its number of spans, and for each span its x, its length, and its
+    /// covers, with a single one for solid spans of negative length.
+    /// All the numbers are little-endian `i32`, and the covers are bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.byte_size());
+        let put = |bytes: &mut Vec<u8>, v: i64| bytes.extend((v as i32).to_le_bytes());
+        for v in [self.min_x, self.min_y, self.max_x, self.max_y] {
+            put(&mut bytes, v);
+        }
+        for row in &self.rows {
+            put(&mut bytes, self.row_byte_size(row) as i64);
+            put(&mut bytes, row.y);
+            put(&mut bytes, row.num_spans as i64);
+            for span in &self.spans[row.spans..row.spans + row.num_spans] {
+                put(&mut bytes, span.x);
+                put(&mut bytes, span.len);
+                bytes.extend(&self.covers[span.covers..span.covers + num_covers(span.len)]);
+            }
+        }
+        bytes
+    }
+
+    /// Deserializes the scanlines written by [`to_bytes`][Self::to_bytes].
+    ///
+    /// Returns `None` if the data is truncated or inconsistent.
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut r = Reader { bytes, pos: 0 };
+        let mut storage = Self::new();
+        let bounds = [r.i32()?, r.i32()?, r.i32()?, r.i32()?];
+        while r.pos < bytes.len() {
+            let start = r.pos;
+            let size = usize::try_from(r.i32()?).ok()?;
+            let y = r.i32()?;
+            let num_spans = usize::try_from(r.i32()?).ok()?;
+            // every span takes at least 9 bytes
+            if num_spans > (bytes.len() - r.pos) / 9 {
+                return None;
+            }
+            storage.rows.push(StoredRow { y, spans: storage.spans.len(), num_spans });
+            for _ in 0..num_spans {
+                let (x, len) = (r.i32()?, r.i32()?);
+                if len == 0 {
+                    return None;
+                }
+                let covers = storage.covers.len();
+                storage.covers.extend(r.bytes(num_covers(len))?);
+                storage.spans.push(StoredSpan { x, len, covers });
+            }
+            if r.pos - start != size {
+                return None;
+            }
+        }
+        if !storage.is_empty() {
+            [storage.min_x, storage.min_y, storage.max_x, storage.max_y] = bounds;
+        }
+        Some(storage)
+    }
+
+    /* private */
+
+    /// Returns the size in bytes of the serialized `row`.
+    fn row_byte_size(&self, row: &StoredRow) -> usize {
+        let spans = &self.spans[row.spans..row.spans + row.num_spans];
+        12 + spans.iter().map(|span| 8 + num_covers(span.len)).sum::<usize>()
+    }
+}
+
+/// Returns the number of covers of a span of length `len`.
+#[inline]
+#[must_use]
+fn num_covers(len: i64) -> usize {
+    if len < 0 {
+        1
+    } else {
+        len as usize
+    }
+}
+
+/// Reads little-endian numbers and bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+    fn i32(&mut self) -> Option<i64> {
+        let b = self.bytes(4)?;
+        Some(i64::from(i32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+    }
+}
+
+impl Render for ScanlineStorage {
+    type Scanline = ScanlineP8;
+
+    /// Records a scanline.
+    fn render(&mut self, data: &RenderData<ScanlineP8>) {
+        let sl = data.scanline();
+        let spans = sl.spans();
+        if spans.is_empty() {
+            return;
+        }
+        self.rows
+            .push(StoredRow { y: sl.y(), spans: self.spans.len(), num_spans: spans.len() });
+        for span in spans {
+            self.spans
+                .push(StoredSpan { x: span.x, len: span.len, covers: self.covers.len() });
+            if span.len < 0 {
+                self.covers.push(span.cover().min(255) as u8);
+            } else {
+                self.covers.extend(span.covers.iter().map(|&c| c.min(255) as u8));
+            }
+            self.min_x = min(self.min_x, span.x);
+            self.max_x = max(self.max_x, span.x + span.len.abs() - 1);
+        }
+        self.min_y = min(self.min_y, sl.y());
+        self.max_y = max(self.max_y, sl.y());
+    }
+
+    /// The color is ignored, only the covers are stored.
+    #[inline]
+    fn color<C: Color>(&mut self, _color: C) {}
+}
@@ -0,0 +1,494 @@
+// agrega::font::cache
+//
+//! Glyph cache
+//!
+//! A [`GlyphCache`] keeps the glyphs already loaded from a font, so text that is
+//! measured and drawn repeatedly only asks the font engine for each glyph once,
+//! like AGG's `font_cache_manager`. Glyphs are keyed by face, size, hinting and
+//! render mode, and the least recently used ones are evicted when the cache
+//! grows over its memory limit.
+//!
+//! FreeType faces are told apart by an id the cache gives to each face it
+//! sees, so a new face loaded at the address of a dropped one doesn't get
+//! its glyphs, while outline fonts are told apart by a hash of their data.
+//!
+//! # Examples
+//! ```
+//! use agrega::{GlyphCache, GlyphData, OutlineFont};
+//!
+//! let data = std::fs::read("tests/std/assets/Helvetica.ttc").unwrap();
+//! let font = OutlineFont::from_collection(data, 0).unwrap();
+//! let cache = GlyphCache::new(64 * 1024);
+//!
+//! let glyph = font.glyph_index('A').unwrap();
+//! let a = cache.outline_glyph(&font, glyph).unwrap();
+//! assert!(matches!(&a.data, GlyphData::Outline(path) if !path.vertices.is_empty()));
+//! assert_eq!(a.advance_x, f64::from(font.advance_width(glyph)));
+//!
+//! // The second request is served from the cache
+//! let _ = cache.outline_glyph(&font, glyph).unwrap();
+//! assert_eq!(cache.len(), 1);
+//! ```
+//
+// TOC
+// - enum GlyphRender
+// - struct GlyphKey
+// - struct CachedGlyph
+// - enum GlyphData
+// - struct GlyphBitmap
+// - struct GlyphCache
+// - helpers
+
+#[cfg(feature = "freetype")]
+use crate::_dep::freetype;
+use crate::{paths::Vertex, AggFontError, OutlineFont, Path};
+use alloc::{collections::BTreeMap, rc::Rc, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    mem::size_of,
+};
+#[cfg(feature = "freetype")]
+use devela::iif;
+
+/// The form in which a glyph is stored in a [`GlyphCache`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlyphRender {
+    /// An anti-aliased bitmap with 8-bit coverage values.
+    Gray8,
+    /// A vector outline that keeps its curves.
+    Outline,
+    /// An anti-aliased bitmap with 8-bit coverage values and three times the
+    /// horizontal resolution, for LCD subpixel rendering.
+    ///
+    /// Its `left` and `width` are in subpixels, and its advances in pixels.
+    Lcd,
+    /// Only the advances, loading the glyph without rendering it.
+    Advance,
+}
+
+/// Identifies a glyph in a [`GlyphCache`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlyphKey {
+    /// The identity of the font face.
+    pub face: u64,
+    /// The size the glyph is scaled to, or 0 for unscaled outlines.
+    pub size: u64,
+    /// Whether the glyph is hinted.
+    pub hinting: bool,
+    /// The form in which the glyph is stored.
+    pub render: GlyphRender,
+    /// The subpixel offset of the glyph, in 1/64 pixels.
+    pub offset: (u8, u8),
+    /// The glyph index in the face.
+    pub glyph: u32,
+}
+
+/// A glyph stored in a [`GlyphCache`].
+#[derive(Clone, Debug)]
+pub struct CachedGlyph {
+    /// The horizontal advance of the pen.
+    pub advance_x: f64,
+    /// The vertical advance of the pen.
+    pub advance_y: f64,
+    /// The rendered glyph.
+    pub data: GlyphData,
+}
+
+/// The rendered data of a [`CachedGlyph`].
+#[derive(Clone, Debug)]
+pub enum GlyphData {
+    /// An 8-bit coverage bitmap.
+    Gray8(GlyphBitmap),
+    /// A vector outline, with the y axis pointing up.
+    Outline(Path),
+    /// Nothing, for glyphs cached only for their advances.
+    Empty,
+}
+
+/// An 8-bit coverage bitmap of a glyph.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphBitmap {
+    /// The distance from the pen position to the left edge of the bitmap.
+    pub left: i64,
+    /// The distance from the baseline up to the top edge of the bitmap.
+    pub top: i64,
+    /// The width of the bitmap, in pixels.
+    pub width: usize,
+    /// The number of rows of the bitmap.
+    pub rows: usize,
+    /// The coverage values, row after row, `width` values each.
+    pub buffer: Vec<u8>,
+}
+
+impl GlyphBitmap {
+    /// Returns the coverage values of the row `i`.
+    #[inline]
+    #[must_use]
+    pub fn row(&self, i: usize) -> &[u8] {
+        &self.buffer[i * self.width..(i + 1) * self.width]
+    }
+}
+
+impl CachedGlyph {
+    /// Returns the memory used by the glyph, in bytes.
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        let data = match &self.data {
+            GlyphData::Gray8(bitmap) => bitmap.buffer.capacity(),
+            GlyphData::Outline(path) => path.vertices.capacity() * size_of::<Vertex<f64>>(),
+            GlyphData::Empty => 0,
+        };
+        size_of::<Self>() + data
+    }
+}
+
+/// A cache of glyphs, with bounded memory and least recently used eviction.
+///
+/// It uses interior mutability so it can be shared by the text drawing
+/// functions while borrowed immutably.
+#[derive(Debug)]
+pub struct GlyphCache {
+    glyphs: RefCell<BTreeMap<GlyphKey, CacheEntry>>,
+    /// The glyph keys, ordered from the least recently used.
+    lru: RefCell<BTreeMap<u64, GlyphKey>>,
+    stamp: Cell<u64>,
+    memory: Cell<usize>,
+    max_memory: usize,
+    /// The FreeType faces seen, by address, with their fingerprint and id.
+    #[cfg(feature = "freetype")]
+    faces: RefCell<BTreeMap<usize, (u64, u64)>>,
+    /// The number of FreeType face ids given.
+    #[cfg(feature = "freetype")]
+    face_ids: Cell<u64>,
+}
+
+/// A glyph in a [`GlyphCache`], with the stamp of its last use and its memory usage.
+#[derive(Debug)]
+struct CacheEntry {
+    glyph: Rc<CachedGlyph>,
+    stamp: u64,
+    bytes: usize,
+}
+
+impl Default for GlyphCache {
+    /// Returns a cache with [`DEFAULT_MAX_MEMORY`][Self::DEFAULT_MAX_MEMORY].
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_MEMORY)
+    }
+}
+
+impl GlyphCache {
+    /// The default memory limit, in bytes.
+    pub const DEFAULT_MAX_MEMORY: usize = 1 << 20;
+
+    /// Returns a new empty cache that uses up to `max_memory` bytes.
+    ///
+    /// A cache without memory stores nothing, and loads the glyphs each time.
+    #[must_use]
+    pub const fn new(max_memory: usize) -> Self {
+        Self {
+            glyphs: RefCell::new(BTreeMap::new()),
+            lru: RefCell::new(BTreeMap::new()),
+            stamp: Cell::new(0),
+            memory: Cell::new(0),
+            max_memory,
+            #[cfg(feature = "freetype")]
+            faces: RefCell::new(BTreeMap::new()),
+            #[cfg(feature = "freetype")]
+            face_ids: Cell::new(0),
+        }
+    }
+
+    /// Returns the memory limit, in bytes.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn max_memory(&self) -> usize { self.max_memory }
+
+    /// Returns the memory used by the cached glyphs, in bytes.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn memory_usage(&self) -> usize { self.memory.get() }
+
+    /// Returns the number of cached glyphs.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn len(&self) -> usize { self.glyphs.borrow().len() }
+
+    /// Returns `true` if there are no cached glyphs.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn is_empty(&self) -> bool { self.glyphs.borrow().is_empty() }
+
+    /// Removes all the glyphs.
+    pub fn clear(&self) {
+        self.glyphs.borrow_mut().clear();
+        self.lru.borrow_mut().clear();
+        self.memory.set(0);
+        #[cfg(feature = "freetype")]
+        self.faces.borrow_mut().clear();
+    }
+
+    /// Returns the glyph for `key`, calling `load` to create it if it isn't cached.
+    ///
+    /// A glyph bigger than the memory limit is returned without being cached.
+    pub fn glyph(
+        &self,
+        key: GlyphKey,
+        load: impl FnOnce() -> Result<CachedGlyph, AggFontError>,
+    ) -> Result<Rc<CachedGlyph>, AggFontError> {
+        let stamp = self.stamp.get() + 1;
+        self.stamp.set(stamp);
+
+        if let Some(entry) = self.glyphs.borrow_mut().get_mut(&key) {
+            let mut lru = self.lru.borrow_mut();
+            lru.remove(&entry.stamp);
+            lru.insert(stamp, key);
+            entry.stamp = stamp;
+            return Ok(Rc::clone(&entry.glyph));
+        }
+
+        let glyph = Rc::new(load()?);
+        let bytes = glyph.memory_usage() + size_of::<GlyphKey>();
+        if bytes <= self.max_memory {
+            let (mut glyphs, mut lru) = (self.glyphs.borrow_mut(), self.lru.borrow_mut());
+            while self.memory.get() + bytes > self.max_memory {
+                let Some((_, oldest)) = lru.pop_first() else { break };
+                if let Some(evicted) = glyphs.remove(&oldest) {
+                    self.memory.set(self.memory.get() - evicted.bytes);
+                }
+            }
+            glyphs.insert(key, CacheEntry { glyph: Rc::clone(&glyph), stamp, bytes });
+            lru.insert(stamp, key);
+            self.memory.set(self.memory.get() + bytes);
+        }
+        Ok(glyph)
+    }
+
+    /// Returns the unscaled outline of `glyph` in `font`.
+    ///
+    /// The outline and the advance are in font units.
+    pub fn outline_glyph(
+        &self,
+        font: &OutlineFont,
+        glyph: u16,
+    ) -> Result<Rc<CachedGlyph>, AggFontError> {
+        let key = GlyphKey {
+            face: font.signature(),
+            size: 0,
+            hinting: false,
+            render: GlyphRender::Outline,
+            offset: (0, 0),
+            glyph: u32::from(glyph),
+        };
+        self.glyph(key, || {
+            let path = font.glyph_outline(glyph)?;
+            let advance_x = f64::from(font.advance_width(glyph));
+            Ok(CachedGlyph { advance_x, advance_y: 0.0, data: GlyphData::Outline(path) })
+        })
+    }
+
+    /// Returns `glyph` in `face`, at the current size of the face.
+    ///
+    /// Bitmaps and outlines are in pixels, with the y axis pointing up.
+    #[cfg(feature = "freetype")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "freetype")))]
+    pub fn ft_glyph(
+        &self,
+        face: &freetype::Face,
+        glyph: u32,
+        render: GlyphRender,
+        hinting: bool,
+    ) -> Result<Rc<CachedGlyph>, AggFontError> {
+        let key = self.ft_key(face, glyph, render, hinting, (0, 0));
+        self.glyph(key, || {
+            face.load_glyph(glyph, ft_load_flags(hinting))?;
+            let slot = face.glyph();
+            let data = match render {
+                GlyphRender::Lcd => return ft_lcd_glyph(face),
+                GlyphRender::Gray8 => {
+                    slot.render_glyph(freetype::RenderMode::Normal)?;
+                    let bitmap = ft_bitmap(&slot.bitmap(), slot.bitmap_left(), slot.bitmap_top());
+                    GlyphData::Gray8(bitmap)
+                }
+                GlyphRender::Outline => {
+                    GlyphData::Outline(slot.outline().map(|o| ft_outline(&o)).unwrap_or_default())
+                }
+                GlyphRender::Advance => GlyphData::Empty,
+            };
+            let adv = slot.advance();
+            Ok(
+                CachedGlyph {
+                    advance_x: adv.x as f64 / 64.0,
+                    advance_y: adv.y as f64 / 64.0,
+                    data,
+                },
+            )
+        })
+    }
+
+    /// Returns the bitmap of `glyph` in `face`, rendered at a subpixel `offset`
+    /// given in 1/64 pixels.
+    #[cfg(feature = "freetype")]
+    pub(crate) fn ft_glyph_offset(
+        &self,
+        face: &freetype::Face,
+        glyph: u32,
+        hinting: bool,
+        offset: (u8, u8),
+    ) -> Result<Rc<CachedGlyph>, AggFontError> {
+        let key = self.ft_key(face, glyph, GlyphRender::Gray8, hinting, offset);
+        self.glyph(key, || {
+            face.load_glyph(glyph, ft_load_flags(hinting))?;
+            let g = face.glyph().get_glyph()?;
+            let dt = freetype::Vector { x: offset.0.into(), y: offset.1.into() };
+            g.transform(None, Some(dt))?;
+            let b = g.to_bitmap(freetype::RenderMode::Normal, None)?;
+            Ok(CachedGlyph {
+                advance_x: g.advance_x() as f64 / 65536.0,
+                advance_y: g.advance_y() as f64 / 65536.0,
+                data: GlyphData::Gray8(ft_bitmap(&b.bitmap(), b.left(), b.top())),
+            })
+        })
+    }
+
+    #[cfg(feature = "freetype")]
+    fn ft_key(
+        &self,
+        face: &freetype::Face,
+        glyph: u32,
+        render: GlyphRender,
+        hinting: bool,
+        offset: (u8, u8),
+    ) -> GlyphKey {
+        let size = face
+            .size_metrics()
+            .map_or(0, |m| (u64::from(m.x_scale as u32) << 32) | u64::from(m.y_scale as u32));
+        GlyphKey { face: self.ft_face_id(face), size, hinting, render, offset, glyph }
+    }
+
+    /// Returns the id of the `face`.
+    ///
+    /// Faces are found by their address, and get a new id when the face at
+    /// an address has another fingerprint, since the face seen there before
+    /// has been dropped.
+    #[cfg(feature = "freetype")]
+    fn ft_face_id(&self, face: &freetype::Face) -> u64 {
+        let address = core::ptr::from_ref(face.raw()) as usize;
+        let fingerprint = ft_fingerprint(face);
+        let mut faces = self.faces.borrow_mut();
+        match faces.get(&address) {
+            Some(&(seen, id)) if seen == fingerprint => id,
+            _ => {
+                let count = self.face_ids.get() + 1;
+                self.face_ids.set(count);
+                let id = fnv1a(fingerprint, &count.to_le_bytes());
+                faces.insert(address, (fingerprint, id));
+                id
+            }
+        }
+    }
+}
+
+/* helpers */
+
+/// Hashes `bytes` into `hash` with 64-bit FNV-1a.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, &b| (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// The initial value of an FNV-1a hash.
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Hashes the names, index and metrics of the `face`.
+#[cfg(feature = "freetype")]
+fn ft_fingerprint(face: &freetype::Face) -> u64 {
+    let raw = face.raw();
+    let names = [face.family_name(), face.style_name(), face.postscript_name()];
+    let hash = names.iter().fold(FNV_OFFSET, |h, name| {
+        fnv1a(fnv1a(h, name.as_deref().unwrap_or_default().as_bytes()), &[0])
+    });
+    let bbox = &raw.bbox;
+    let longs = [raw.num_faces, raw.face_index, raw.face_flags, raw.style_flags, raw.num_glyphs];
+    let hash = longs
+        .into_iter()
+        .chain([bbox.xMin, bbox.yMin, bbox.xMax, bbox.yMax])
+        .fold(hash, |h, v| fnv1a(h, &v.to_le_bytes()));
+    [raw.ascender, raw.descender, raw.height]
+        .into_iter()
+        .fold(fnv1a(hash, &raw.units_per_EM.to_le_bytes()), |h, v| fnv1a(h, &v.to_le_bytes()))
+}
+
+#[cfg(feature = "freetype")]
+fn ft_load_flags(hinting: bool) -> freetype::face::LoadFlag {
+    use freetype::face::LoadFlag;
+    if hinting {
+        LoadFlag::DEFAULT
+    } else {
+        LoadFlag::DEFAULT | LoadFlag::NO_HINTING
+    }
+}
+
+/// Renders the glyph loaded in `face` stretched three times horizontally.
+#[cfg(feature = "freetype")]
+fn ft_lcd_glyph(face: &freetype::Face) -> Result<CachedGlyph, AggFontError> {
+    let g = face.glyph().get_glyph()?;
+    let stretch = freetype::Matrix { xx: 3 << 16, xy: 0, yx: 0, yy: 1 << 16 };
+    g.transform(Some(stretch), None)?;
+    let b = g.to_bitmap(freetype::RenderMode::Normal, None)?;
+    Ok(CachedGlyph {
+        // The advance is stretched too
+        advance_x: g.advance_x() as f64 / 65536.0 / 3.0,
+        advance_y: g.advance_y() as f64 / 65536.0,
+        data: GlyphData::Gray8(ft_bitmap(&b.bitmap(), b.left(), b.top())),
+    })
+}
+
+/// Copies a FreeType bitmap, dropping the padding at the end of its rows.
+#[cfg(feature = "freetype")]
+fn ft_bitmap(bitmap: &freetype::Bitmap, left: i32, top: i32) -> GlyphBitmap {
+    let (width, rows) = (bitmap.width().max(0) as usize, bitmap.rows().max(0) as usize);
+    let buffer = bitmap_rows(bitmap.buffer(), width, rows, bitmap.pitch());
+    GlyphBitmap { left: left.into(), top: top.into(), width, rows, buffer }
+}
+
+/// Copies the `rows` of a bitmap from the top, `width` bytes each.
+///
+/// Rows are `pitch` bytes apart, and a negative pitch stores them from the bottom.
+#[cfg(feature = "freetype")]
+pub(crate) fn bitmap_rows(src: &[u8], width: usize, rows: usize, pitch: i32) -> Vec<u8> {
+    let stride = pitch.unsigned_abs() as usize;
+    let mut buffer = Vec::with_capacity(width * rows);
+    for i in 0..rows {
+        let start = iif![pitch < 0; rows - 1 - i; i] * stride;
+        buffer.extend_from_slice(&src[start..start + width]);
+    }
+    buffer
+}
+
+/// Converts a FreeType outline to a path in pixels.
+#[cfg(feature = "freetype")]
+fn ft_outline(outline: &freetype::outline::Outline) -> Path {
+    use freetype::outline::Curve;
+    let px = |v: &freetype::Vector| (v.x as f64 / 64.0, v.y as f64 / 64.0);
+    let mut path = Path::new();
+    for contour in outline.contours_iter() {
+        let (x, y) = px(contour.start());
+        path.move_to(x, y);
+        for curve in contour {
+            match curve {
+                Curve::Line(p) => {
+                    let (x, y) = px(&p);
+                    path.line_to(x, y);
+                }
+                Curve::Bezier2(c, p) => {
+                    let ((cx, cy), (x, y)) = (px(&c), px(&p));
+                    path.curve3(cx, cy, x, y);
+                }
+                Curve::Bezier3(c1, c2, p) => {
+                    let ((x1, y1), (x2, y2), (x, y)) = (px(&c1), px(&c2), px(&p));
+                    path.curve4(x1, y1, x2, y2, x, y);
+                }
+            }
+        }
+        path.close_polygon();
+    }
+    path
+}
@@ -0,0 +1,35 @@
+// agrega::font::error
+
+#[cfg(feature = "freetype")]
+use crate::_dep::freetype;
+use alloc::string::String;
+
+/// A font error.
+#[derive(Debug)]
+pub enum AggFontError {
+    /// Freetype Error
+    #[cfg(feature = "freetype")]
+    Ft(freetype::error::Error),
+    Io(String),
+    /// The font collection doesn't have a font at the given index.
+    FaceIndex(usize),
+    /// A table required to read the font is missing.
+    MissingTable(&'static str),
+    /// The font data is truncated or inconsistent.
+    Malformed(&'static str),
+    /// The font uses a format that is not supported.
+    Unsupported(&'static str),
+    /// No font has a glyph for the character.
+    GlyphNotFound(char),
+}
+#[cfg(feature = "freetype")]
+impl From<freetype::error::Error> for AggFontError {
+    fn from(err: freetype::error::Error) -> Self {
+        AggFontError::Ft(err)
+    }
+}
+impl From<String> for AggFontError {
+    fn from(err: String) -> Self {
+        AggFontError::Io(err)
+    }
+}
@@ -1,25 +1,45 @@
 // agrega::font::gsv
 
-use crate::{paths::Vertex, VertexSource};
+use crate::{
+    paths::Vertex, AggFontError, GlyphRun, LayoutFont, LineCap, LineJoin, PathCommand, Stroke,
+    TextMetrics, Transform, VertexSource, XAlign, YAlign,
+};
 use alloc::{
     string::{String, ToString},
-    vec,
     vec::Vec,
 };
+use core::ops::Range;
+use devela::iif;
 
 /* private helpers */
 
-#[derive(Debug, PartialEq)]
-enum TextStatus {
-    Initial,
-    NextChar,
-    StartGlyph,
-    Glyph,
+/// Returns the 16-bit value at the start of `v`, in the byte order of the font.
+#[inline(always)]
+const fn value(v: &[u8], big_endian: bool) -> i16 {
+    iif![big_endian; i16::from_be_bytes([v[0], v[1]]); i16::from_le_bytes([v[0], v[1]])]
 }
 
-#[inline(always)]
-const fn value(v: &[u8]) -> i16 {
-    i16::from_ne_bytes([v[0], v[1]])
+/// Checks that `font` is a valid GSV font with the given byte order.
+///
+/// The header starts with the offset of the glyph indices, and has the base
+/// height at byte 4 and the descender at byte 8. The 257 indices are the
+/// offsets of the glyph strokes, as pairs of bytes, after the indices.
+fn check_font(font: &[u8], big_endian: bool) -> Result<(), AggFontError> {
+    iif![font.len() < 10; return Err(AggFontError::Malformed("GSV header is truncated"))];
+    iif![value(&font[4..], big_endian) <= 0;
+        return Err(AggFontError::Malformed("GSV base height is not positive"))];
+    let indices = value(font, big_endian) as u16 as usize;
+    let glyphs = indices + 257 * 2;
+    iif![font.len() < glyphs; return Err(AggFontError::Malformed("GSV glyph indices are truncated"))];
+    let mut prev = 0;
+    for i in (indices..glyphs).step_by(2) {
+        let offset = value(&font[i..], big_endian) as u16 as usize;
+        iif![offset < prev || (offset - prev) % 2 != 0;
+            return Err(AggFontError::Malformed("GSV glyph offsets are unsorted or odd"))];
+        prev = offset;
+    }
+    iif![font.len() < glyphs + prev; return Err(AggFontError::Malformed("GSV glyphs are truncated"))];
+    Ok(())
 }
 
 /* public items */
@@ -40,9 +60,11 @@ pub struct GsvText {
     font: Vec<u8>,
     //loaded_font: Vec<u8>,
     //status: TextStatus,
-    #[allow(dead_code)]
+    /// The byte order of the font data.
     big_endian: bool,
     flip: bool,
+    xalign: XAlign,
+    yalign: YAlign,
     //indices: Vec<u8>,
     //glyphs: Vec<i8>,
     //bglyphs: Vec<i8>,
@@ -65,6 +87,8 @@ impl GsvText {
             font: GsvDefaultFont::get().to_vec(),
             //status: TextStatus::Initial,
             flip: false,
+            xalign: XAlign::Left,
+            yalign: YAlign::Baseline,
             big_endian: false,
             //chr: [0_u8;2],
             text: String::new(),
@@ -76,7 +100,7 @@ impl GsvText {
         self.height = height;
         self.width = width;
     }
-    /// Adjusts the spacing between characters.
+    /// Adjusts the spacing added after each character.
     pub fn space(&mut self, space: f64) {
         self.space = space;
     }
@@ -98,83 +122,270 @@ impl GsvText {
     pub fn text(&mut self, text: &str) {
         self.text = text.to_string();
     }
-}
+    /// Switches to the GSV font in `data`, like the contents of an AGG `.gsv` file.
+    ///
+    /// The byte order is detected, preferring little endian.
+    ///
+    /// # Errors
+    /// Returns [`AggFontError::Malformed`] if the data is not a valid GSV font
+    /// in either byte order, keeping the current font.
+    pub fn font(&mut self, data: &[u8]) -> Result<(), AggFontError> {
+        self.big_endian = match (check_font(data, false), check_font(data, true)) {
+            (Ok(()), _) => false,
+            (Err(_), Ok(())) => true,
+            (Err(err), Err(_)) => return Err(err),
+        };
+        self.font = data.to_vec();
+        Ok(())
+    }
+    /// Switches to the GSV font in the file at `path`.
+    ///
+    /// # Errors
+    /// Returns [`AggFontError::Io`] if the file can't be read, or
+    /// [`AggFontError::Malformed`] if it is not a valid GSV font.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "nightly", doc(cfg(feature = "std")))]
+    pub fn load_font<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<(), AggFontError> {
+        let data = std::fs::read(path).map_err(|err| AggFontError::Io(err.to_string()))?;
+        self.font(&data)
+    }
+    /// Aligns each line of text horizontally to the start point.
+    ///
+    /// The default is [`XAlign::Left`].
+    pub fn xalign(&mut self, xalign: XAlign) {
+        self.xalign = xalign;
+    }
+    /// Aligns the block of text vertically to the start point.
+    ///
+    /// The default is [`YAlign::Baseline`], for the baseline of the first line.
+    pub fn yalign(&mut self, yalign: YAlign) {
+        self.yalign = yalign;
+    }
 
-impl VertexSource for GsvText {
-    // fn rewind(&self) {}
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        let mut out = vec![];
-        let mut chars = self.text.char_indices();
-        let mut b: usize = 0;
-        let mut e: usize = 0;
-        let mut x = self.start_x;
-        let mut y = self.y;
-        let indices = value(&self.font[0..]) as usize;
-        let glyphs = indices + 257 * 2;
+    /// Returns the width of the widest line of text.
+    ///
+    /// It includes the `space` between characters, but not after the last one.
+    #[must_use]
+    pub fn text_width(&self) -> f64 {
+        self.text.split('\n').map(|line| self.line_width(line)).fold(0.0, f64::max)
+    }
 
-        let mut status = TextStatus::Initial;
-        let base_height: f64 = f64::from(value(&self.font[4..]));
-        let mut hi = self.height / base_height;
+    /// Measures the text, relative to a start point at the origin, unflipped
+    /// and unaligned.
+    ///
+    /// For several lines, the advance is the one of the widest line, and the
+    /// ink box includes all of them. Lines are `height + line_space` apart,
+    /// so the line gap is that minus the ascent and descent, and is usually negative.
+    #[must_use]
+    pub fn metrics(&self) -> TextMetrics {
+        let mut metrics = TextMetrics {
+            advance: self.text_width(),
+            ascent: self.ascent(),
+            descent: self.descent(),
+            line_gap: self.line_space + self.descent(),
+            ..TextMetrics::default()
+        };
+        let scale = self.scale(false);
+        for (i, line) in self.text.split('\n').enumerate() {
+            let (mut x, mut y) = (0.0, -(i as f64) * (self.height + self.line_space));
+            for chr in line.chars() {
+                let mut prev = (x, y);
+                for v in self.glyph(chr, &mut x, &mut y, scale) {
+                    if v.cmd == PathCommand::LineTo {
+                        metrics.add_ink(prev.0, prev.1, v.x, v.y);
+                    }
+                    prev = (v.x, v.y);
+                }
+                x += self.space;
+            }
+        }
+        metrics
+    }
+}
+
+impl GsvText {
+    /// Returns the horizontal and vertical scale of the font units.
+    fn scale(&self, flip: bool) -> (f64, f64) {
+        let base_height: f64 = f64::from(value(&self.font[4..], self.big_endian));
+        let hi = self.height / base_height;
         let wi = if self.width == 0.0 {
             hi
         } else {
             self.width / base_height
         };
-        if self.flip {
-            hi *= -1.0;
+        (wi, if flip { -hi } else { hi })
+    }
+
+    /// Returns the width of a `line` of text, without the trailing space.
+    fn line_width(&self, line: &str) -> f64 {
+        let (width, count) = line.chars().fold((0.0, 0), |(w, n), c| (w + self.advance(c), n + 1));
+        iif![count == 0; 0.0; width - self.space]
+    }
+
+    /// Returns how far up from the start point the first baseline is moved
+    /// by the vertical alignment of `lines` lines of text.
+    fn yalign_offset(&self, lines: usize) -> f64 {
+        let last = (lines.max(1) - 1) as f64 * (self.height + self.line_space);
+        match self.yalign {
+            YAlign::Top => -self.ascent(),
+            YAlign::Center => (last - self.descent() - self.ascent()) / 2.0,
+            YAlign::Bottom => last - self.descent(),
+            YAlign::Baseline => 0.0,
         }
+    }
 
-        loop {
-            match status {
-                TextStatus::Initial => {
-                    status = TextStatus::NextChar;
-                }
-                TextStatus::NextChar => match chars.next() {
-                    None => break,
-                    Some((_, chr)) => {
-                        if chr == '\n' {
-                            x = self.start_x;
-                            y -= if self.flip {
-                                -(self.height + self.line_space)
-                            } else {
-                                self.height + self.line_space
-                            };
-                        }
-                        let mut idx = chr as usize & 0xFF;
-                        idx *= 2;
-                        b = glyphs + value(&self.font[indices + idx..]) as usize;
-                        e = glyphs + value(&self.font[indices + idx + 2..]) as usize;
-                        status = TextStatus::StartGlyph;
-                    }
-                },
-                TextStatus::StartGlyph => {
-                    out.push(Vertex::move_to(x, y));
-                    status = TextStatus::Glyph;
-                }
-                TextStatus::Glyph => {
-                    for i in (b..e).step_by(2) {
-                        let dx = i32::from(self.font[i] as i8);
-                        let mut yc = self.font[i + 1] as i8;
-                        let yf = (self.font[i + 1] & 0x80) as i8;
-                        yc <<= 1;
-                        yc >>= 1;
-                        let dy = i32::from(yc);
-                        x += wi * f64::from(dx);
-                        y += hi * f64::from(dy);
-                        if yf != 0 {
-                            out.push(Vertex::move_to(x, y));
-                        } else {
-                            out.push(Vertex::line_to(x, y));
-                        }
-                    }
-                    status = TextStatus::NextChar;
-                }
+    /// Returns the byte range of the strokes of `chr` in the font.
+    fn glyph_range(&self, chr: char) -> Range<usize> {
+        let offset = |at: usize| value(&self.font[at..], self.big_endian) as u16 as usize;
+        let indices = offset(0);
+        let glyphs = indices + 257 * 2;
+        let idx = (chr as usize & 0xFF) * 2;
+        let b = glyphs + offset(indices + idx);
+        let e = glyphs + offset(indices + idx + 2);
+        b..e
+    }
+
+    /// Returns the strokes of `chr` drawn from the pen position (`x`, `y`),
+    /// moving the pen to the end of the glyph.
+    fn glyph(&self, chr: char, x: &mut f64, y: &mut f64, (wi, hi): (f64, f64)) -> Vec<Vertex<f64>> {
+        let range = self.glyph_range(chr);
+        let mut out = Vec::with_capacity(range.len() / 2 + 1);
+        out.push(Vertex::move_to(*x, *y));
+        for i in range.step_by(2) {
+            let dx = i32::from(self.font[i] as i8);
+            let mut yc = self.font[i + 1] as i8;
+            let yf = (self.font[i + 1] & 0x80) as i8;
+            yc <<= 1;
+            yc >>= 1;
+            let dy = i32::from(yc);
+            *x += wi * f64::from(dx);
+            *y += hi * f64::from(dy);
+            if yf != 0 {
+                out.push(Vertex::move_to(*x, *y));
+            } else {
+                out.push(Vertex::line_to(*x, *y));
             }
         }
         out
     }
 }
 
+impl VertexSource for GsvText {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let scale = self.scale(self.flip);
+        let up = iif![self.flip; -1.0; 1.0];
+        let lines = self.text.split('\n').count();
+        let y0 = self.y + up * self.yalign_offset(lines);
+
+        self.text.split('\n').enumerate().flat_map(move |(i, line)| {
+            let width = self.line_width(line);
+            let mut x = self.start_x
+                - match self.xalign {
+                    XAlign::Left => 0.0,
+                    XAlign::Center => width / 2.0,
+                    XAlign::Right => width,
+                };
+            let mut y = y0 - up * i as f64 * (self.height + self.line_space);
+            // The pen position carries over from one glyph to the next
+            line.chars().flat_map(move |chr| {
+                let glyph = self.glyph(chr, &mut x, &mut y, scale);
+                x += self.space;
+                glyph
+            })
+        })
+    }
+}
+
+impl LayoutFont for GsvText {
+    fn advance(&self, c: char) -> f64 {
+        let (wi, _) = self.scale(false);
+        let dx: i32 = self.glyph_range(c).step_by(2).map(|i| i32::from(self.font[i] as i8)).sum();
+        wi * f64::from(dx) + self.space
+    }
+    fn ascent(&self) -> f64 {
+        self.height
+    }
+    fn descent(&self) -> f64 {
+        self.scale(false).1 * f64::from(value(&self.font[8..], self.big_endian))
+    }
+}
+
+/// Strokes a [`GsvText`], optionally transforming the outline.
+///
+/// Like AGG's `gsv_text_outline`, the default caps and joins are round.
+#[derive(Debug)]
+pub struct GsvTextOutline<'a> {
+    stroke: Stroke<&'a GsvText>,
+    transform: Option<Transform>,
+}
+
+impl<'a> GsvTextOutline<'a> {
+    /// Returns a new `GsvTextOutline` of `text`, with a width of 1.0.
+    pub fn new(text: &'a GsvText) -> Self {
+        let mut stroke = Stroke::new(text);
+        stroke.width(1.0);
+        stroke.line_cap(LineCap::Round);
+        stroke.line_join(LineJoin::Round);
+        Self { stroke, transform: None }
+    }
+    /// Sets the width of the strokes.
+    pub fn width(&mut self, width: f64) {
+        self.stroke.width(width);
+    }
+    /// Sets the style of the ends of the strokes.
+    pub fn line_cap(&mut self, line_cap: LineCap) {
+        self.stroke.line_cap(line_cap);
+    }
+    /// Sets the style of the corners of the strokes.
+    pub fn line_join(&mut self, line_join: LineJoin) {
+        self.stroke.line_join(line_join);
+    }
+    /// Transforms the stroked outline, or leaves it untransformed with `None`.
+    pub fn transform(&mut self, transform: Option<Transform>) {
+        self.transform = transform;
+    }
+}
+
+impl VertexSource for GsvTextOutline<'_> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.stroke.vertices().map(|v| match &self.transform {
+            Some(trans) => {
+                let (x, y) = trans.transform(v.x, v.y);
+                Vertex::new(x, y, v.cmd)
+            }
+            None => v,
+        })
+    }
+}
+
+/// Renders the glyph runs of a [`TextLayout`] with the strokes of a [`GsvText`].
+///
+/// The glyphs are flipped for the y axis pointing down, like the layout.
+///
+/// [`TextLayout`]: crate::TextLayout
+#[derive(Debug, Clone, Copy)]
+pub struct GsvLayout<'a> {
+    font: &'a GsvText,
+    runs: &'a [GlyphRun],
+}
+
+impl<'a> GsvLayout<'a> {
+    /// Returns a new `GsvLayout` rendering the `runs` with `font`.
+    pub const fn new(font: &'a GsvText, runs: &'a [GlyphRun]) -> Self {
+        Self { font, runs }
+    }
+}
+
+impl VertexSource for GsvLayout<'_> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let scale = self.font.scale(true);
+        self.runs.iter().flat_map(|run| &run.glyphs).flat_map(move |glyph| {
+            let (mut x, mut y) = (glyph.x, glyph.y);
+            self.font.glyph(glyph.c, &mut x, &mut y, scale)
+        })
+    }
+}
+
 /// The default font for [`GsvText`].
 pub struct GsvDefaultFont;
 
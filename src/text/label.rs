@@ -1,44 +1,272 @@
 // agrega::font::label
 
-use crate::{Pixel, RenderingBase, Rgba8, _dep::freetype};
+use crate::{
+    _dep::freetype, AggFontError, CachedGlyph, GlyphBitmap, GlyphCache, GlyphData, GlyphRender,
+    GlyphRun, LayoutFont, LcdFilter, LcdPixel, Pixel, RenderingBase, Rgba8, SubpixelOrder,
+    TextMetrics, XAlign, YAlign,
+};
 use alloc::{
+    rc::Rc,
     string::{String, ToString},
+    vec,
     vec::Vec,
 };
+use devela::iif;
 
-/// Returns the string width using the given `font_face`.
-pub fn string_width(txt: &str, font_face: &freetype::Face) -> f64 {
-    let mut width = 0.0;
-    for c in txt.chars() {
-        let glyph_index = font_face.get_char_index(c as usize).unwrap();
-        font_face.load_glyph(glyph_index, freetype::face::LoadFlag::DEFAULT).unwrap();
-        let glyph = font_face.glyph();
-        glyph.render_glyph(freetype::RenderMode::Normal).unwrap();
-        let adv = glyph.advance();
-        width += adv.x as f64
-    }
-    width / 64.0
+/// What to draw for the characters that no face of a [`FontChain`] has.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MissingGlyph {
+    /// Fails with [`AggFontError::GlyphNotFound`].
+    #[default]
+    Error,
+    /// Draws the `.notdef` glyph of the first face, usually an empty box.
+    Notdef,
+    /// Draws the given replacement character, like `'\u{FFFD}'`, or the
+    /// `.notdef` glyph of the first face if it's also missing.
+    Replace(char),
+}
+
+/// A FreeType face, with fallback faces for the characters it doesn't have.
+///
+/// The vertical metrics are always the ones of the first face. The fallback
+/// faces must be set to the same size.
+///
+/// # Examples
+/// ```no_run
+/// use agrega::{_dep::freetype::Library, FontChain, GlyphCache, MissingGlyph, text_metrics_cached};
+///
+/// let lib = Library::init().unwrap();
+/// let latin = lib.new_face("latin.ttf", 0).unwrap();
+/// let symbols = lib.new_face("symbols.ttf", 0).unwrap();
+/// latin.set_char_size(12 * 64, 0, 72, 0).unwrap();
+/// symbols.set_char_size(12 * 64, 0, 72, 0).unwrap();
+///
+/// let fonts = FontChain::new(&latin)
+///     .fallback(&symbols)
+///     .missing_glyph(MissingGlyph::Replace('?'));
+/// let metrics = text_metrics_cached("Done ✓", &fonts, &GlyphCache::default()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FontChain<'a> {
+    faces: Vec<&'a freetype::Face>,
+    missing: MissingGlyph,
+}
+
+impl<'a> FontChain<'a> {
+    /// Returns a new `FontChain` with the given first `face`.
+    pub fn new(face: &'a freetype::Face) -> Self {
+        Self { faces: vec![face], missing: MissingGlyph::Error }
+    }
+    /// Adds a fallback `face`, searched after the previous ones, and returns itself.
+    pub fn fallback(mut self, face: &'a freetype::Face) -> Self {
+        self.faces.push(face);
+        self
+    }
+    /// Sets the `policy` for the characters missing from every face, and returns itself.
+    pub const fn missing_glyph(mut self, policy: MissingGlyph) -> Self {
+        self.missing = policy;
+        self
+    }
+
+    /// Returns the faces, in the order they are searched.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn faces(&self) -> &[&'a freetype::Face] { &self.faces }
+
+    /// Returns the first face.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn face(&self) -> &'a freetype::Face { self.faces[0] }
+
+    /// Returns the face and the glyph index for the character `c`.
+    ///
+    /// # Errors
+    /// Returns [`AggFontError::GlyphNotFound`] if no face has the character,
+    /// and the policy is [`MissingGlyph::Error`].
+    pub fn glyph(&self, c: char) -> Result<(&'a freetype::Face, u32), AggFontError> {
+        if let Some(glyph) = self.find(c) {
+            return Ok(glyph);
+        }
+        match self.missing {
+            MissingGlyph::Error => Err(AggFontError::GlyphNotFound(c)),
+            MissingGlyph::Notdef => Ok((self.face(), 0)),
+            MissingGlyph::Replace(r) => Ok(self.find(r).unwrap_or((self.face(), 0))),
+        }
+    }
+
+    /// Sets the character `size` of all the faces, in points at 72 dpi.
+    pub fn set_char_size(&self, size: f64) -> Result<(), AggFontError> {
+        for face in &self.faces {
+            face.set_char_size((size * 64.0) as isize, 0, 72, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first face that has the character `c`, and its glyph index.
+    fn find(&self, c: char) -> Option<(&'a freetype::Face, u32)> {
+        self.faces
+            .iter()
+            .find_map(|&face| Some((face, face.get_char_index(c as usize)?)))
+    }
+
+    /// Returns the horizontal kerning between two glyphs, in pixels.
+    ///
+    /// Glyphs from different faces have no kerning.
+    fn kerning(left: (&freetype::Face, u32), right: (&freetype::Face, u32)) -> f64 {
+        iif![!core::ptr::eq(left.0, right.0); return 0.0];
+        kerning(left.0, left.1, right.1)
+    }
+}
+
+impl<'a> From<&'a freetype::Face> for FontChain<'a> {
+    fn from(face: &'a freetype::Face) -> Self {
+        Self::new(face)
+    }
+}
+
+/// Returns the string width using the given `font_face`, including kerning.
+///
+/// The glyphs are loaded without being rendered.
+///
+/// # Errors
+/// Returns an error if the font has no size, or is missing some character.
+pub fn string_width(txt: &str, font_face: &freetype::Face) -> Result<f64, AggFontError> {
+    string_width_cached(txt, &font_face.into(), &uncached())
+}
+
+/// Returns the string width using the given `fonts`, including kerning,
+/// reading the glyph advances through `cache`.
+///
+/// The glyphs are loaded without being rendered.
+///
+/// # Errors
+/// Returns an error if the first font has no size, or if a character is
+/// missing and the policy is [`MissingGlyph::Error`].
+pub fn string_width_cached(
+    txt: &str,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+) -> Result<f64, AggFontError> {
+    Ok(measure(txt, fonts, cache, false)?.advance)
 }
 
 /// Returns the line height of the given `font_face`.
-pub fn line_height(font_face: &freetype::Face) -> f64 {
-    let met = font_face.size_metrics().unwrap();
-    (met.ascender - met.descender) as f64 / 64.0
+///
+/// # Errors
+/// Returns an error if the font has no size.
+pub fn line_height(font_face: &freetype::Face) -> Result<f64, AggFontError> {
+    let met = size_metrics(font_face)?;
+    Ok((met.ascender - met.descender) as f64 / 64.0)
+}
+
+/// Measures the text using the given `font_face`, at its current size.
+///
+/// The glyphs are rendered on each call to measure their ink box,
+/// use [`text_metrics_cached`] to keep them.
+///
+/// # Errors
+/// Returns an error if the font has no size, or is missing some character.
+pub fn text_metrics(txt: &str, font_face: &freetype::Face) -> Result<TextMetrics, AggFontError> {
+    text_metrics_cached(txt, &font_face.into(), &uncached())
+}
+
+/// Measures the text using the given `fonts`, at their current size,
+/// reading the glyphs through `cache`.
+///
+/// The ink box covers the glyph bitmaps drawn with hinting, without subpixel offsets.
+///
+/// # Errors
+/// Returns an error if the first font has no size, if a glyph can't be loaded,
+/// or if a character is missing and the policy is [`MissingGlyph::Error`].
+pub fn text_metrics_cached(
+    txt: &str,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+) -> Result<TextMetrics, AggFontError> {
+    measure(txt, fonts, cache, true)
 }
 
-/// Horizontal text alignment.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum XAlign {
-    Left,
-    Center,
-    Right,
+/// Measures the text, with its ink box if `ink` is true, or else loading the
+/// glyphs without rendering them.
+fn measure(
+    txt: &str,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+    ink: bool,
+) -> Result<TextMetrics, AggFontError> {
+    let met = size_metrics(fonts.face())?;
+    let (ascent, descent) = (met.ascender as f64 / 64.0, met.descender as f64 / 64.0);
+    let line_gap = met.height as f64 / 64.0 - (ascent - descent);
+    let mut metrics = TextMetrics { ascent, descent, line_gap, ..TextMetrics::default() };
+    let (mut x, mut prev) = (0.0, None);
+    for c in txt.chars() {
+        let (face, index) = fonts.glyph(c)?;
+        iif![let Some(prev) = prev; x += FontChain::kerning(prev, (face, index))];
+        prev = Some((face, index));
+        let render = iif![ink; GlyphRender::Gray8; GlyphRender::Advance];
+        let glyph = cache.ft_glyph(face, index, render, true)?;
+        if let GlyphData::Gray8(b) = &glyph.data {
+            if b.width > 0 && b.rows > 0 {
+                let (left, top) = (x + b.left as f64, b.top as f64);
+                metrics.add_ink(left, top - b.rows as f64, left + b.width as f64, top);
+            }
+        }
+        x += glyph.advance_x;
+    }
+    metrics.advance = x;
+    Ok(metrics)
 }
-/// Vertical text alignment.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum YAlign {
-    Top,
-    Center,
-    Bottom,
+
+/// FreeType faces with a glyph cache, that measure text for a [`TextLayout`].
+///
+/// The characters missing from the fonts measure nothing.
+///
+/// [`TextLayout`]: crate::TextLayout
+#[derive(Debug, Clone)]
+pub struct FtFont<'a> {
+    fonts: FontChain<'a>,
+    cache: &'a GlyphCache,
+}
+
+impl<'a> FtFont<'a> {
+    /// Returns a new `FtFont` reading the glyphs of a face or a [`FontChain`]
+    /// through `cache`.
+    #[inline]
+    pub fn new(fonts: impl Into<FontChain<'a>>, cache: &'a GlyphCache) -> Self {
+        Self { fonts: fonts.into(), cache }
+    }
+    /// Returns the first FreeType face.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn face(&self) -> &'a freetype::Face { self.fonts.face() }
+
+    /// Returns the font chain.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn fonts(&self) -> &FontChain<'a> { &self.fonts }
+
+    /// Returns the glyph cache.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn cache(&self) -> &'a GlyphCache { self.cache }
+}
+
+impl LayoutFont for FtFont<'_> {
+    fn advance(&self, c: char) -> f64 {
+        let Ok((face, index)) = self.fonts.glyph(c) else { return 0.0 };
+        let glyph = self.cache.ft_glyph(face, index, GlyphRender::Advance, true);
+        glyph.map_or(0.0, |glyph| glyph.advance_x)
+    }
+    fn kerning(&self, left: char, right: char) -> f64 {
+        match (self.fonts.glyph(left), self.fonts.glyph(right)) {
+            (Ok(left), Ok(right)) => FontChain::kerning(left, right),
+            _ => 0.0,
+        }
+    }
+    fn ascent(&self) -> f64 {
+        self.face().size_metrics().map_or(0.0, |met| met.ascender as f64 / 64.0)
+    }
+    fn descent(&self) -> f64 {
+        self.face().size_metrics().map_or(0.0, |met| met.descender as f64 / 64.0)
+    }
+    fn line_height(&self) -> f64 {
+        self.face().size_metrics().map_or(0.0, |met| met.height as f64 / 64.0)
+    }
 }
 
 /// Used for rendering text, rasterized with `freetype`.
@@ -49,9 +277,13 @@ pub struct Label<'a> {
     xa: XAlign,
     ya: YAlign,
     color: Rgba8,
-    font: &'a freetype::Face,
-    #[allow(dead_code)]
+    fonts: FontChain<'a>,
+    cache: Option<&'a GlyphCache>,
+    /// The glyphs of the label, when it has no shared cache.
+    own_cache: GlyphCache,
     size: f64,
+    order: SubpixelOrder,
+    filter: LcdFilter,
 }
 
 impl<'a> Label<'a> {
@@ -64,25 +296,33 @@ impl<'a> Label<'a> {
         size: f64,
         font: &'a freetype::Face,
     ) -> Result<Self, AggFontError> {
-        let resolution = 72;
-        font.set_char_size((size * 64.0) as isize, 0, resolution, 0)?;
+        let fonts = FontChain::new(font);
+        fonts.set_char_size(size)?;
         Ok(Self {
             txt: txt.to_string(),
             x,
             y,
             xa: XAlign::Left,
-            ya: YAlign::Bottom,
+            ya: YAlign::Baseline,
             color: Rgba8::black(),
             size,
-            font,
+            fonts,
+            cache: None,
+            own_cache: GlyphCache::default(),
+            order: SubpixelOrder::Rgb,
+            filter: LcdFilter::default(),
         })
     }
     /// Returns the `(width, height)` of the text.
     #[inline]
-    pub fn size(&self) -> (f64, f64) {
-        let w = string_width(&self.txt, self.font);
-        let h = line_height(self.font);
-        (w, h)
+    pub fn size(&self) -> Result<(f64, f64), AggFontError> {
+        let metrics = self.metrics()?;
+        Ok((metrics.advance, metrics.height()))
+    }
+    /// Returns the metrics of the text.
+    #[inline]
+    pub fn metrics(&self) -> Result<TextMetrics, AggFontError> {
+        text_metrics_cached(&self.txt, &self.fonts, self.glyph_cache())
     }
     /// Sets the `horizontal_alignment` and returns itself.
     #[inline]
@@ -102,32 +342,135 @@ impl<'a> Label<'a> {
         self.color = color;
         self
     }
+    /// Sets a glyph `cache` to share with other labels, and returns itself.
+    ///
+    /// Without a shared cache, the label keeps its glyphs in its own one.
+    #[inline]
+    pub const fn cache(mut self, cache: &'a GlyphCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    /// Adds a fallback `font` for the missing characters, sets it to the size
+    /// of the label, and returns itself.
+    #[inline]
+    pub fn fallback(mut self, font: &'a freetype::Face) -> Result<Self, AggFontError> {
+        font.set_char_size((self.size * 64.0) as isize, 0, 72, 0)?;
+        self.fonts = self.fonts.fallback(font);
+        Ok(self)
+    }
+    /// Sets the `policy` for the characters missing from every font, and returns itself.
+    #[inline]
+    pub fn missing_glyph(mut self, policy: MissingGlyph) -> Self {
+        self.fonts = self.fonts.missing_glyph(policy);
+        self
+    }
+
+    /// Sets the subpixel `order` of the screen, used by [`draw_lcd`][Self::draw_lcd],
+    /// and returns itself.
+    #[inline]
+    pub const fn subpixel_order(mut self, order: SubpixelOrder) -> Self {
+        self.order = order;
+        self
+    }
+    /// Sets the `filter` used by [`draw_lcd`][Self::draw_lcd], and returns itself.
+    #[inline]
+    pub const fn lcd_filter(mut self, filter: LcdFilter) -> Self {
+        self.filter = filter;
+        self
+    }
 
     /// Draws the text on the given `rendering_base`.
     ///
     /// It rounds the floating-point coordinates towards 0.
+    ///
+    /// # Errors
+    /// See [`draw_text_aligned`].
     #[inline]
-    pub fn draw<T: Pixel>(&mut self, rendering_base: &mut RenderingBase<T>) {
-        draw_text(&self.txt, self.x as i64, self.y as i64, self.color, self.font, rendering_base);
+    pub fn draw<T: Pixel>(
+        &mut self,
+        rendering_base: &mut RenderingBase<T>,
+    ) -> Result<(), AggFontError> {
+        draw_text_aligned(
+            &self.txt,
+            self.x as i64,
+            self.y as i64,
+            self.xa,
+            self.ya,
+            self.color,
+            &self.fonts,
+            self.glyph_cache(),
+            rendering_base,
+        )
     }
 
     /// Draws the text on the given `rendering_base`, with subpixel positioning.
+    ///
+    /// # Errors
+    /// See [`draw_text_subpixel_cached`].
     #[inline]
-    pub fn draw_subpixel<T: Pixel>(&mut self, rendering_base: &mut RenderingBase<T>) {
-        draw_text_subpixel(
+    pub fn draw_subpixel<T: Pixel>(
+        &mut self,
+        rendering_base: &mut RenderingBase<T>,
+    ) -> Result<(), AggFontError> {
+        draw_text_subpixel_cached(
             &self.txt,
             self.x,
             self.y,
             self.xa,
             self.ya,
             self.color,
-            self.font,
+            &self.fonts,
+            self.glyph_cache(),
             rendering_base,
-        );
+        )
+    }
+
+    /// Draws the text on the given `rendering_base` for LCD screens, covering
+    /// each color channel on its own, with a third of a pixel of horizontal
+    /// resolution.
+    ///
+    /// The glyphs are rendered three times wider, and filtered with the
+    /// [`lcd_filter`][Self::lcd_filter] for the [`subpixel_order`][Self::subpixel_order].
+    ///
+    /// # Errors
+    /// See [`draw_text_subpixel_cached`].
+    pub fn draw_lcd<T: LcdPixel>(
+        &mut self,
+        rendering_base: &mut RenderingBase<T>,
+    ) -> Result<(), AggFontError> {
+        let cache = self.glyph_cache();
+        let metrics = measure(&self.txt, &self.fonts, cache, false)?;
+        let (dx, dy) = align(&metrics, self.xa, self.ya);
+        let (mut x, y) = (self.x + dx, (self.y + dy).round() as i64);
+
+        // Collect the glyphs, in subpixels, before blending any
+        let mut glyphs = Vec::new();
+        let mut prev = None;
+        for c in self.txt.chars() {
+            let (face, index) = self.fonts.glyph(c)?;
+            iif![let Some(prev) = prev; x += FontChain::kerning(prev, (face, index))];
+            prev = Some((face, index));
+            let glyph = cache.ft_glyph(face, index, GlyphRender::Lcd, true)?;
+            glyphs.push(((x * 3.0).round() as i64, glyph.clone()));
+            x += glyph.advance_x;
+        }
+        blend_lcd(rendering_base, &glyphs, y, self.color, self.order, &self.filter);
+        Ok(())
+    }
+
+    /// Returns the shared glyph cache, or else the own one.
+    fn glyph_cache(&self) -> &GlyphCache {
+        self.cache.unwrap_or(&self.own_cache)
     }
 }
 
-/// Draws text.
+/// Draws text centered at (`x`, `y`).
+///
+/// The glyphs are rendered on each call, use [`draw_text_cached`] to keep them.
+///
+/// # Errors
+/// Returns an error if the font has no size, or is missing some character.
+/// Nothing is drawn then.
 pub fn draw_text<T: Pixel>(
     txt: &str,
     x: i64,
@@ -135,44 +478,73 @@ pub fn draw_text<T: Pixel>(
     color: Rgba8,
     font: &freetype::Face,
     ren_base: &mut RenderingBase<T>,
-) {
-    let (mut x, mut y) = (x, y);
-    let width = string_width(txt, font);
-    let height = line_height(font);
-    // Shift to center justification, x and y
-    let dx = (width / 2.0).round() as i64;
-    let dy = (height / 2.0).round() as i64;
-    x -= dx;
-    y += dy;
+) -> Result<(), AggFontError> {
+    draw_text_cached(txt, x, y, color, &font.into(), &uncached(), ren_base)
+}
+
+/// Draws text centered at (`x`, `y`), reading the glyphs through `cache`.
+///
+/// # Errors
+/// See [`draw_text_aligned`].
+#[inline]
+pub fn draw_text_cached<T: Pixel>(
+    txt: &str,
+    x: i64,
+    y: i64,
+    color: Rgba8,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+    ren_base: &mut RenderingBase<T>,
+) -> Result<(), AggFontError> {
+    let (xa, ya) = (XAlign::Center, YAlign::Center);
+    draw_text_aligned(txt, x, y, xa, ya, color, fonts, cache, ren_base)
+}
+
+/// Draws text aligned at (`x`, `y`) by its metrics, reading the glyphs through `cache`.
+///
+/// # Errors
+/// Returns an error if the first font has no size, if a glyph can't be loaded,
+/// or if a character is missing and the policy is [`MissingGlyph::Error`].
+/// Nothing is drawn then.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_aligned<T: Pixel>(
+    txt: &str,
+    x: i64,
+    y: i64,
+    xalign: XAlign,
+    yalign: YAlign,
+    color: Rgba8,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+    ren_base: &mut RenderingBase<T>,
+) -> Result<(), AggFontError> {
+    let metrics = measure(txt, fonts, cache, false)?;
+    let (dx, dy) = align(&metrics, xalign, yalign);
+    let (mut x, mut y) = (x + dx.round() as i64, y + dy.round() as i64);
+    let mut prev = None;
     for c in txt.chars() {
-        let glyph_index = font.get_char_index(c as usize).unwrap();
-        font.load_glyph(glyph_index, freetype::face::LoadFlag::DEFAULT).unwrap();
-        font.glyph().render_glyph(freetype::RenderMode::Normal).unwrap();
-        let g = font.glyph().bitmap();
-        let left = font.glyph().bitmap_left() as i64;
-        let top = font.glyph().bitmap_top() as i64;
-        let buf: Vec<_> = g.buffer().iter().map(|&x| x as u64).collect();
-        let rows = g.rows() as i64;
-        let pitch = g.pitch().unsigned_abs() as usize;
-        let width = g.width() as i64;
-        for i in 0..rows {
-            ren_base.blend_solid_hspan(
-                x + left,
-                y - top + i,
-                width,
-                color,
-                &buf[pitch * i as usize..],
-            );
+        let (face, index) = fonts.glyph(c)?;
+        iif![let Some(prev) = prev; x += FontChain::kerning(prev, (face, index)).round() as i64];
+        prev = Some((face, index));
+        let glyph = cache.ft_glyph(face, index, GlyphRender::Gray8, true)?;
+        if let GlyphData::Gray8(bitmap) = &glyph.data {
+            blend_bitmap(ren_base, bitmap, x, y, color);
         }
-        let adv = font.glyph().advance();
-        x += (adv.x as f64 / 64.0).round() as i64;
-        y += (adv.y as f64 / 64.0).round() as i64;
+        x += glyph.advance_x.round() as i64;
+        y += glyph.advance_y.round() as i64;
     }
+    Ok(())
 }
 
 /// Draws text with subpixel-positioning.
 ///
 /// - [Freetype Subpixel positioning](https://freetype.org/freetype2/docs/glyphs/glyphs-5.html#section-2)
+///
+/// The glyphs are rendered on each call, use [`draw_text_subpixel_cached`] to keep them.
+///
+/// # Errors
+/// Returns an error if the font has no size, or is missing some character.
+/// Nothing is drawn then.
 #[allow(clippy::too_many_arguments)]
 pub fn draw_text_subpixel<T: Pixel>(
     txt: &str,
@@ -183,70 +555,176 @@ pub fn draw_text_subpixel<T: Pixel>(
     color: Rgba8,
     font: &freetype::Face,
     ren_base: &mut RenderingBase<T>,
-) {
-    let (mut x, mut y) = (x, y);
-    let width = string_width(txt, font);
+) -> Result<(), AggFontError> {
+    let (fonts, cache) = (font.into(), uncached());
+    draw_text_subpixel_cached(txt, x, y, xalign, yalign, color, &fonts, &cache, ren_base)
+}
 
-    let asc = font.size_metrics().unwrap().ascender as f64 / 64.0;
-    x += match xalign {
+/// Draws text with subpixel-positioning, reading the glyphs through `cache`.
+///
+/// Each glyph is cached once for every subpixel offset it is drawn at.
+///
+/// # Errors
+/// Returns an error if the first font has no size, if a glyph can't be loaded,
+/// or if a character is missing and the policy is [`MissingGlyph::Error`].
+/// Nothing is drawn then.
+#[allow(clippy::too_many_arguments)]
+pub fn draw_text_subpixel_cached<T: Pixel>(
+    txt: &str,
+    x: f64,
+    y: f64,
+    xalign: XAlign,
+    yalign: YAlign,
+    color: Rgba8,
+    fonts: &FontChain,
+    cache: &GlyphCache,
+    ren_base: &mut RenderingBase<T>,
+) -> Result<(), AggFontError> {
+    let metrics = measure(txt, fonts, cache, false)?;
+    let (dx, dy) = align(&metrics, xalign, yalign);
+    let (mut x, mut y) = (x + dx, y + dy);
+    let mut prev = None;
+    for c in txt.chars() {
+        let (face, index) = fonts.glyph(c)?;
+        iif![let Some(prev) = prev; x += FontChain::kerning(prev, (face, index))];
+        prev = Some((face, index));
+        let glyph = draw_glyph(ren_base, face, cache, index, x, y, color)?;
+        x += glyph.advance_x;
+        y += glyph.advance_y;
+    }
+    Ok(())
+}
+
+/// Draws the glyph runs of a [`TextLayout`] measured with `font`, with subpixel positioning.
+///
+/// # Errors
+/// Returns an error if a glyph can't be loaded, or if a character is missing
+/// and the policy is [`MissingGlyph::Error`]. The glyphs before it are drawn.
+///
+/// [`TextLayout`]: crate::TextLayout
+pub fn draw_layout<T: Pixel>(
+    runs: &[GlyphRun],
+    color: Rgba8,
+    font: &FtFont,
+    ren_base: &mut RenderingBase<T>,
+) -> Result<(), AggFontError> {
+    for glyph in runs.iter().flat_map(|run| &run.glyphs) {
+        let (face, index) = font.fonts.glyph(glyph.c)?;
+        draw_glyph(ren_base, face, font.cache, index, glyph.x, glyph.y, color)?;
+    }
+    Ok(())
+}
+
+/* private helpers */
+
+/// Returns a cache that stores nothing, to load the glyphs directly.
+const fn uncached() -> GlyphCache {
+    GlyphCache::new(0)
+}
+
+/// Returns the size metrics of the `font`, or an error if it has no size.
+fn size_metrics(font: &freetype::Face) -> Result<freetype::ffi::FT_Size_Metrics, AggFontError> {
+    font.size_metrics().ok_or(AggFontError::Ft(freetype::Error::InvalidSizeHandle))
+}
+
+/// Returns the horizontal kerning between two glyphs, in pixels.
+fn kerning(font: &freetype::Face, left: u32, right: u32) -> f64 {
+    iif![!font.has_kerning(); return 0.0];
+    let mode = freetype::face::KerningMode::KerningDefault;
+    font.get_kerning(left, right, mode).map_or(0.0, |v| v.x as f64 / 64.0)
+}
+
+/// Returns the offset from the aligned position to the pen origin, with the
+/// y axis pointing down.
+fn align(metrics: &TextMetrics, xalign: XAlign, yalign: YAlign) -> (f64, f64) {
+    let dx = match xalign {
         XAlign::Left => 0.0,
-        XAlign::Right => -width,
-        XAlign::Center => -width / 2.0,
+        XAlign::Center => -metrics.advance / 2.0,
+        XAlign::Right => -metrics.advance,
     };
-    y += match yalign {
-        YAlign::Top => asc,
-        YAlign::Bottom => 0.0,
-        YAlign::Center => asc / 2.0,
+    let dy = match yalign {
+        YAlign::Top => metrics.ascent,
+        YAlign::Center => (metrics.ascent + metrics.descent) / 2.0,
+        YAlign::Bottom => metrics.descent,
+        YAlign::Baseline => 0.0,
     };
+    (dx, dy)
+}
 
-    for c in txt.chars() {
-        let glyph_index = font.get_char_index(c as usize).unwrap();
-        font.load_glyph(glyph_index, freetype::face::LoadFlag::DEFAULT).unwrap();
-
-        let glyph = font.glyph().get_glyph().unwrap();
-        let dt = freetype::Vector {
-            x: ((x - x.floor()) * 64.0).round() as i64,
-            y: ((y - y.floor()) * 64.0).round() as i64,
-        };
-        glyph.transform(None, Some(dt)).unwrap();
-        let g = glyph.to_bitmap(freetype::RenderMode::Normal, None).unwrap();
-        let left = g.left() as i64;
-        let top = g.top() as i64;
-        let bit = g.bitmap();
-        let buf: Vec<_> = bit.buffer().iter().map(|&x| x as u64).collect();
-        let rows = bit.rows() as i64;
-        let width = bit.width() as i64;
-        let pitch = bit.pitch().unsigned_abs() as usize;
-        for i in 0..rows {
-            ren_base.blend_solid_hspan(
-                x.floor() as i64 + left,
-                y.floor() as i64 + i - top,
-                width,
-                color,
-                &buf[pitch * i as usize..],
-            );
-        }
-
-        x += glyph.advance_x() as f64 / 65536.0;
-        y += glyph.advance_y() as f64 / 65536.0;
+/// Draws the glyph `index` with its origin at (`x`, `y`), with subpixel positioning.
+fn draw_glyph<T: Pixel>(
+    ren_base: &mut RenderingBase<T>,
+    font: &freetype::Face,
+    cache: &GlyphCache,
+    index: u32,
+    x: f64,
+    y: f64,
+    color: Rgba8,
+) -> Result<Rc<CachedGlyph>, AggFontError> {
+    let offset = (((x - x.floor()) * 64.0).round() as u8, ((y - y.floor()) * 64.0).round() as u8);
+    let glyph = cache.ft_glyph_offset(font, index, true, offset)?;
+    if let GlyphData::Gray8(bitmap) = &glyph.data {
+        blend_bitmap(ren_base, bitmap, x.floor() as i64, y.floor() as i64, color);
     }
+    Ok(glyph)
 }
 
-/// A font error.
-#[derive(Debug)]
-pub enum AggFontError {
-    /// Freetype Error
-    Ft(freetype::error::Error),
-    Io(String),
-}
-impl From<freetype::error::Error> for AggFontError {
-    fn from(err: freetype::error::Error) -> Self {
-        AggFontError::Ft(err)
+/// Blends the LCD `glyphs`, with their origins at their subpixel positions
+/// and the baseline at `y`.
+fn blend_lcd<T: LcdPixel>(
+    ren_base: &mut RenderingBase<T>,
+    glyphs: &[(i64, Rc<CachedGlyph>)],
+    y: i64,
+    color: Rgba8,
+    order: SubpixelOrder,
+    filter: &LcdFilter,
+) {
+    let bitmaps: Vec<_> = glyphs
+        .iter()
+        .filter_map(|(x, glyph)| match &glyph.data {
+            GlyphData::Gray8(b) if b.width > 0 && b.rows > 0 => Some((x + b.left, b)),
+            _ => None,
+        })
+        .collect();
+    iif![bitmaps.is_empty(); return];
+    let left = bitmaps.iter().map(|(x, _)| *x).min().unwrap_or(0);
+    let right = bitmaps.iter().map(|(x, b)| x + b.width as i64).max().unwrap_or(0);
+    let top = bitmaps.iter().map(|(_, b)| b.top).max().unwrap_or(0);
+    let bottom = bitmaps.iter().map(|(_, b)| b.top - b.rows as i64).min().unwrap_or(0);
+
+    // The covers of overlapping glyphs are added, and each row filtered at once
+    let mut row = vec![0_u8; (right - left) as usize];
+    for line in bottom..top {
+        row.fill(0);
+        for (x, b) in &bitmaps {
+            iif![line >= b.top || line < b.top - b.rows as i64; continue];
+            let start = (x - left) as usize;
+            let covers = b.row((b.top - 1 - line) as usize);
+            for (sum, &cover) in row[start..].iter_mut().zip(covers) {
+                *sum = sum.saturating_add(cover);
+            }
+        }
+        // The filtered covers start two subpixels to the left
+        let start = left - 2;
+        let pixels = order.pixels(&filter.apply(&row), start.rem_euclid(3) as usize);
+        ren_base.blend_lcd_hspan(start.div_euclid(3), y - 1 - line, color, &pixels);
     }
 }
-impl From<String> for AggFontError {
-    fn from(err: String) -> Self {
-        AggFontError::Io(err)
+
+/// Blends a glyph `bitmap` with its origin at (`x`, `y`).
+fn blend_bitmap<T: Pixel>(
+    ren_base: &mut RenderingBase<T>,
+    bitmap: &GlyphBitmap,
+    x: i64,
+    y: i64,
+    color: Rgba8,
+) {
+    let mut covers = Vec::with_capacity(bitmap.width);
+    for i in 0..bitmap.rows {
+        covers.clear();
+        covers.extend(bitmap.row(i).iter().map(|&c| u64::from(c)));
+        let (x, y) = (x + bitmap.left, y - bitmap.top + i as i64);
+        ren_base.blend_solid_hspan(x, y, bitmap.width as i64, color, &covers);
     }
 }
 
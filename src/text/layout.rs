@@ -0,0 +1,374 @@
+// agrega::font::layout
+//
+//! Text layout
+//!
+//! A [`TextLayout`] breaks a string into lines at hard line breaks, and at
+//! whitespace to fit a maximum width, aligns them, and positions their glyphs
+//! as [`GlyphRun`]s. Any [`LayoutFont`] can measure the text, and the runs can
+//! be drawn with FreeType bitmaps using `draw_layout`, or with [`GsvText`]
+//! strokes using [`GsvLayout`].
+//!
+//! # Examples
+//! ```
+//! use agrega::{GsvLayout, GsvText, Overflow, TextLayout, VertexSource, XAlign};
+//!
+//! let mut font = GsvText::new();
+//! font.size(10.0, 0.0);
+//! let runs = TextLayout::new("The quick brown fox jumps over the lazy dog")
+//!     .position(10.0, 10.0)
+//!     .max_width(100.0)
+//!     .xalign(XAlign::Center)
+//!     .overflow(Overflow::Ellipsis)
+//!     .layout(&font);
+//! assert!(runs.len() > 1);
+//! assert!(runs.iter().all(|run| run.width <= 100.0));
+//!
+//! let strokes = GsvLayout::new(&font, &runs).xconvert();
+//! assert!(!strokes.is_empty());
+//! ```
+//!
+//! [`GsvText`]: crate::GsvText
+//! [`GsvLayout`]: crate::GsvLayout
+//
+// TOC
+// - enum XAlign
+// - enum YAlign
+// - enum Overflow
+// - trait LayoutFont
+// - struct PositionedGlyph
+// - struct GlyphRun
+// - struct TextLayout
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use devela::iif;
+
+/// Horizontal text alignment.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum XAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Vertical text alignment.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum YAlign {
+    /// The ascent is at the given position.
+    Top,
+    /// The middle between the ascent and the descent is at the given position.
+    Center,
+    /// The descent is at the given position.
+    Bottom,
+    /// The baseline is at the given position.
+    #[default]
+    Baseline,
+}
+
+/// What to do with the lines that don't fit the maximum width of a [`TextLayout`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// The lines are kept whole.
+    #[default]
+    Visible,
+    /// The glyphs that end beyond the maximum width are dropped.
+    Clip,
+    /// The line is shortened to end with an ellipsis (`...`).
+    Ellipsis,
+}
+
+/// A font that can measure text for a [`TextLayout`].
+///
+/// Vertical values have the y axis pointing up from the baseline.
+pub trait LayoutFont {
+    /// Returns the horizontal advance of the character `c`.
+    fn advance(&self, c: char) -> f64;
+
+    /// Returns the kerning adjustment between the consecutive characters
+    /// `left` and `right`.
+    fn kerning(&self, left: char, right: char) -> f64 {
+        let _ = (left, right);
+        0.0
+    }
+
+    /// Returns the distance from the baseline up to the top of the tallest glyphs.
+    fn ascent(&self) -> f64;
+
+    /// Returns the distance from the baseline to the bottom of the lowest
+    /// glyphs, usually negative.
+    fn descent(&self) -> f64;
+
+    /// Returns the distance between the baselines of consecutive lines.
+    fn line_height(&self) -> f64 {
+        self.ascent() - self.descent()
+    }
+}
+
+/// A glyph positioned by a [`TextLayout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PositionedGlyph {
+    /// The character of the glyph.
+    pub c: char,
+    /// The horizontal position of the pen.
+    pub x: f64,
+    /// The vertical position of the baseline.
+    pub y: f64,
+}
+
+/// A line of glyphs positioned by a [`TextLayout`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GlyphRun {
+    /// The text of the line, as drawn.
+    pub text: String,
+    /// The horizontal position of the start of the line.
+    pub x: f64,
+    /// The vertical position of the baseline.
+    pub y: f64,
+    /// The advance width of the line, including kerning.
+    pub width: f64,
+    /// The glyphs of the line, one for each character of `text`.
+    pub glyphs: Vec<PositionedGlyph>,
+}
+
+/// Lays out multi-line text, with word wrapping and alignment.
+///
+/// Positions have the y axis pointing down, like the rendering surfaces.
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    text: String,
+    x: f64,
+    y: f64,
+    max_width: Option<f64>,
+    max_lines: Option<usize>,
+    xa: XAlign,
+    ya: YAlign,
+    line_space: f64,
+    overflow: Overflow,
+}
+
+impl TextLayout {
+    /// The text that ends the lines shortened by [`Overflow::Ellipsis`].
+    pub const ELLIPSIS: &'static str = "...";
+
+    /// Returns a new `TextLayout` for the text `txt`.
+    ///
+    /// By default the lines are only broken at `\n`, and the left and the top
+    /// of the text are at the origin.
+    pub fn new(txt: &str) -> Self {
+        Self {
+            text: txt.to_string(),
+            x: 0.0,
+            y: 0.0,
+            max_width: None,
+            max_lines: None,
+            xa: XAlign::Left,
+            ya: YAlign::Top,
+            line_space: 0.0,
+            overflow: Overflow::Visible,
+        }
+    }
+    /// Sets the position (`x`, `y`) where the text is aligned, and returns itself.
+    #[inline]
+    pub const fn position(mut self, x: f64, y: f64) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+    /// Sets the maximum `width` of the lines, breaking them at whitespace,
+    /// and returns itself.
+    #[inline]
+    pub const fn max_width(mut self, width: f64) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+    /// Sets the maximum number of `lines`, and returns itself.
+    ///
+    /// With [`Overflow::Ellipsis`] the last line ends with an ellipsis if
+    /// there were more lines.
+    #[inline]
+    pub const fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines);
+        self
+    }
+    /// Sets the `horizontal_alignment` of every line and returns itself.
+    ///
+    /// It aligns the start, the middle or the end of each line.
+    #[inline]
+    pub const fn xalign(mut self, horizontal_alignment: XAlign) -> Self {
+        self.xa = horizontal_alignment;
+        self
+    }
+    /// Sets the `vertical_alignment` of the whole text and returns itself.
+    ///
+    /// It aligns the ascent of the first line, the descent of the last line,
+    /// the middle between them, or the baseline of the first line.
+    #[inline]
+    pub const fn yalign(mut self, vertical_alignment: YAlign) -> Self {
+        self.ya = vertical_alignment;
+        self
+    }
+    /// Sets the extra `line_space` between lines, and returns itself.
+    #[inline]
+    pub const fn line_space(mut self, line_space: f64) -> Self {
+        self.line_space = line_space;
+        self
+    }
+    /// Sets the `overflow` policy for the lines wider than the maximum width,
+    /// and returns itself.
+    #[inline]
+    pub const fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Lays out the text measured with `font`, returning a run for each line.
+    pub fn layout<F: LayoutFont + ?Sized>(&self, font: &F) -> Vec<GlyphRun> {
+        let mut lines = Vec::new();
+        for paragraph in self.text.split('\n') {
+            let paragraph = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+            match self.max_width {
+                Some(max_width) => wrap(font, paragraph, max_width, &mut lines),
+                None => lines.push(paragraph.chars().collect()),
+            }
+        }
+        let truncated = self.max_lines.is_some_and(|max| lines.len() > max);
+        iif![let Some(max) = self.max_lines; lines.truncate(max)];
+
+        let last = lines.len().saturating_sub(1);
+        for (i, line) in lines.iter_mut().enumerate() {
+            let ellipsis = truncated && i == last && self.overflow == Overflow::Ellipsis;
+            self.fit(font, line, ellipsis);
+        }
+
+        let widths: Vec<f64> = lines.iter().map(|line| measure(font, None, line)).collect();
+        let step = font.line_height() + self.line_space;
+        let (ascent, descent) = (font.ascent(), font.descent());
+        let height = ascent - descent + step * last as f64;
+        let top = self.y
+            - match self.ya {
+                YAlign::Top => 0.0,
+                YAlign::Center => height / 2.0,
+                YAlign::Bottom => height,
+                YAlign::Baseline => ascent,
+            };
+
+        lines
+            .into_iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (line, width))| {
+                let x = self.x
+                    - match self.xa {
+                        XAlign::Left => 0.0,
+                        XAlign::Center => width / 2.0,
+                        XAlign::Right => width,
+                    };
+                let y = top + ascent + step * i as f64;
+                let (mut pen, mut prev) = (x, None);
+                let glyphs = line
+                    .iter()
+                    .map(|&c| {
+                        iif![let Some(p) = prev; pen += font.kerning(p, c)];
+                        let glyph = PositionedGlyph { c, x: pen, y };
+                        pen += font.advance(c);
+                        prev = Some(c);
+                        glyph
+                    })
+                    .collect();
+                GlyphRun { text: line.iter().collect(), x, y, width, glyphs }
+            })
+            .collect()
+    }
+
+    /// Applies the overflow policy to a `line`, forcing an `ellipsis` at its end.
+    fn fit<F: LayoutFont + ?Sized>(&self, font: &F, line: &mut Vec<char>, ellipsis: bool) {
+        let max_width = self.max_width.unwrap_or(f64::INFINITY);
+        let fits = |line: &[char]| measure(font, None, line) <= max_width;
+        match self.overflow {
+            Overflow::Clip if !fits(line) => {
+                let (mut pen, mut prev, mut len) = (0.0, None, 0);
+                for &c in line.iter() {
+                    iif![let Some(p) = prev; pen += font.kerning(p, c)];
+                    pen += font.advance(c);
+                    iif![pen > max_width; break];
+                    (prev, len) = (Some(c), len + 1);
+                }
+                line.truncate(len);
+            }
+            Overflow::Ellipsis if ellipsis || !fits(line) => {
+                let with_ellipsis = |line: &[char]| {
+                    let mut line = line.to_vec();
+                    line.extend(Self::ELLIPSIS.chars());
+                    line
+                };
+                while !line.is_empty() && !fits(&with_ellipsis(line)) {
+                    line.pop();
+                }
+                while line.last().is_some_and(|c| c.is_whitespace()) {
+                    line.pop();
+                }
+                line.extend(Self::ELLIPSIS.chars());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the advance width of `chars`, following the character `prev`.
+fn measure<F: LayoutFont + ?Sized>(font: &F, mut prev: Option<char>, chars: &[char]) -> f64 {
+    let mut width = 0.0;
+    for &c in chars {
+        iif![let Some(p) = prev; width += font.kerning(p, c)];
+        width += font.advance(c);
+        prev = Some(c);
+    }
+    width
+}
+
+/// Breaks a `paragraph` at whitespace into lines that fit `max_width`,
+/// appending them to `lines`.
+///
+/// Words wider than `max_width` are left whole in their own line. The
+/// whitespace at the start of the paragraph is kept, and the one around
+/// the breaks is dropped.
+fn wrap<F: LayoutFont + ?Sized>(
+    font: &F,
+    paragraph: &str,
+    max_width: f64,
+    lines: &mut Vec<Vec<char>>,
+) {
+    let (mut line, mut width) = (Vec::new(), 0.0);
+    let (mut rest, mut first_line) = (paragraph, true);
+    while !rest.is_empty() {
+        let spaces = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+        let words = rest[spaces..].find(char::is_whitespace).map_or(rest.len(), |w| spaces + w);
+        let (space, word) = (&rest[..spaces], &rest[spaces..words]);
+        rest = &rest[words..];
+
+        iif![word.is_empty(); break]; // trailing whitespace
+
+        let segment: Vec<char> = space.chars().chain(word.chars()).collect();
+        let advance = measure(font, line.last().copied(), &segment);
+        if first_line && line.is_empty() || width + advance <= max_width {
+            line.extend(segment);
+            width += advance;
+        } else {
+            lines.push(trim_end(line));
+            line = word.chars().collect();
+            width = measure(font, None, &line);
+            first_line = false;
+        }
+    }
+    lines.push(trim_end(line));
+}
+
+/// Removes the whitespace at the end of a `line`.
+fn trim_end(mut line: Vec<char>) -> Vec<char> {
+    while line.last().is_some_and(|c| c.is_whitespace()) {
+        line.pop();
+    }
+    line
+}
@@ -0,0 +1,123 @@
+// agrega::font::lcd
+//
+//! LCD subpixel text
+//!
+//! LCD screens split each pixel into red, green and blue stripes, so text
+//! drawn with three times the horizontal resolution, with each channel
+//! covered on its own, looks sharper. An [`LcdFilter`] spreads the coverage
+//! of each subpixel over its neighbors, like AGG's `lcd_distribution_lut`,
+//! to reduce the color fringes.
+//!
+//! # Examples
+//! ```
+//! use agrega::{LcdFilter, SubpixelOrder};
+//!
+//! let filter = LcdFilter::default();
+//! let covers = filter.apply(&[0, 0, 255, 0, 0]);
+//! assert_eq!(covers.len(), 5 + 4);
+//! assert_eq!(covers[4], 85);
+//! assert_eq!((covers[3], covers[5]), (57, 57));
+//!
+//! // Subpixels are grouped in pixels, from the left
+//! let pixels = SubpixelOrder::Bgr.pixels(&[10, 20, 30, 40], 0);
+//! assert_eq!(pixels, [[30, 20, 10], [0, 0, 40]]);
+//! ```
+//
+// TOC
+// - enum SubpixelOrder
+// - struct LcdFilter
+
+use alloc::{vec, vec::Vec};
+#[allow(unused_imports)]
+use devela::{iif, ExtFloat};
+
+/// The order of the color stripes in the pixels of an LCD screen.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum SubpixelOrder {
+    /// Red on the left, blue on the right.
+    #[default]
+    Rgb,
+    /// Blue on the left, red on the right.
+    Bgr,
+}
+
+impl SubpixelOrder {
+    /// Groups the subpixel `covers` in pixels, returning the red, green and
+    /// blue covers of each one.
+    ///
+    /// The first cover is at the subpixel `offset`, from 0 to 2, of the first pixel.
+    #[must_use]
+    pub fn pixels(self, covers: &[u8], offset: usize) -> Vec<[u64; 3]> {
+        let mut pixels = Vec::with_capacity((offset + covers.len()).div_ceil(3));
+        let mut pixel = [0; 3];
+        for (i, &cover) in covers.iter().enumerate() {
+            let sub = (offset + i) % 3;
+            pixel[sub] = u64::from(cover);
+            if sub == 2 || i == covers.len() - 1 {
+                iif![self == SubpixelOrder::Bgr; pixel.reverse()];
+                pixels.push(pixel);
+                pixel = [0; 3];
+            }
+        }
+        pixels
+    }
+}
+
+/// The weights of a filter that spreads the coverage of each subpixel over
+/// the two subpixels at each side.
+///
+/// The weights are normalized so the total coverage is preserved.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LcdFilter {
+    primary: f64,
+    secondary: f64,
+    tertiary: f64,
+}
+
+impl Default for LcdFilter {
+    /// Returns the filter used by AGG's LCD demo, with weights 3/9, 2/9 and 1/9.
+    fn default() -> Self {
+        Self::new(3.0, 2.0, 1.0)
+    }
+}
+
+impl LcdFilter {
+    /// A filter that leaves the coverage unchanged, with the strongest color fringes.
+    pub const NONE: Self = Self::new(1.0, 0.0, 0.0);
+
+    /// Returns a new filter with the weights of the `primary` subpixel, and of
+    /// the `secondary` and `tertiary` ones at each side of it.
+    pub const fn new(primary: f64, secondary: f64, tertiary: f64) -> Self {
+        let norm = 1.0 / (primary + 2.0 * secondary + 2.0 * tertiary);
+        Self { primary: primary * norm, secondary: secondary * norm, tertiary: tertiary * norm }
+    }
+
+    /// Returns the normalized weight of the covered subpixel.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn primary(&self) -> f64 { self.primary }
+
+    /// Returns the normalized weight of the subpixels next to the covered one.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn secondary(&self) -> f64 { self.secondary }
+
+    /// Returns the normalized weight of the subpixels two places away from the covered one.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn tertiary(&self) -> f64 { self.tertiary }
+
+    /// Filters the subpixel `covers`.
+    ///
+    /// The result has two more covers at each side, so it starts two subpixels
+    /// to the left of `covers`.
+    #[must_use]
+    pub fn apply(&self, covers: &[u8]) -> Vec<u8> {
+        let weights = [self.tertiary, self.secondary, self.primary, self.secondary, self.tertiary];
+        let mut sums = vec![0.0; covers.len() + 4];
+        for (i, &cover) in covers.iter().enumerate() {
+            iif![cover == 0; continue];
+            for (sum, weight) in sums[i..i + 5].iter_mut().zip(weights) {
+                *sum += f64::from(cover) * weight;
+            }
+        }
+        sums.into_iter().map(|sum| sum.round().min(255.0) as u8).collect()
+    }
+}
@@ -0,0 +1,40 @@
+// agrega::font::metrics
+
+use crate::Rectangle;
+
+/// The measurements of a run of text.
+///
+/// Vertical values follow the typographic convention, with the y axis pointing
+/// up from the baseline, so the descent is usually negative.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextMetrics {
+    /// The advance width of the text, including kerning.
+    pub advance: f64,
+    /// The bounding box of the drawn glyphs, relative to the pen origin.
+    ///
+    /// It is `None` if the text draws nothing, like a string of spaces.
+    pub ink: Option<Rectangle<f64>>,
+    /// The distance from the baseline up to the top of the tallest glyphs.
+    pub ascent: f64,
+    /// The distance from the baseline to the bottom of the lowest glyphs.
+    pub descent: f64,
+    /// The extra space between lines.
+    pub line_gap: f64,
+}
+
+impl TextMetrics {
+    /// Returns the height of the text, from the descent to the ascent.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn height(&self) -> f64 { self.ascent - self.descent }
+
+    /// Returns the distance between the baselines of consecutive lines.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub fn line_height(&self) -> f64 { self.ascent - self.descent + self.line_gap }
+
+    /// Expands the ink box to include the rectangle from (`x1`, `y1`) to (`x2`, `y2`).
+    pub(crate) fn add_ink(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let ink = self.ink.get_or_insert_with(|| Rectangle::new(x1, y1, x1, y1));
+        ink.expand(x1, y1);
+        ink.expand(x2, y2);
+    }
+}
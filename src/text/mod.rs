@@ -1,7 +1,17 @@
 // agrega::font
 
+mod cache;
+mod error;
 mod gsv;
-pub use gsv::*;
+mod layout;
+mod lcd;
+mod metrics;
+mod raster;
+mod ttf;
+pub use {cache::*, error::*, gsv::*, layout::*, lcd::*, metrics::*, raster::*, ttf::*};
+
+#[cfg(test)]
+mod tests;
 
 #[cfg(feature = "freetype")]
 #[cfg_attr(feature = "nightly", doc(cfg(feature = "freetype")))]
@@ -0,0 +1,540 @@
+// agrega::font::raster
+//
+//! Bitmap text
+//!
+//! A [`GlyphRasterBin`] reads a bitmap font in the format of AGG's
+//! `glyph_raster_bin`, and [`RendererRasterHTextSolid`] and
+//! [`RendererRasterVTextSolid`] draw strings with it, pixel by pixel, without
+//! rasterizing any outline. [`RasterFonts`] has some fonts embedded in the
+//! crate, which are original designs rather than ports of AGG's, and fonts
+//! in the same format, like the ones in AGG's `agg_embedded_raster_fonts.cpp`,
+//! can be used too.
+//!
+//! # Examples
+//! ```
+//! use agrega::{GlyphRasterBin, Pixfmt, RasterFonts, RendererRasterHTextSolid, RenderingBase, Rgb8};
+//!
+//! let font = GlyphRasterBin::new(RasterFonts::MONO_6X9).unwrap();
+//! assert_eq!(font.width("Hello"), 30);
+//!
+//! let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(40, 12));
+//! ren_base.clear(Rgb8::white());
+//! let mut ren = RendererRasterHTextSolid::with_base(&mut ren_base, font);
+//! ren.color(Rgb8::black());
+//! ren.render_text(5.0, 9.0, "Hello");
+//! assert!(ren_base.as_bytes().iter().any(|&v| v == 0));
+//! ```
+//
+// TOC
+// - struct GlyphRasterBin
+// - struct RasterGlyph
+// - struct RendererRasterHTextSolid
+// - struct RendererRasterVTextSolid
+// - struct RasterFonts
+
+use crate::{AggFontError, Color, Pixel, RenderingBase, Rgba8};
+#[allow(unused_imports)]
+use devela::{iif, ExtFloat};
+
+/// The cover of the set pixels of a glyph.
+const COVER_FULL: u64 = 255;
+
+/// Checks that `font` is a valid bitmap font, with all its glyphs complete.
+fn check_font(font: &[u8]) -> Result<(), AggFontError> {
+    iif![font.len() < 4; return Err(AggFontError::Malformed("raster font header is truncated"))];
+    let (height, baseline, count) = (font[0] as usize, font[1], font[3] as usize);
+    iif![baseline > font[0];
+        return Err(AggFontError::Malformed("raster font baseline is above its height"))];
+    let glyphs = 4 + count * 2;
+    iif![font.len() < glyphs;
+        return Err(AggFontError::Malformed("raster font glyph offsets are truncated"))];
+    for offset in font[4..glyphs].chunks_exact(2) {
+        let start = glyphs + u16::from_le_bytes([offset[0], offset[1]]) as usize;
+        let width =
+            *font.get(start).ok_or(AggFontError::Malformed("raster font glyph is missing"))?;
+        iif![font.len() < start + 1 + usize::from(width).div_ceil(8) * height;
+            return Err(AggFontError::Malformed("raster font glyph is truncated"))];
+    }
+    Ok(())
+}
+
+/// A bitmap font, like AGG's `glyph_raster_bin`.
+///
+/// The font data starts with the height of the glyphs, the rows below the
+/// baseline, the first character and the number of characters, followed by
+/// the little-endian offsets of the glyphs. Each glyph is its width and its
+/// rows from the top, one bit per pixel with the most significant bit first,
+/// with each row padded to a whole byte.
+#[derive(Debug, Clone, Copy)]
+pub struct GlyphRasterBin<'a> {
+    font: &'a [u8],
+}
+
+impl<'a> GlyphRasterBin<'a> {
+    /// Returns a new `GlyphRasterBin` reading the `font` data.
+    ///
+    /// # Errors
+    /// Returns [`AggFontError::Malformed`] if the header, the offsets or any
+    /// of the glyphs are truncated, or if the baseline is above the height.
+    pub fn new(font: &'a [u8]) -> Result<Self, AggFontError> {
+        check_font(font)?;
+        Ok(Self { font })
+    }
+
+    /// Returns the height of the glyphs, in pixels.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn height(&self) -> usize { self.font[0] as usize }
+
+    /// Returns the number of rows of the glyphs below the baseline.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn baseline(&self) -> usize { self.font[1] as usize }
+
+    /// Returns the number of rows of the glyphs above the baseline.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn ascent(&self) -> usize { self.height() - self.baseline() }
+
+    /// Returns the glyph of the character `c`, or `None` if the font doesn't have it.
+    #[must_use]
+    pub fn glyph(&self, c: char) -> Option<RasterGlyph<'a>> {
+        let (start, count) = (u32::from(self.font[2]), u32::from(self.font[3]));
+        let index = (c as u32).checked_sub(start).filter(|&i| i < count)? as usize;
+        let offset = u16::from_le_bytes([self.font[4 + index * 2], self.font[5 + index * 2]]);
+        let start = 4 + count as usize * 2 + offset as usize;
+        let width = self.font[start];
+        let stride = usize::from(width).div_ceil(8);
+        let rows = &self.font[start + 1..start + 1 + stride * self.height()];
+        Some(RasterGlyph { width: width.into(), stride, rows })
+    }
+
+    /// Returns the width of the text, in pixels.
+    ///
+    /// The characters missing from the font have no width.
+    #[must_use]
+    pub fn width(&self, txt: &str) -> usize {
+        txt.chars().filter_map(|c| self.glyph(c)).map(|glyph| glyph.width).sum()
+    }
+}
+
+/// A glyph of a [`GlyphRasterBin`] font.
+#[derive(Debug, Clone, Copy)]
+pub struct RasterGlyph<'a> {
+    width: usize,
+    stride: usize,
+    rows: &'a [u8],
+}
+
+impl RasterGlyph<'_> {
+    /// Returns the width of the glyph, which is also its advance.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn width(&self) -> usize { self.width }
+
+    /// Returns whether the pixel at column `x` of the `row` is set,
+    /// counting the rows from the top.
+    #[must_use]
+    pub fn is_set(&self, x: usize, row: usize) -> bool {
+        iif![x >= self.width; return false];
+        self.rows
+            .get(row * self.stride + x / 8)
+            .is_some_and(|&bits| bits & (0x80 >> (x % 8)) != 0)
+    }
+
+    /// Fills `covers` with the coverage of the pixels of the `row`.
+    fn span(&self, row: usize, covers: &mut [u64]) {
+        for (x, cover) in covers.iter_mut().enumerate() {
+            *cover = iif![self.is_set(x, row); COVER_FULL; 0];
+        }
+    }
+}
+
+/// Renders horizontal text with a [`GlyphRasterBin`] font and a solid color,
+/// like AGG's `renderer_raster_htext_solid`.
+#[derive(Debug)]
+pub struct RendererRasterHTextSolid<'a, T: 'a> {
+    base: &'a mut RenderingBase<T>,
+    font: GlyphRasterBin<'a>,
+    color: Rgba8,
+}
+
+impl<'a, T: Pixel> RendererRasterHTextSolid<'a, T> {
+    /// Creates a new renderer drawing with `font` on the `base`, in black.
+    #[inline]
+    pub fn with_base(base: &'a mut RenderingBase<T>, font: GlyphRasterBin<'a>) -> Self {
+        Self { base, font, color: Rgba8::black() }
+    }
+    /// Sets the `color` of the text.
+    #[inline]
+    pub fn color<C: Color>(&mut self, color: C) {
+        self.color = Rgba8::from_trait(color);
+    }
+    /// Sets the `font` of the text.
+    #[inline]
+    pub fn font(&mut self, font: GlyphRasterBin<'a>) {
+        self.font = font;
+    }
+
+    /// Draws the text from (`x`, `y`), rounded to whole pixels, with the
+    /// baseline at `y` and the y axis pointing down.
+    ///
+    /// The characters missing from the font are skipped.
+    pub fn render_text(&mut self, x: f64, y: f64, txt: &str) {
+        let (mut x, y) = (x.round() as i64, y.round() as i64);
+        let top = y - self.font.ascent() as i64;
+        let mut covers = [0; 256];
+        for glyph in txt.chars().filter_map(|c| self.font.glyph(c)) {
+            let covers = &mut covers[..glyph.width];
+            for row in 0..self.font.height() {
+                glyph.span(row, covers);
+                let len = covers.len() as i64;
+                self.base.blend_solid_hspan(x, top + row as i64, len, self.color, covers);
+            }
+            x += glyph.width as i64;
+        }
+    }
+}
+
+/// Renders vertical text with a [`GlyphRasterBin`] font and a solid color,
+/// like AGG's `renderer_raster_vtext_solid`.
+///
+/// The text runs down, with the glyphs rotated a quarter turn clockwise.
+#[derive(Debug)]
+pub struct RendererRasterVTextSolid<'a, T: 'a> {
+    base: &'a mut RenderingBase<T>,
+    font: GlyphRasterBin<'a>,
+    color: Rgba8,
+}
+
+impl<'a, T: Pixel> RendererRasterVTextSolid<'a, T> {
+    /// Creates a new renderer drawing with `font` on the `base`, in black.
+    #[inline]
+    pub fn with_base(base: &'a mut RenderingBase<T>, font: GlyphRasterBin<'a>) -> Self {
+        Self { base, font, color: Rgba8::black() }
+    }
+    /// Sets the `color` of the text.
+    #[inline]
+    pub fn color<C: Color>(&mut self, color: C) {
+        self.color = Rgba8::from_trait(color);
+    }
+    /// Sets the `font` of the text.
+    #[inline]
+    pub fn font(&mut self, font: GlyphRasterBin<'a>) {
+        self.font = font;
+    }
+
+    /// Draws the text down from (`x`, `y`), rounded to whole pixels, with the
+    /// baseline at `x` and the tops of the glyphs to its right.
+    ///
+    /// The characters missing from the font are skipped.
+    pub fn render_text(&mut self, x: f64, y: f64, txt: &str) {
+        let (x, mut y) = (x.round() as i64, y.round() as i64);
+        let right = x + self.font.ascent() as i64 - 1;
+        let mut covers = [0; 256];
+        for glyph in txt.chars().filter_map(|c| self.font.glyph(c)) {
+            let covers = &mut covers[..glyph.width];
+            for row in 0..self.font.height() {
+                glyph.span(row, covers);
+                let len = covers.len() as i64;
+                self.base.blend_solid_vspan(right - row as i64, y, len, self.color, covers);
+            }
+            y += glyph.width as i64;
+        }
+    }
+}
+
+/// Bitmap fonts for [`GlyphRasterBin`], embedded in the crate.
+///
+/// All of them have the printable ASCII characters, from `' '` to `'~'`.
+/// They are original designs in the format of AGG's embedded raster fonts.
+pub struct RasterFonts;
+
+impl RasterFonts {
+    /// A monospaced font with 3x5 pixel glyphs, in 4x6 pixel cells.
+    pub const MONO_4X6: &'static [u8] = &MONO_4X6_DATA;
+    /// A monospaced font with 5x7 pixel glyphs and descenders, in 6x9 pixel cells.
+    pub const MONO_6X9: &'static [u8] = &MONO_6X9_DATA;
+    /// A bold version of [`MONO_6X9`][Self::MONO_6X9], in 7x9 pixel cells.
+    pub const MONO_7X9_BOLD: &'static [u8] = &MONO_7X9_BOLD_DATA;
+    /// A proportional version of [`MONO_6X9`][Self::MONO_6X9], 9 pixels high.
+    pub const SANS_9: &'static [u8] = &SANS_9_DATA;
+}
+
+const MONO_4X6_DATA: [u8; 859] = [
+    0x06, 0x01, 0x20, 0x5f, 0x00, 0x00, 0x07, 0x00, 0x0e, 0x00, 0x15, 0x00, 0x1c, 0x00, 0x23, 0x00,
+    0x2a, 0x00, 0x31, 0x00, 0x38, 0x00, 0x3f, 0x00, 0x46, 0x00, 0x4d, 0x00, 0x54, 0x00, 0x5b, 0x00,
+    0x62, 0x00, 0x69, 0x00, 0x70, 0x00, 0x77, 0x00, 0x7e, 0x00, 0x85, 0x00, 0x8c, 0x00, 0x93, 0x00,
+    0x9a, 0x00, 0xa1, 0x00, 0xa8, 0x00, 0xaf, 0x00, 0xb6, 0x00, 0xbd, 0x00, 0xc4, 0x00, 0xcb, 0x00,
+    0xd2, 0x00, 0xd9, 0x00, 0xe0, 0x00, 0xe7, 0x00, 0xee, 0x00, 0xf5, 0x00, 0xfc, 0x00, 0x03, 0x01,
+    0x0a, 0x01, 0x11, 0x01, 0x18, 0x01, 0x1f, 0x01, 0x26, 0x01, 0x2d, 0x01, 0x34, 0x01, 0x3b, 0x01,
+    0x42, 0x01, 0x49, 0x01, 0x50, 0x01, 0x57, 0x01, 0x5e, 0x01, 0x65, 0x01, 0x6c, 0x01, 0x73, 0x01,
+    0x7a, 0x01, 0x81, 0x01, 0x88, 0x01, 0x8f, 0x01, 0x96, 0x01, 0x9d, 0x01, 0xa4, 0x01, 0xab, 0x01,
+    0xb2, 0x01, 0xb9, 0x01, 0xc0, 0x01, 0xc7, 0x01, 0xce, 0x01, 0xd5, 0x01, 0xdc, 0x01, 0xe3, 0x01,
+    0xea, 0x01, 0xf1, 0x01, 0xf8, 0x01, 0xff, 0x01, 0x06, 0x02, 0x0d, 0x02, 0x14, 0x02, 0x1b, 0x02,
+    0x22, 0x02, 0x29, 0x02, 0x30, 0x02, 0x37, 0x02, 0x3e, 0x02, 0x45, 0x02, 0x4c, 0x02, 0x53, 0x02,
+    0x5a, 0x02, 0x61, 0x02, 0x68, 0x02, 0x6f, 0x02, 0x76, 0x02, 0x7d, 0x02, 0x84, 0x02, 0x8b, 0x02,
+    0x92, 0x02, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x40, 0x40, 0x40, 0x00, 0x40, 0x00,
+    0x04, 0xa0, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x04, 0xa0, 0xe0, 0xa0, 0xe0, 0xa0, 0x00, 0x04, 0x60,
+    0xc0, 0x40, 0x60, 0xc0, 0x00, 0x04, 0x80, 0x20, 0x40, 0x80, 0x20, 0x00, 0x04, 0x40, 0xa0, 0x40,
+    0xa0, 0x60, 0x00, 0x04, 0x40, 0x40, 0x00, 0x00, 0x00, 0x00, 0x04, 0x20, 0x40, 0x40, 0x40, 0x20,
+    0x00, 0x04, 0x80, 0x40, 0x40, 0x40, 0x80, 0x00, 0x04, 0x00, 0xa0, 0x40, 0xa0, 0x00, 0x00, 0x04,
+    0x00, 0x40, 0xe0, 0x40, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x40, 0x40, 0x80, 0x04, 0x00, 0x00,
+    0xe0, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x04, 0x20, 0x20, 0x40, 0x80,
+    0x80, 0x00, 0x04, 0x40, 0xa0, 0xa0, 0xa0, 0x40, 0x00, 0x04, 0x40, 0xc0, 0x40, 0x40, 0xe0, 0x00,
+    0x04, 0xc0, 0x20, 0x40, 0x80, 0xe0, 0x00, 0x04, 0xc0, 0x20, 0x40, 0x20, 0xc0, 0x00, 0x04, 0xa0,
+    0xa0, 0xe0, 0x20, 0x20, 0x00, 0x04, 0xe0, 0x80, 0xc0, 0x20, 0xc0, 0x00, 0x04, 0x60, 0x80, 0xe0,
+    0xa0, 0xe0, 0x00, 0x04, 0xe0, 0x20, 0x40, 0x40, 0x40, 0x00, 0x04, 0xe0, 0xa0, 0xe0, 0xa0, 0xe0,
+    0x00, 0x04, 0xe0, 0xa0, 0xe0, 0x20, 0xc0, 0x00, 0x04, 0x00, 0x40, 0x00, 0x40, 0x00, 0x00, 0x04,
+    0x00, 0x40, 0x00, 0x40, 0x40, 0x80, 0x04, 0x20, 0x40, 0x80, 0x40, 0x20, 0x00, 0x04, 0x00, 0xe0,
+    0x00, 0xe0, 0x00, 0x00, 0x04, 0x80, 0x40, 0x20, 0x40, 0x80, 0x00, 0x04, 0xc0, 0x20, 0x40, 0x00,
+    0x40, 0x00, 0x04, 0x40, 0xa0, 0xe0, 0x80, 0x60, 0x00, 0x04, 0x40, 0xa0, 0xe0, 0xa0, 0xa0, 0x00,
+    0x04, 0xc0, 0xa0, 0xc0, 0xa0, 0xc0, 0x00, 0x04, 0x60, 0x80, 0x80, 0x80, 0x60, 0x00, 0x04, 0xc0,
+    0xa0, 0xa0, 0xa0, 0xc0, 0x00, 0x04, 0xe0, 0x80, 0xc0, 0x80, 0xe0, 0x00, 0x04, 0xe0, 0x80, 0xc0,
+    0x80, 0x80, 0x00, 0x04, 0x60, 0x80, 0xa0, 0xa0, 0x60, 0x00, 0x04, 0xa0, 0xa0, 0xe0, 0xa0, 0xa0,
+    0x00, 0x04, 0xe0, 0x40, 0x40, 0x40, 0xe0, 0x00, 0x04, 0x20, 0x20, 0x20, 0xa0, 0x40, 0x00, 0x04,
+    0xa0, 0xa0, 0xc0, 0xa0, 0xa0, 0x00, 0x04, 0x80, 0x80, 0x80, 0x80, 0xe0, 0x00, 0x04, 0xa0, 0xe0,
+    0xe0, 0xa0, 0xa0, 0x00, 0x04, 0xc0, 0xa0, 0xa0, 0xa0, 0xa0, 0x00, 0x04, 0xe0, 0xa0, 0xa0, 0xa0,
+    0xe0, 0x00, 0x04, 0xc0, 0xa0, 0xc0, 0x80, 0x80, 0x00, 0x04, 0x40, 0xa0, 0xa0, 0xc0, 0x60, 0x00,
+    0x04, 0xc0, 0xa0, 0xc0, 0xa0, 0xa0, 0x00, 0x04, 0x60, 0x80, 0x40, 0x20, 0xc0, 0x00, 0x04, 0xe0,
+    0x40, 0x40, 0x40, 0x40, 0x00, 0x04, 0xa0, 0xa0, 0xa0, 0xa0, 0xe0, 0x00, 0x04, 0xa0, 0xa0, 0xa0,
+    0xa0, 0x40, 0x00, 0x04, 0xa0, 0xa0, 0xe0, 0xe0, 0xa0, 0x00, 0x04, 0xa0, 0xa0, 0x40, 0xa0, 0xa0,
+    0x00, 0x04, 0xa0, 0xa0, 0x40, 0x40, 0x40, 0x00, 0x04, 0xe0, 0x20, 0x40, 0x80, 0xe0, 0x00, 0x04,
+    0xc0, 0x80, 0x80, 0x80, 0xc0, 0x00, 0x04, 0x80, 0x80, 0x40, 0x20, 0x20, 0x00, 0x04, 0x60, 0x20,
+    0x20, 0x20, 0x60, 0x00, 0x04, 0x40, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0xe0, 0x04, 0x80, 0x40, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x60, 0xa0, 0x60, 0x00,
+    0x04, 0x80, 0x80, 0xc0, 0xa0, 0xc0, 0x00, 0x04, 0x00, 0x00, 0x60, 0x80, 0x60, 0x00, 0x04, 0x20,
+    0x20, 0x60, 0xa0, 0x60, 0x00, 0x04, 0x00, 0x40, 0xe0, 0x80, 0x60, 0x00, 0x04, 0x20, 0x40, 0xe0,
+    0x40, 0x40, 0x00, 0x04, 0x00, 0x00, 0x60, 0xa0, 0x60, 0xc0, 0x04, 0x80, 0x80, 0xc0, 0xa0, 0xa0,
+    0x00, 0x04, 0x40, 0x00, 0x40, 0x40, 0x40, 0x00, 0x04, 0x20, 0x00, 0x20, 0x20, 0x20, 0xc0, 0x04,
+    0x80, 0x80, 0xa0, 0xc0, 0xa0, 0x00, 0x04, 0xc0, 0x40, 0x40, 0x40, 0xe0, 0x00, 0x04, 0x00, 0x00,
+    0xe0, 0xe0, 0xa0, 0x00, 0x04, 0x00, 0x00, 0xc0, 0xa0, 0xa0, 0x00, 0x04, 0x00, 0x00, 0x40, 0xa0,
+    0x40, 0x00, 0x04, 0x00, 0x00, 0xc0, 0xa0, 0xc0, 0x80, 0x04, 0x00, 0x00, 0x60, 0xa0, 0x60, 0x20,
+    0x04, 0x00, 0x00, 0x60, 0x80, 0x80, 0x00, 0x04, 0x00, 0x00, 0x60, 0x40, 0xc0, 0x00, 0x04, 0x40,
+    0x40, 0xe0, 0x40, 0x60, 0x00, 0x04, 0x00, 0x00, 0xa0, 0xa0, 0x60, 0x00, 0x04, 0x00, 0x00, 0xa0,
+    0xa0, 0x40, 0x00, 0x04, 0x00, 0x00, 0xa0, 0xe0, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xa0, 0x40, 0xa0,
+    0x00, 0x04, 0x00, 0x00, 0xa0, 0xa0, 0x60, 0xc0, 0x04, 0x00, 0x00, 0xe0, 0x40, 0xe0, 0x00, 0x04,
+    0x60, 0x40, 0xc0, 0x40, 0x60, 0x00, 0x04, 0x40, 0x40, 0x40, 0x40, 0x40, 0x00, 0x04, 0xc0, 0x40,
+    0x60, 0x40, 0xc0, 0x00, 0x04, 0x00, 0x00, 0x60, 0xc0, 0x00, 0x00,
+];
+
+const MONO_6X9_DATA: [u8; 1144] = [
+    0x09, 0x02, 0x20, 0x5f, 0x00, 0x00, 0x0a, 0x00, 0x14, 0x00, 0x1e, 0x00, 0x28, 0x00, 0x32, 0x00,
+    0x3c, 0x00, 0x46, 0x00, 0x50, 0x00, 0x5a, 0x00, 0x64, 0x00, 0x6e, 0x00, 0x78, 0x00, 0x82, 0x00,
+    0x8c, 0x00, 0x96, 0x00, 0xa0, 0x00, 0xaa, 0x00, 0xb4, 0x00, 0xbe, 0x00, 0xc8, 0x00, 0xd2, 0x00,
+    0xdc, 0x00, 0xe6, 0x00, 0xf0, 0x00, 0xfa, 0x00, 0x04, 0x01, 0x0e, 0x01, 0x18, 0x01, 0x22, 0x01,
+    0x2c, 0x01, 0x36, 0x01, 0x40, 0x01, 0x4a, 0x01, 0x54, 0x01, 0x5e, 0x01, 0x68, 0x01, 0x72, 0x01,
+    0x7c, 0x01, 0x86, 0x01, 0x90, 0x01, 0x9a, 0x01, 0xa4, 0x01, 0xae, 0x01, 0xb8, 0x01, 0xc2, 0x01,
+    0xcc, 0x01, 0xd6, 0x01, 0xe0, 0x01, 0xea, 0x01, 0xf4, 0x01, 0xfe, 0x01, 0x08, 0x02, 0x12, 0x02,
+    0x1c, 0x02, 0x26, 0x02, 0x30, 0x02, 0x3a, 0x02, 0x44, 0x02, 0x4e, 0x02, 0x58, 0x02, 0x62, 0x02,
+    0x6c, 0x02, 0x76, 0x02, 0x80, 0x02, 0x8a, 0x02, 0x94, 0x02, 0x9e, 0x02, 0xa8, 0x02, 0xb2, 0x02,
+    0xbc, 0x02, 0xc6, 0x02, 0xd0, 0x02, 0xda, 0x02, 0xe4, 0x02, 0xee, 0x02, 0xf8, 0x02, 0x02, 0x03,
+    0x0c, 0x03, 0x16, 0x03, 0x20, 0x03, 0x2a, 0x03, 0x34, 0x03, 0x3e, 0x03, 0x48, 0x03, 0x52, 0x03,
+    0x5c, 0x03, 0x66, 0x03, 0x70, 0x03, 0x7a, 0x03, 0x84, 0x03, 0x8e, 0x03, 0x98, 0x03, 0xa2, 0x03,
+    0xac, 0x03, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x00, 0x20, 0x00, 0x00, 0x06, 0x50, 0x50, 0x50, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x06, 0x50, 0x50, 0xf8, 0x50, 0xf8, 0x50, 0x50, 0x00, 0x00, 0x06, 0x20, 0x78, 0xa0, 0x70, 0x28,
+    0xf0, 0x20, 0x00, 0x00, 0x06, 0xc0, 0xc8, 0x10, 0x20, 0x40, 0x98, 0x18, 0x00, 0x00, 0x06, 0x60,
+    0x90, 0xa0, 0x40, 0xa8, 0x90, 0x68, 0x00, 0x00, 0x06, 0x20, 0x20, 0x40, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x06, 0x10, 0x20, 0x40, 0x40, 0x40, 0x20, 0x10, 0x00, 0x00, 0x06, 0x40, 0x20, 0x10,
+    0x10, 0x10, 0x20, 0x40, 0x00, 0x00, 0x06, 0x00, 0x20, 0xa8, 0x70, 0xa8, 0x20, 0x00, 0x00, 0x00,
+    0x06, 0x00, 0x20, 0x20, 0xf8, 0x20, 0x20, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x60, 0x60, 0x20, 0x40, 0x06, 0x00, 0x00, 0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x60, 0x60, 0x00, 0x00, 0x06, 0x00, 0x08, 0x10, 0x20, 0x40, 0x80, 0x00,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x98, 0xa8, 0xc8, 0x88, 0x70, 0x00, 0x00, 0x06, 0x20, 0x60, 0x20,
+    0x20, 0x20, 0x20, 0x70, 0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x10, 0x20, 0x40, 0xf8, 0x00, 0x00,
+    0x06, 0xf8, 0x10, 0x20, 0x10, 0x08, 0x88, 0x70, 0x00, 0x00, 0x06, 0x10, 0x30, 0x50, 0x90, 0xf8,
+    0x10, 0x10, 0x00, 0x00, 0x06, 0xf8, 0x80, 0xf0, 0x08, 0x08, 0x88, 0x70, 0x00, 0x00, 0x06, 0x30,
+    0x40, 0x80, 0xf0, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0xf8, 0x08, 0x10, 0x20, 0x40, 0x40, 0x40,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x88, 0x70, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x78, 0x08, 0x10, 0x60, 0x00, 0x00, 0x06, 0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x00, 0x00, 0x00,
+    0x06, 0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x20, 0x40, 0x00, 0x06, 0x10, 0x20, 0x40, 0x80, 0x40,
+    0x20, 0x10, 0x00, 0x00, 0x06, 0x00, 0x00, 0xf8, 0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x06, 0x40,
+    0x20, 0x10, 0x08, 0x10, 0x20, 0x40, 0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x10, 0x20, 0x00, 0x20,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x68, 0xa8, 0xa8, 0x70, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x88, 0xf8, 0x88, 0x88, 0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0x88, 0x88, 0xf0, 0x00, 0x00,
+    0x06, 0x70, 0x88, 0x80, 0x80, 0x80, 0x88, 0x70, 0x00, 0x00, 0x06, 0xe0, 0x90, 0x88, 0x88, 0x88,
+    0x90, 0xe0, 0x00, 0x00, 0x06, 0xf8, 0x80, 0x80, 0xf0, 0x80, 0x80, 0xf8, 0x00, 0x00, 0x06, 0xf8,
+    0x80, 0x80, 0xf0, 0x80, 0x80, 0x80, 0x00, 0x00, 0x06, 0x70, 0x88, 0x80, 0xb8, 0x88, 0x88, 0x78,
+    0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0xf8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x70, 0x20, 0x20,
+    0x20, 0x20, 0x20, 0x70, 0x00, 0x00, 0x06, 0x38, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x00, 0x00,
+    0x06, 0x88, 0x90, 0xa0, 0xc0, 0xa0, 0x90, 0x88, 0x00, 0x00, 0x06, 0x80, 0x80, 0x80, 0x80, 0x80,
+    0x80, 0xf8, 0x00, 0x00, 0x06, 0x88, 0xd8, 0xa8, 0xa8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x88,
+    0x88, 0xc8, 0xa8, 0x98, 0x88, 0x88, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70,
+    0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0x80, 0x80, 0x80, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x88, 0xa8, 0x90, 0x68, 0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0xa0, 0x90, 0x88, 0x00, 0x00,
+    0x06, 0x78, 0x80, 0x80, 0x70, 0x08, 0x08, 0xf0, 0x00, 0x00, 0x06, 0xf8, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0x88,
+    0x88, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0xa8, 0xa8, 0xa8, 0x50,
+    0x00, 0x00, 0x06, 0x88, 0x88, 0x50, 0x20, 0x50, 0x88, 0x88, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88,
+    0x50, 0x20, 0x20, 0x20, 0x00, 0x00, 0x06, 0xf8, 0x08, 0x10, 0x20, 0x40, 0x80, 0xf8, 0x00, 0x00,
+    0x06, 0x70, 0x40, 0x40, 0x40, 0x40, 0x40, 0x70, 0x00, 0x00, 0x06, 0x00, 0x80, 0x40, 0x20, 0x10,
+    0x08, 0x00, 0x00, 0x00, 0x06, 0x70, 0x10, 0x10, 0x10, 0x10, 0x10, 0x70, 0x00, 0x00, 0x06, 0x20,
+    0x50, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xf8, 0x00, 0x06, 0x40, 0x20, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70,
+    0x08, 0x78, 0x88, 0x78, 0x00, 0x00, 0x06, 0x80, 0x80, 0xb0, 0xc8, 0x88, 0x88, 0xf0, 0x00, 0x00,
+    0x06, 0x00, 0x00, 0x70, 0x80, 0x80, 0x88, 0x70, 0x00, 0x00, 0x06, 0x08, 0x08, 0x68, 0x98, 0x88,
+    0x88, 0x78, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70, 0x88, 0xf8, 0x80, 0x70, 0x00, 0x00, 0x06, 0x30,
+    0x48, 0x40, 0xe0, 0x40, 0x40, 0x40, 0x00, 0x00, 0x06, 0x00, 0x00, 0x78, 0x88, 0x88, 0x88, 0x78,
+    0x08, 0x70, 0x06, 0x80, 0x80, 0xb0, 0xc8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x20, 0x00, 0x60,
+    0x20, 0x20, 0x20, 0x70, 0x00, 0x00, 0x06, 0x10, 0x00, 0x30, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60,
+    0x06, 0x80, 0x80, 0x90, 0xa0, 0xc0, 0xa0, 0x90, 0x00, 0x00, 0x06, 0x60, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x70, 0x00, 0x00, 0x06, 0x00, 0x00, 0xd0, 0xa8, 0xa8, 0xa8, 0xa8, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0xb0, 0xc8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70, 0x88, 0x88, 0x88, 0x70,
+    0x00, 0x00, 0x06, 0x00, 0x00, 0xf0, 0x88, 0x88, 0x88, 0xf0, 0x80, 0x80, 0x06, 0x00, 0x00, 0x78,
+    0x88, 0x88, 0x88, 0x78, 0x08, 0x08, 0x06, 0x00, 0x00, 0xb0, 0xc8, 0x80, 0x80, 0x80, 0x00, 0x00,
+    0x06, 0x00, 0x00, 0x70, 0x80, 0x70, 0x08, 0xf0, 0x00, 0x00, 0x06, 0x40, 0x40, 0xe0, 0x40, 0x40,
+    0x48, 0x30, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x88, 0xa8, 0xa8, 0x50,
+    0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x50, 0x20, 0x50, 0x88, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88,
+    0x88, 0x88, 0x88, 0x78, 0x08, 0x70, 0x06, 0x00, 0x00, 0xf8, 0x10, 0x20, 0x40, 0xf8, 0x00, 0x00,
+    0x06, 0x10, 0x20, 0x20, 0x40, 0x20, 0x20, 0x10, 0x00, 0x00, 0x06, 0x20, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x00, 0x00, 0x06, 0x40, 0x20, 0x20, 0x10, 0x20, 0x20, 0x40, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x40, 0xa8, 0x10, 0x00, 0x00, 0x00, 0x00,
+];
+
+const MONO_7X9_BOLD_DATA: [u8; 1144] = [
+    0x09, 0x02, 0x20, 0x5f, 0x00, 0x00, 0x0a, 0x00, 0x14, 0x00, 0x1e, 0x00, 0x28, 0x00, 0x32, 0x00,
+    0x3c, 0x00, 0x46, 0x00, 0x50, 0x00, 0x5a, 0x00, 0x64, 0x00, 0x6e, 0x00, 0x78, 0x00, 0x82, 0x00,
+    0x8c, 0x00, 0x96, 0x00, 0xa0, 0x00, 0xaa, 0x00, 0xb4, 0x00, 0xbe, 0x00, 0xc8, 0x00, 0xd2, 0x00,
+    0xdc, 0x00, 0xe6, 0x00, 0xf0, 0x00, 0xfa, 0x00, 0x04, 0x01, 0x0e, 0x01, 0x18, 0x01, 0x22, 0x01,
+    0x2c, 0x01, 0x36, 0x01, 0x40, 0x01, 0x4a, 0x01, 0x54, 0x01, 0x5e, 0x01, 0x68, 0x01, 0x72, 0x01,
+    0x7c, 0x01, 0x86, 0x01, 0x90, 0x01, 0x9a, 0x01, 0xa4, 0x01, 0xae, 0x01, 0xb8, 0x01, 0xc2, 0x01,
+    0xcc, 0x01, 0xd6, 0x01, 0xe0, 0x01, 0xea, 0x01, 0xf4, 0x01, 0xfe, 0x01, 0x08, 0x02, 0x12, 0x02,
+    0x1c, 0x02, 0x26, 0x02, 0x30, 0x02, 0x3a, 0x02, 0x44, 0x02, 0x4e, 0x02, 0x58, 0x02, 0x62, 0x02,
+    0x6c, 0x02, 0x76, 0x02, 0x80, 0x02, 0x8a, 0x02, 0x94, 0x02, 0x9e, 0x02, 0xa8, 0x02, 0xb2, 0x02,
+    0xbc, 0x02, 0xc6, 0x02, 0xd0, 0x02, 0xda, 0x02, 0xe4, 0x02, 0xee, 0x02, 0xf8, 0x02, 0x02, 0x03,
+    0x0c, 0x03, 0x16, 0x03, 0x20, 0x03, 0x2a, 0x03, 0x34, 0x03, 0x3e, 0x03, 0x48, 0x03, 0x52, 0x03,
+    0x5c, 0x03, 0x66, 0x03, 0x70, 0x03, 0x7a, 0x03, 0x84, 0x03, 0x8e, 0x03, 0x98, 0x03, 0xa2, 0x03,
+    0xac, 0x03, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x30, 0x30, 0x30,
+    0x30, 0x30, 0x00, 0x30, 0x00, 0x00, 0x07, 0x78, 0x78, 0x78, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x07, 0x78, 0x78, 0xfc, 0x78, 0xfc, 0x78, 0x78, 0x00, 0x00, 0x07, 0x30, 0x7c, 0xf0, 0x78, 0x3c,
+    0xf8, 0x30, 0x00, 0x00, 0x07, 0xe0, 0xec, 0x18, 0x30, 0x60, 0xdc, 0x1c, 0x00, 0x00, 0x07, 0x70,
+    0xd8, 0xf0, 0x60, 0xfc, 0xd8, 0x7c, 0x00, 0x00, 0x07, 0x30, 0x30, 0x60, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x07, 0x18, 0x30, 0x60, 0x60, 0x60, 0x30, 0x18, 0x00, 0x00, 0x07, 0x60, 0x30, 0x18,
+    0x18, 0x18, 0x30, 0x60, 0x00, 0x00, 0x07, 0x00, 0x30, 0xfc, 0x78, 0xfc, 0x30, 0x00, 0x00, 0x00,
+    0x07, 0x00, 0x30, 0x30, 0xfc, 0x30, 0x30, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x70, 0x70, 0x30, 0x60, 0x07, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x70, 0x70, 0x00, 0x00, 0x07, 0x00, 0x0c, 0x18, 0x30, 0x60, 0xc0, 0x00,
+    0x00, 0x00, 0x07, 0x78, 0xcc, 0xdc, 0xfc, 0xec, 0xcc, 0x78, 0x00, 0x00, 0x07, 0x30, 0x70, 0x30,
+    0x30, 0x30, 0x30, 0x78, 0x00, 0x00, 0x07, 0x78, 0xcc, 0x0c, 0x18, 0x30, 0x60, 0xfc, 0x00, 0x00,
+    0x07, 0xfc, 0x18, 0x30, 0x18, 0x0c, 0xcc, 0x78, 0x00, 0x00, 0x07, 0x18, 0x38, 0x78, 0xd8, 0xfc,
+    0x18, 0x18, 0x00, 0x00, 0x07, 0xfc, 0xc0, 0xf8, 0x0c, 0x0c, 0xcc, 0x78, 0x00, 0x00, 0x07, 0x38,
+    0x60, 0xc0, 0xf8, 0xcc, 0xcc, 0x78, 0x00, 0x00, 0x07, 0xfc, 0x0c, 0x18, 0x30, 0x60, 0x60, 0x60,
+    0x00, 0x00, 0x07, 0x78, 0xcc, 0xcc, 0x78, 0xcc, 0xcc, 0x78, 0x00, 0x00, 0x07, 0x78, 0xcc, 0xcc,
+    0x7c, 0x0c, 0x18, 0x70, 0x00, 0x00, 0x07, 0x00, 0x70, 0x70, 0x00, 0x70, 0x70, 0x00, 0x00, 0x00,
+    0x07, 0x00, 0x70, 0x70, 0x00, 0x70, 0x70, 0x30, 0x60, 0x00, 0x07, 0x18, 0x30, 0x60, 0xc0, 0x60,
+    0x30, 0x18, 0x00, 0x00, 0x07, 0x00, 0x00, 0xfc, 0x00, 0xfc, 0x00, 0x00, 0x00, 0x00, 0x07, 0x60,
+    0x30, 0x18, 0x0c, 0x18, 0x30, 0x60, 0x00, 0x00, 0x07, 0x78, 0xcc, 0x0c, 0x18, 0x30, 0x00, 0x30,
+    0x00, 0x00, 0x07, 0x78, 0xcc, 0x0c, 0x7c, 0xfc, 0xfc, 0x78, 0x00, 0x00, 0x07, 0x78, 0xcc, 0xcc,
+    0xcc, 0xfc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0xf8, 0xcc, 0xcc, 0xf8, 0xcc, 0xcc, 0xf8, 0x00, 0x00,
+    0x07, 0x78, 0xcc, 0xc0, 0xc0, 0xc0, 0xcc, 0x78, 0x00, 0x00, 0x07, 0xf0, 0xd8, 0xcc, 0xcc, 0xcc,
+    0xd8, 0xf0, 0x00, 0x00, 0x07, 0xfc, 0xc0, 0xc0, 0xf8, 0xc0, 0xc0, 0xfc, 0x00, 0x00, 0x07, 0xfc,
+    0xc0, 0xc0, 0xf8, 0xc0, 0xc0, 0xc0, 0x00, 0x00, 0x07, 0x78, 0xcc, 0xc0, 0xfc, 0xcc, 0xcc, 0x7c,
+    0x00, 0x00, 0x07, 0xcc, 0xcc, 0xcc, 0xfc, 0xcc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0x78, 0x30, 0x30,
+    0x30, 0x30, 0x30, 0x78, 0x00, 0x00, 0x07, 0x3c, 0x18, 0x18, 0x18, 0x18, 0xd8, 0x70, 0x00, 0x00,
+    0x07, 0xcc, 0xd8, 0xf0, 0xe0, 0xf0, 0xd8, 0xcc, 0x00, 0x00, 0x07, 0xc0, 0xc0, 0xc0, 0xc0, 0xc0,
+    0xc0, 0xfc, 0x00, 0x00, 0x07, 0xcc, 0xfc, 0xfc, 0xfc, 0xcc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0xcc,
+    0xcc, 0xec, 0xfc, 0xdc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0x78, 0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0x78,
+    0x00, 0x00, 0x07, 0xf8, 0xcc, 0xcc, 0xf8, 0xc0, 0xc0, 0xc0, 0x00, 0x00, 0x07, 0x78, 0xcc, 0xcc,
+    0xcc, 0xfc, 0xd8, 0x7c, 0x00, 0x00, 0x07, 0xf8, 0xcc, 0xcc, 0xf8, 0xf0, 0xd8, 0xcc, 0x00, 0x00,
+    0x07, 0x7c, 0xc0, 0xc0, 0x78, 0x0c, 0x0c, 0xf8, 0x00, 0x00, 0x07, 0xfc, 0x30, 0x30, 0x30, 0x30,
+    0x30, 0x30, 0x00, 0x00, 0x07, 0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0xcc, 0x78, 0x00, 0x00, 0x07, 0xcc,
+    0xcc, 0xcc, 0xcc, 0xcc, 0x78, 0x30, 0x00, 0x00, 0x07, 0xcc, 0xcc, 0xcc, 0xfc, 0xfc, 0xfc, 0x78,
+    0x00, 0x00, 0x07, 0xcc, 0xcc, 0x78, 0x30, 0x78, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0xcc, 0xcc, 0xcc,
+    0x78, 0x30, 0x30, 0x30, 0x00, 0x00, 0x07, 0xfc, 0x0c, 0x18, 0x30, 0x60, 0xc0, 0xfc, 0x00, 0x00,
+    0x07, 0x78, 0x60, 0x60, 0x60, 0x60, 0x60, 0x78, 0x00, 0x00, 0x07, 0x00, 0xc0, 0x60, 0x30, 0x18,
+    0x0c, 0x00, 0x00, 0x00, 0x07, 0x78, 0x18, 0x18, 0x18, 0x18, 0x18, 0x78, 0x00, 0x00, 0x07, 0x30,
+    0x78, 0xcc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xfc, 0x00, 0x07, 0x60, 0x30, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x78,
+    0x0c, 0x7c, 0xcc, 0x7c, 0x00, 0x00, 0x07, 0xc0, 0xc0, 0xf8, 0xec, 0xcc, 0xcc, 0xf8, 0x00, 0x00,
+    0x07, 0x00, 0x00, 0x78, 0xc0, 0xc0, 0xcc, 0x78, 0x00, 0x00, 0x07, 0x0c, 0x0c, 0x7c, 0xdc, 0xcc,
+    0xcc, 0x7c, 0x00, 0x00, 0x07, 0x00, 0x00, 0x78, 0xcc, 0xfc, 0xc0, 0x78, 0x00, 0x00, 0x07, 0x38,
+    0x6c, 0x60, 0xf0, 0x60, 0x60, 0x60, 0x00, 0x00, 0x07, 0x00, 0x00, 0x7c, 0xcc, 0xcc, 0xcc, 0x7c,
+    0x0c, 0x78, 0x07, 0xc0, 0xc0, 0xf8, 0xec, 0xcc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0x30, 0x00, 0x70,
+    0x30, 0x30, 0x30, 0x78, 0x00, 0x00, 0x07, 0x18, 0x00, 0x38, 0x18, 0x18, 0x18, 0x18, 0xd8, 0x70,
+    0x07, 0xc0, 0xc0, 0xd8, 0xf0, 0xe0, 0xf0, 0xd8, 0x00, 0x00, 0x07, 0x70, 0x30, 0x30, 0x30, 0x30,
+    0x30, 0x78, 0x00, 0x00, 0x07, 0x00, 0x00, 0xf8, 0xfc, 0xfc, 0xfc, 0xfc, 0x00, 0x00, 0x07, 0x00,
+    0x00, 0xf8, 0xec, 0xcc, 0xcc, 0xcc, 0x00, 0x00, 0x07, 0x00, 0x00, 0x78, 0xcc, 0xcc, 0xcc, 0x78,
+    0x00, 0x00, 0x07, 0x00, 0x00, 0xf8, 0xcc, 0xcc, 0xcc, 0xf8, 0xc0, 0xc0, 0x07, 0x00, 0x00, 0x7c,
+    0xcc, 0xcc, 0xcc, 0x7c, 0x0c, 0x0c, 0x07, 0x00, 0x00, 0xf8, 0xec, 0xc0, 0xc0, 0xc0, 0x00, 0x00,
+    0x07, 0x00, 0x00, 0x78, 0xc0, 0x78, 0x0c, 0xf8, 0x00, 0x00, 0x07, 0x60, 0x60, 0xf0, 0x60, 0x60,
+    0x6c, 0x38, 0x00, 0x00, 0x07, 0x00, 0x00, 0xcc, 0xcc, 0xcc, 0xdc, 0x7c, 0x00, 0x00, 0x07, 0x00,
+    0x00, 0xcc, 0xcc, 0xcc, 0x78, 0x30, 0x00, 0x00, 0x07, 0x00, 0x00, 0xcc, 0xcc, 0xfc, 0xfc, 0x78,
+    0x00, 0x00, 0x07, 0x00, 0x00, 0xcc, 0x78, 0x30, 0x78, 0xcc, 0x00, 0x00, 0x07, 0x00, 0x00, 0xcc,
+    0xcc, 0xcc, 0xcc, 0x7c, 0x0c, 0x78, 0x07, 0x00, 0x00, 0xfc, 0x18, 0x30, 0x60, 0xfc, 0x00, 0x00,
+    0x07, 0x18, 0x30, 0x30, 0x60, 0x30, 0x30, 0x18, 0x00, 0x00, 0x07, 0x30, 0x30, 0x30, 0x30, 0x30,
+    0x30, 0x30, 0x00, 0x00, 0x07, 0x60, 0x30, 0x30, 0x18, 0x30, 0x30, 0x60, 0x00, 0x00, 0x07, 0x00,
+    0x00, 0x60, 0xfc, 0x18, 0x00, 0x00, 0x00, 0x00,
+];
+
+const SANS_9_DATA: [u8; 1144] = [
+    0x09, 0x02, 0x20, 0x5f, 0x00, 0x00, 0x0a, 0x00, 0x14, 0x00, 0x1e, 0x00, 0x28, 0x00, 0x32, 0x00,
+    0x3c, 0x00, 0x46, 0x00, 0x50, 0x00, 0x5a, 0x00, 0x64, 0x00, 0x6e, 0x00, 0x78, 0x00, 0x82, 0x00,
+    0x8c, 0x00, 0x96, 0x00, 0xa0, 0x00, 0xaa, 0x00, 0xb4, 0x00, 0xbe, 0x00, 0xc8, 0x00, 0xd2, 0x00,
+    0xdc, 0x00, 0xe6, 0x00, 0xf0, 0x00, 0xfa, 0x00, 0x04, 0x01, 0x0e, 0x01, 0x18, 0x01, 0x22, 0x01,
+    0x2c, 0x01, 0x36, 0x01, 0x40, 0x01, 0x4a, 0x01, 0x54, 0x01, 0x5e, 0x01, 0x68, 0x01, 0x72, 0x01,
+    0x7c, 0x01, 0x86, 0x01, 0x90, 0x01, 0x9a, 0x01, 0xa4, 0x01, 0xae, 0x01, 0xb8, 0x01, 0xc2, 0x01,
+    0xcc, 0x01, 0xd6, 0x01, 0xe0, 0x01, 0xea, 0x01, 0xf4, 0x01, 0xfe, 0x01, 0x08, 0x02, 0x12, 0x02,
+    0x1c, 0x02, 0x26, 0x02, 0x30, 0x02, 0x3a, 0x02, 0x44, 0x02, 0x4e, 0x02, 0x58, 0x02, 0x62, 0x02,
+    0x6c, 0x02, 0x76, 0x02, 0x80, 0x02, 0x8a, 0x02, 0x94, 0x02, 0x9e, 0x02, 0xa8, 0x02, 0xb2, 0x02,
+    0xbc, 0x02, 0xc6, 0x02, 0xd0, 0x02, 0xda, 0x02, 0xe4, 0x02, 0xee, 0x02, 0xf8, 0x02, 0x02, 0x03,
+    0x0c, 0x03, 0x16, 0x03, 0x20, 0x03, 0x2a, 0x03, 0x34, 0x03, 0x3e, 0x03, 0x48, 0x03, 0x52, 0x03,
+    0x5c, 0x03, 0x66, 0x03, 0x70, 0x03, 0x7a, 0x03, 0x84, 0x03, 0x8e, 0x03, 0x98, 0x03, 0xa2, 0x03,
+    0xac, 0x03, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x80, 0x80, 0x80,
+    0x80, 0x80, 0x00, 0x80, 0x00, 0x00, 0x04, 0xa0, 0xa0, 0xa0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x06, 0x50, 0x50, 0xf8, 0x50, 0xf8, 0x50, 0x50, 0x00, 0x00, 0x06, 0x20, 0x78, 0xa0, 0x70, 0x28,
+    0xf0, 0x20, 0x00, 0x00, 0x06, 0xc0, 0xc8, 0x10, 0x20, 0x40, 0x98, 0x18, 0x00, 0x00, 0x06, 0x60,
+    0x90, 0xa0, 0x40, 0xa8, 0x90, 0x68, 0x00, 0x00, 0x03, 0x40, 0x40, 0x80, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x04, 0x20, 0x40, 0x80, 0x80, 0x80, 0x40, 0x20, 0x00, 0x00, 0x04, 0x80, 0x40, 0x20,
+    0x20, 0x20, 0x40, 0x80, 0x00, 0x00, 0x06, 0x00, 0x20, 0xa8, 0x70, 0xa8, 0x20, 0x00, 0x00, 0x00,
+    0x06, 0x00, 0x20, 0x20, 0xf8, 0x20, 0x20, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xc0, 0xc0, 0x40, 0x80, 0x06, 0x00, 0x00, 0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xc0, 0xc0, 0x00, 0x00, 0x06, 0x00, 0x08, 0x10, 0x20, 0x40, 0x80, 0x00,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x98, 0xa8, 0xc8, 0x88, 0x70, 0x00, 0x00, 0x04, 0x40, 0xc0, 0x40,
+    0x40, 0x40, 0x40, 0xe0, 0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x10, 0x20, 0x40, 0xf8, 0x00, 0x00,
+    0x06, 0xf8, 0x10, 0x20, 0x10, 0x08, 0x88, 0x70, 0x00, 0x00, 0x06, 0x10, 0x30, 0x50, 0x90, 0xf8,
+    0x10, 0x10, 0x00, 0x00, 0x06, 0xf8, 0x80, 0xf0, 0x08, 0x08, 0x88, 0x70, 0x00, 0x00, 0x06, 0x30,
+    0x40, 0x80, 0xf0, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0xf8, 0x08, 0x10, 0x20, 0x40, 0x40, 0x40,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x88, 0x70, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x78, 0x08, 0x10, 0x60, 0x00, 0x00, 0x03, 0x00, 0xc0, 0xc0, 0x00, 0xc0, 0xc0, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0xc0, 0xc0, 0x00, 0xc0, 0xc0, 0x40, 0x80, 0x00, 0x05, 0x10, 0x20, 0x40, 0x80, 0x40,
+    0x20, 0x10, 0x00, 0x00, 0x06, 0x00, 0x00, 0xf8, 0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x05, 0x80,
+    0x40, 0x20, 0x10, 0x20, 0x40, 0x80, 0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x10, 0x20, 0x00, 0x20,
+    0x00, 0x00, 0x06, 0x70, 0x88, 0x08, 0x68, 0xa8, 0xa8, 0x70, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x88, 0xf8, 0x88, 0x88, 0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0x88, 0x88, 0xf0, 0x00, 0x00,
+    0x06, 0x70, 0x88, 0x80, 0x80, 0x80, 0x88, 0x70, 0x00, 0x00, 0x06, 0xe0, 0x90, 0x88, 0x88, 0x88,
+    0x90, 0xe0, 0x00, 0x00, 0x06, 0xf8, 0x80, 0x80, 0xf0, 0x80, 0x80, 0xf8, 0x00, 0x00, 0x06, 0xf8,
+    0x80, 0x80, 0xf0, 0x80, 0x80, 0x80, 0x00, 0x00, 0x06, 0x70, 0x88, 0x80, 0xb8, 0x88, 0x88, 0x78,
+    0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0xf8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x04, 0xe0, 0x40, 0x40,
+    0x40, 0x40, 0x40, 0xe0, 0x00, 0x00, 0x06, 0x38, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60, 0x00, 0x00,
+    0x06, 0x88, 0x90, 0xa0, 0xc0, 0xa0, 0x90, 0x88, 0x00, 0x00, 0x06, 0x80, 0x80, 0x80, 0x80, 0x80,
+    0x80, 0xf8, 0x00, 0x00, 0x06, 0x88, 0xd8, 0xa8, 0xa8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x88,
+    0x88, 0xc8, 0xa8, 0x98, 0x88, 0x88, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70,
+    0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0x80, 0x80, 0x80, 0x00, 0x00, 0x06, 0x70, 0x88, 0x88,
+    0x88, 0xa8, 0x90, 0x68, 0x00, 0x00, 0x06, 0xf0, 0x88, 0x88, 0xf0, 0xa0, 0x90, 0x88, 0x00, 0x00,
+    0x06, 0x78, 0x80, 0x80, 0x70, 0x08, 0x08, 0xf0, 0x00, 0x00, 0x06, 0xf8, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70, 0x00, 0x00, 0x06, 0x88,
+    0x88, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88, 0xa8, 0xa8, 0xa8, 0x50,
+    0x00, 0x00, 0x06, 0x88, 0x88, 0x50, 0x20, 0x50, 0x88, 0x88, 0x00, 0x00, 0x06, 0x88, 0x88, 0x88,
+    0x50, 0x20, 0x20, 0x20, 0x00, 0x00, 0x06, 0xf8, 0x08, 0x10, 0x20, 0x40, 0x80, 0xf8, 0x00, 0x00,
+    0x04, 0xe0, 0x80, 0x80, 0x80, 0x80, 0x80, 0xe0, 0x00, 0x00, 0x06, 0x00, 0x80, 0x40, 0x20, 0x10,
+    0x08, 0x00, 0x00, 0x00, 0x04, 0xe0, 0x20, 0x20, 0x20, 0x20, 0x20, 0xe0, 0x00, 0x00, 0x06, 0x20,
+    0x50, 0x88, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xf8, 0x00, 0x04, 0x80, 0x40, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70,
+    0x08, 0x78, 0x88, 0x78, 0x00, 0x00, 0x06, 0x80, 0x80, 0xb0, 0xc8, 0x88, 0x88, 0xf0, 0x00, 0x00,
+    0x06, 0x00, 0x00, 0x70, 0x80, 0x80, 0x88, 0x70, 0x00, 0x00, 0x06, 0x08, 0x08, 0x68, 0x98, 0x88,
+    0x88, 0x78, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70, 0x88, 0xf8, 0x80, 0x70, 0x00, 0x00, 0x06, 0x30,
+    0x48, 0x40, 0xe0, 0x40, 0x40, 0x40, 0x00, 0x00, 0x06, 0x00, 0x00, 0x78, 0x88, 0x88, 0x88, 0x78,
+    0x08, 0x70, 0x06, 0x80, 0x80, 0xb0, 0xc8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x04, 0x40, 0x00, 0xc0,
+    0x40, 0x40, 0x40, 0xe0, 0x00, 0x00, 0x05, 0x10, 0x00, 0x30, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60,
+    0x05, 0x80, 0x80, 0x90, 0xa0, 0xc0, 0xa0, 0x90, 0x00, 0x00, 0x04, 0xc0, 0x40, 0x40, 0x40, 0x40,
+    0x40, 0xe0, 0x00, 0x00, 0x06, 0x00, 0x00, 0xd0, 0xa8, 0xa8, 0xa8, 0xa8, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0xb0, 0xc8, 0x88, 0x88, 0x88, 0x00, 0x00, 0x06, 0x00, 0x00, 0x70, 0x88, 0x88, 0x88, 0x70,
+    0x00, 0x00, 0x06, 0x00, 0x00, 0xf0, 0x88, 0x88, 0x88, 0xf0, 0x80, 0x80, 0x06, 0x00, 0x00, 0x78,
+    0x88, 0x88, 0x88, 0x78, 0x08, 0x08, 0x06, 0x00, 0x00, 0xb0, 0xc8, 0x80, 0x80, 0x80, 0x00, 0x00,
+    0x06, 0x00, 0x00, 0x70, 0x80, 0x70, 0x08, 0xf0, 0x00, 0x00, 0x06, 0x40, 0x40, 0xe0, 0x40, 0x40,
+    0x48, 0x30, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x88, 0x88, 0x88, 0x50, 0x20, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x88, 0xa8, 0xa8, 0x50,
+    0x00, 0x00, 0x06, 0x00, 0x00, 0x88, 0x50, 0x20, 0x50, 0x88, 0x00, 0x00, 0x06, 0x00, 0x00, 0x88,
+    0x88, 0x88, 0x88, 0x78, 0x08, 0x70, 0x06, 0x00, 0x00, 0xf8, 0x10, 0x20, 0x40, 0xf8, 0x00, 0x00,
+    0x04, 0x20, 0x40, 0x40, 0x80, 0x40, 0x40, 0x20, 0x00, 0x00, 0x02, 0x80, 0x80, 0x80, 0x80, 0x80,
+    0x80, 0x80, 0x00, 0x00, 0x04, 0x80, 0x40, 0x40, 0x20, 0x40, 0x40, 0x80, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x40, 0xa8, 0x10, 0x00, 0x00, 0x00, 0x00,
+];
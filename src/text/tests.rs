@@ -0,0 +1,588 @@
+// agrega::font::tests
+
+use super::{
+    AggFontError, CachedGlyph, GlyphBitmap, GlyphCache, GlyphData, GlyphKey, GlyphRasterBin,
+    GlyphRender, GlyphRun, GsvDefaultFont, GsvText, GsvTextOutline, LayoutFont, LcdFilter,
+    OutlineFont, OutlineText, Overflow, RasterFonts, RasterGlyph, SubpixelOrder, TextLayout,
+    XAlign, YAlign,
+};
+use crate::{bounding_rect, PathCommand, Transform, Vertex, VertexSource};
+use alloc::{vec, vec::Vec};
+use core::mem::size_of;
+use devela::iif;
+
+/// Builds a font file from its tables, which must be sorted by tag.
+fn sfnt(version: &[u8; 4], tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+    let mut out = version.to_vec();
+    out.extend((tables.len() as u16).to_be_bytes());
+    out.extend([0; 6]); // search range, entry selector, range shift
+    let mut offset = 12 + 16 * tables.len();
+    for (tag, data) in tables {
+        out.extend(*tag);
+        out.extend([0; 4]); // checksum
+        out.extend((offset as u32).to_be_bytes());
+        out.extend((data.len() as u32).to_be_bytes());
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in tables {
+        out.extend(data);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    out
+}
+
+/// Builds the common tables of a font with 2 glyphs, mapping 'A' to glyph 1.
+fn common_tables(long_loca: bool) -> [(&'static [u8; 4], Vec<u8>); 5] {
+    let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 1, 0, 0, 0, 12];
+    #[rustfmt::skip]
+    cmap.extend([
+        0, 4, 0, 32, 0, 0, // format, length, language
+        0, 4, 0, 4, 0, 1, 0, 0, // segments * 2, search range, entry selector, range shift
+        0, 0x41, 0xff, 0xff, 0, 0, // end codes, padding
+        0, 0x41, 0xff, 0xff, // start codes
+        0xff, 0xc0, 0, 1, // deltas
+        0, 0, 0, 0, // range offsets
+    ]);
+    let mut head = vec![0; 54];
+    head[..4].copy_from_slice(&[0, 1, 0, 0]);
+    head[12..16].copy_from_slice(&0x5F0F_3CF5_u32.to_be_bytes());
+    head[18..20].copy_from_slice(&1000_u16.to_be_bytes());
+    head[50..52].copy_from_slice(&u16::from(long_loca).to_be_bytes());
+    let mut hhea = vec![0; 36];
+    hhea[..4].copy_from_slice(&[0, 1, 0, 0]);
+    hhea[4..6].copy_from_slice(&800_i16.to_be_bytes());
+    hhea[6..8].copy_from_slice(&(-200_i16).to_be_bytes());
+    hhea[8..10].copy_from_slice(&90_i16.to_be_bytes());
+    hhea[34..36].copy_from_slice(&1_u16.to_be_bytes());
+    // Glyph 1 reuses the last advance, and has its own left side bearing
+    let hmtx = vec![2, 0x58, 0, 0, 0, 100];
+    let maxp = vec![0, 0, 0x50, 0, 0, 2];
+    [
+        (b"cmap", cmap),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"maxp", maxp),
+    ]
+}
+
+/// Builds a CFF table with an empty `.notdef` and a glyph 1 that exercises
+/// hints, subroutines and curves.
+fn cff_table() -> Vec<u8> {
+    cff_table_with(None, &[])
+}
+
+/// Builds the [`cff_table`] with another encoded private DICT size, and
+/// `private_prefix` before the private DICT entries.
+fn cff_table_with(private_size: Option<&[u8]>, private_prefix: &[u8]) -> Vec<u8> {
+    #[rustfmt::skip]
+    let glyph: Vec<u8> = vec![
+        248, 136, 139, 189, 1, // 500 0 50 hstem, with the width
+        239, 159, 3, // 100 20 vstem
+        19, 0xe0, // hintmask
+        239, 239, 21, // 100 100 rmoveto
+        247, 92, 6, // 200 hlineto
+        32, 29, // -107 callgsubr
+        89, 119, 159, 189, 31, // -50 -20 20 50 hvcurveto
+        32, 10, // -107 callsubr
+        14, // endchar
+    ];
+    let gsubr: Vec<u8> = vec![247, 92, 7, 11]; // 200 vlineto return
+    let lsubr: Vec<u8> = vec![251, 22, 251, 162, 5, 11]; // -130 -270 rlineto return
+
+    let index = |items: &[&[u8]]| -> Vec<u8> {
+        if items.is_empty() {
+            return vec![0, 0];
+        }
+        let mut out = (items.len() as u16).to_be_bytes().to_vec();
+        out.push(1);
+        let mut offset = 1;
+        out.push(offset);
+        for item in items {
+            offset += item.len() as u8;
+            out.push(offset);
+        }
+        items.iter().for_each(|item| out.extend(*item));
+        out
+    };
+    let int = |v: i32| {
+        let mut out = vec![29];
+        out.extend(v.to_be_bytes());
+        out
+    };
+    let header = [1, 0, 4, 1];
+    let names = index(&[b"Test"]);
+    let strings = index(&[]);
+    let gsubrs = index(&[&gsubr]);
+    let charstrings = index(&[&[14], &glyph]);
+    // Subrs (19), right after the private dict
+    let private = [private_prefix, &int(6 + private_prefix.len() as i32), &[19]].concat();
+    let private_size = private_size.map_or_else(|| int(private.len() as i32), <[u8]>::to_vec);
+    // Top dict: CharStrings (17), Private (18) with fixed size operands
+    let top_len = 5 + 1 + private_size.len() + 5 + 1;
+    let top_index_len = 2 + 1 + 2 + top_len;
+    let charstrings_at = header.len() + names.len() + top_index_len + strings.len() + gsubrs.len();
+    let private_at = charstrings_at + charstrings.len();
+    let mut top = int(charstrings_at as i32);
+    top.push(17);
+    top.extend(&private_size);
+    top.extend(int(private_at as i32));
+    top.push(18);
+    assert_eq!(top.len(), top_len);
+
+    [
+        &header[..],
+        &names,
+        &index(&[&top]),
+        &strings,
+        &gsubrs,
+        &charstrings,
+        &private,
+        &index(&[&lsubr]),
+    ]
+    .concat()
+}
+
+#[test]
+fn cff_outline() {
+    let mut tables = common_tables(false).to_vec();
+    tables.insert(0, (b"CFF ", cff_table()));
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+
+    assert_eq!((font.units_per_em(), font.num_glyphs()), (1000, 2));
+    assert_eq!((font.ascender(), font.descender(), font.line_gap()), (800, -200, 90));
+    assert_eq!(font.glyph_index('A'), Some(1));
+    assert_eq!(font.glyph_index('B'), None);
+    assert_eq!((font.advance_width(1), font.left_side_bearing(1)), (600, 100));
+
+    let path = font.glyph_outline(1).unwrap();
+    assert_eq!(
+        path.vertices,
+        [
+            Vertex::move_to(100.0, 100.0),
+            Vertex::line_to(300.0, 100.0),
+            Vertex::line_to(300.0, 300.0),
+            Vertex::new(250.0, 300.0, PathCommand::Curve4),
+            Vertex::new(230.0, 320.0, PathCommand::Curve4),
+            Vertex::new(230.0, 370.0, PathCommand::Curve4),
+            Vertex::line_to(100.0, 100.0),
+            Vertex::close_polygon(100.0, 100.0),
+        ]
+    );
+    assert!(font.glyph_outline(0).unwrap().vertices.is_empty());
+    assert!(matches!(font.glyph_outline(2), Err(AggFontError::Malformed(_))));
+}
+
+#[test]
+fn glyf_outline() {
+    #[rustfmt::skip]
+    let simple: Vec<u8> = vec![
+        0, 1, 0, 10, 0, 0, 0, 110, 0, 100, // 1 contour, bounds
+        0, 2, 0, 0, // end point, no instructions
+        0x33, 0x36, 0x22, // on, off, off
+        10, 100, 100, // x: +10, +100, -100
+        100, 0, // y: same, +100, same, and padding
+    ];
+    #[rustfmt::skip]
+    let composite: Vec<u8> = vec![
+        0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0,
+        0x00, 0x0a, 0, 1, 5, 0xfb, 0x40, 0x00, // glyph 1 moved by (5, -5), scaled by 1.0
+    ];
+    let mut glyf = simple;
+    glyf.extend(&composite);
+    let loca = [0_u32, 0, 22, 40].iter().flat_map(|v| v.to_be_bytes()).collect();
+
+    let mut tables = common_tables(true).to_vec();
+    tables.insert(1, (b"glyf", glyf));
+    tables.insert(5, (b"loca", loca));
+    tables[6].1[5] = 3; // maxp: 3 glyphs
+    let font = OutlineFont::new(sfnt(&[0, 1, 0, 0], &tables)).unwrap();
+    assert_eq!(font.num_glyphs(), 3);
+
+    // The glyph is shifted so that its left edge matches the side bearing
+    let path = font.glyph_outline(1).unwrap();
+    assert_eq!(
+        path.vertices,
+        [
+            Vertex::move_to(100.0, 0.0),
+            Vertex::new(200.0, 100.0, PathCommand::Curve3),
+            Vertex::new(150.0, 100.0, PathCommand::Curve3),
+            Vertex::new(100.0, 100.0, PathCommand::Curve3),
+            Vertex::new(100.0, 0.0, PathCommand::Curve3),
+            Vertex::close_polygon(100.0, 0.0),
+        ]
+    );
+    let path = font.glyph_outline(2).unwrap();
+    assert_eq!(path.vertices[0], Vertex::move_to(15.0, -5.0));
+    assert_eq!(path.vertices.len(), 6);
+    assert!(font.glyph_outline(0).unwrap().vertices.is_empty());
+
+    assert!(matches!(
+        OutlineFont::from_collection(vec![0; 4], 1),
+        Err(AggFontError::FaceIndex(1))
+    ));
+    assert!(matches!(OutlineFont::new(vec![0; 16]), Err(AggFontError::Unsupported(_))));
+}
+
+#[test]
+fn malformed_fonts() {
+    // A format 12 cmap whose glyph indices overflow
+    let mut cmap = vec![0, 0, 0, 1, 0, 3, 0, 10, 0, 0, 0, 12];
+    #[rustfmt::skip]
+    cmap.extend([
+        0, 12, 0, 0, 0, 0, 0, 28, 0, 0, 0, 0, // format, length, language
+        0, 0, 0, 1, // groups
+        0, 0, 0, 0x41, 0, 0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // 'A'.., from u32::MAX
+    ]);
+    let mut tables = common_tables(false).to_vec();
+    tables[0].1 = cmap;
+    tables.insert(0, (b"CFF ", cff_table()));
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+    assert_eq!((font.glyph_index('A'), font.glyph_index('B')), (None, None));
+
+    // A private DICT real with a huge exponent is parsed in bounded time
+    let real = [30, 0x1b, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9f];
+    tables[0].1 = cff_table_with(None, &[&real[..], &[12, 9]].concat()); // BlueScale
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+    assert_eq!(font.glyph_outline(1).unwrap().vertices.len(), 8);
+
+    // A private DICT that ends past the address space leaves no local subroutines
+    let huge = [30, 0x00, 0x1b, 0x30, 0xff]; // 1e30, saturated to usize::MAX
+    tables[0].1 = cff_table_with(Some(&huge), &[]);
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+    assert!(matches!(font.glyph_outline(1), Err(AggFontError::Malformed(_))));
+
+    // Composite glyphs 2 to 8 that each reuse the next one 20 times, down to glyph 1
+    let (mut glyf, mut loca) = (vec![], vec![0_u32, 0, 0]);
+    for glyph in 2..=8_u8 {
+        glyf.extend([0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0]);
+        for i in 0..20 {
+            let more = iif![i < 19; 0x20; 0];
+            glyf.extend([0, 0x02 | more, 0, iif![glyph < 8; glyph + 1; 1], 0, 0]);
+        }
+        loca.push(glyf.len() as u32);
+    }
+    let loca = loca.iter().flat_map(|v| v.to_be_bytes()).collect();
+    let mut tables = common_tables(true).to_vec();
+    tables.insert(1, (b"glyf", glyf));
+    tables.insert(5, (b"loca", loca));
+    tables[6].1[5] = 9; // maxp: 9 glyphs
+    let font = OutlineFont::new(sfnt(&[0, 1, 0, 0], &tables)).unwrap();
+    assert!(font.glyph_outline(7).unwrap().vertices.is_empty());
+    assert!(matches!(font.glyph_outline(2), Err(AggFontError::Malformed(_))));
+}
+
+#[test]
+fn glyph_cache() {
+    let key = |glyph| GlyphKey {
+        face: 1,
+        size: 12,
+        hinting: true,
+        render: GlyphRender::Gray8,
+        offset: (0, 0),
+        glyph,
+    };
+    let bitmap = |width| {
+        let buffer = vec![255; width * 10];
+        let bitmap = GlyphBitmap { left: 0, top: 10, width, rows: 10, buffer };
+        Ok(CachedGlyph { advance_x: width as f64, advance_y: 0.0, data: GlyphData::Gray8(bitmap) })
+    };
+    let bytes = bitmap(100).unwrap().memory_usage() + size_of::<GlyphKey>();
+    let cache = GlyphCache::new(3 * bytes);
+
+    for glyph in 0..3 {
+        cache.glyph(key(glyph), || bitmap(100)).unwrap();
+    }
+    assert_eq!((cache.len(), cache.memory_usage()), (3, 3 * bytes));
+    // A cached glyph is not loaded again, and becomes the most recently used
+    let glyph = cache.glyph(key(0), || panic!("loaded twice")).unwrap();
+    assert_eq!(glyph.advance_x, 100.0);
+    // The least recently used glyph is evicted to make room
+    cache.glyph(key(3), || bitmap(100)).unwrap();
+    assert_eq!((cache.len(), cache.memory_usage()), (3, 3 * bytes));
+    assert!(cache.glyph(key(1), || Err(AggFontError::Malformed("evicted"))).is_err());
+    cache.glyph(key(0), || panic!("evicted the wrong glyph")).unwrap();
+
+    // Glyphs bigger than the limit are returned but not cached
+    let big = cache.glyph(key(4), || bitmap(1000)).unwrap();
+    assert_eq!(big.advance_x, 1000.0);
+    assert_eq!(cache.len(), 3);
+    cache.clear();
+    assert!(cache.is_empty() && cache.memory_usage() == 0);
+}
+
+#[test]
+fn glyph_cache_outlines() {
+    let mut tables = common_tables(false).to_vec();
+    tables.insert(0, (b"CFF ", cff_table()));
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+    let copy = font.clone();
+    let cache = GlyphCache::default();
+
+    let glyph = cache.outline_glyph(&font, 1).unwrap();
+    assert_eq!(glyph.advance_x, 600.0);
+    assert!(matches!(&glyph.data, GlyphData::Outline(p) if p.vertices.len() == 8));
+    // Copies of a font share its cached glyphs
+    cache.outline_glyph(&copy, 1).unwrap();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.outline_glyph(&font, 2).is_err());
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+#[cfg(feature = "freetype")]
+fn glyph_bitmap_rows() {
+    // Rows are copied from the top without their padding, whatever their order
+    let down = [1, 2, 0, 3, 4, 0];
+    assert_eq!(super::cache::bitmap_rows(&down, 2, 2, 3), [1, 2, 3, 4]);
+    let up = [3, 4, 0, 1, 2, 0];
+    assert_eq!(super::cache::bitmap_rows(&up, 2, 2, -3), [1, 2, 3, 4]);
+}
+
+#[test]
+fn kerning() {
+    #[rustfmt::skip]
+    let kern: Vec<u8> = vec![
+        0, 0, 0, 1, // version 0, 1 subtable
+        0, 0, 0, 26, 0, 1, // version, length, horizontal format 0
+        0, 2, 0, 12, 0, 1, 0, 0, // 2 pairs, search range, entry selector, range shift
+        0, 0, 0, 1, 0, 10, // (0, 1): 10
+        0, 1, 0, 1, 0xff, 0xce, // (1, 1): -50
+    ];
+    let mut tables = common_tables(false).to_vec();
+    tables.insert(0, (b"CFF ", cff_table()));
+    tables.insert(4, (b"kern", kern));
+    let font = OutlineFont::new(sfnt(b"OTTO", &tables)).unwrap();
+    assert_eq!((font.kerning(0, 1), font.kerning(1, 1), font.kerning(1, 0)), (10, -50, 0));
+
+    let mut text = OutlineText::new(&font);
+    text.size(10.0, 0.0);
+    text.start_point(100.0, 100.0);
+    text.flip(true);
+    text.text("AA\nA");
+    let metrics = text.metrics();
+    assert_eq!(metrics.advance, 11.5);
+    assert_eq!((metrics.ascent, metrics.descent, metrics.line_gap), (8.0, -2.0, 0.9));
+    assert_eq!(metrics.line_height(), 10.9);
+    // Two kerned glyphs on the first line, one on the second
+    let ink = metrics.ink.unwrap();
+    assert_eq!((ink.x1(), ink.x2()), (1.0, 8.5));
+    assert!((ink.y1() - (1.0 - 10.9)).abs() < 1e-9 && (ink.y2() - 3.7).abs() < 1e-9);
+}
+
+/// A monospaced font with 10 units advances, and kerning between `A` and `V`.
+struct Mono;
+impl LayoutFont for Mono {
+    fn advance(&self, _: char) -> f64 {
+        10.0
+    }
+    fn kerning(&self, left: char, right: char) -> f64 {
+        iif![(left, right) == ('A', 'V'); -2.0; 0.0]
+    }
+    fn ascent(&self) -> f64 {
+        8.0
+    }
+    fn descent(&self) -> f64 {
+        -2.0
+    }
+}
+
+#[test]
+fn text_layout() {
+    let texts = |runs: &[GlyphRun]| runs.iter().map(|r| r.text.clone()).collect::<Vec<_>>();
+
+    // Hard breaks, and leading whitespace kept at the start of a paragraph
+    let runs = TextLayout::new("ab\n\n  cd  \r\nAV")
+        .position(5.0, 100.0)
+        .line_space(2.0)
+        .layout(&Mono);
+    assert_eq!(texts(&runs), ["ab", "", "  cd  ", "AV"]);
+    assert_eq!(runs.iter().map(|r| r.y).collect::<Vec<_>>(), [108.0, 120.0, 132.0, 144.0]);
+    assert_eq!(runs[3].width, 18.0);
+    let xs: Vec<_> = runs[3].glyphs.iter().map(|g| g.x).collect();
+    assert_eq!(xs, [5.0, 13.0]);
+
+    // Word wrapping, dropping the whitespace around the breaks
+    let layout = TextLayout::new("one two  three fourteen").max_width(75.0);
+    assert_eq!(texts(&layout.clone().layout(&Mono)), ["one two", "three", "fourteen"]);
+    let runs = layout.clone().xalign(XAlign::Right).yalign(YAlign::Bottom).layout(&Mono);
+    assert_eq!(runs.iter().map(|r| r.x).collect::<Vec<_>>(), [-70.0, -50.0, -80.0]);
+    assert_eq!(runs[2].y, -2.0);
+    let runs = layout.clone().xalign(XAlign::Center).yalign(YAlign::Baseline).layout(&Mono);
+    assert_eq!((runs[1].x, runs[0].y), (-25.0, 0.0));
+
+    // Overflow policies
+    let runs = layout.clone().overflow(Overflow::Clip).layout(&Mono);
+    assert_eq!(texts(&runs), ["one two", "three", "fourtee"]);
+    let runs = layout.clone().overflow(Overflow::Ellipsis).layout(&Mono);
+    assert_eq!(texts(&runs), ["one two", "three", "four..."]);
+    let runs = layout.clone().overflow(Overflow::Ellipsis).max_lines(2).layout(&Mono);
+    assert_eq!(texts(&runs), ["one two", "thre..."]);
+    let runs = layout.overflow(Overflow::Clip).max_lines(1).layout(&Mono);
+    assert_eq!(texts(&runs), ["one two"]);
+}
+
+#[test]
+fn gsv_text_measure() {
+    let mut text = GsvText::new();
+    text.size(21.0, 0.0);
+    text.text("ab\nc");
+    let width = text.text_width();
+    assert_eq!(width, text.advance('a') + text.advance('b'));
+
+    // The space goes between characters, on the widest line
+    text.space(2.0);
+    assert_eq!(text.text_width(), width + 2.0);
+    text.line_space(4.0);
+    let m = text.metrics();
+    assert_eq!((m.advance, m.ascent, m.descent, m.line_gap), (width + 2.0, 21.0, -7.0, -3.0));
+    let ink = m.ink.unwrap();
+    assert!(ink.x1() >= 0.0 && ink.x2() <= m.advance && ink.y2() <= m.ascent);
+    assert_eq!((m.line_height(), ink.y1()), (25.0, -25.0));
+
+    // Alignment moves the vertices by the width of each line, and the ascent
+    text.text("ab");
+    text.start_point(100.0, 50.0);
+    text.flip(true);
+    let start: Vec<_> = text.vertices().collect();
+    text.xalign(XAlign::Right);
+    text.yalign(YAlign::Top);
+    let aligned: Vec<_> = text.vertices().collect();
+    assert_eq!(start.len(), aligned.len());
+    for (s, a) in start.iter().zip(&aligned) {
+        assert!((s.x - width - 2.0 - a.x).abs() < 1e-9 && (s.y + 21.0 - a.y).abs() < 1e-9);
+    }
+    text.xalign(XAlign::Center);
+    text.yalign(YAlign::Center);
+    let ink = bounding_rect(&text).unwrap();
+    assert!(ink.x1() < 100.0 && ink.x2() > 100.0 && ink.y1() < 50.0 && ink.y2() > 50.0);
+
+    // The outline is wider than the strokes, and transformed at the end
+    let mut outline = GsvTextOutline::new(&text);
+    outline.width(4.0);
+    let out = bounding_rect(&outline).unwrap();
+    assert!(out.y1() < ink.y1() - 1.0 && out.y2() > ink.y2() + 1.0);
+    outline.transform(Some(Transform::translating(10.0, -5.0)));
+    let moved = bounding_rect(&outline).unwrap();
+    assert!((moved.x1() - out.x1() - 10.0).abs() < 1e-9);
+    assert!((moved.y2() - out.y2() + 5.0).abs() < 1e-9);
+}
+
+/// Builds a GSV font whose only glyph is an 'A' made of two lines.
+fn gsv_font(big_endian: bool) -> Vec<u8> {
+    let bytes = |v: i16| iif![big_endian; v.to_be_bytes(); v.to_le_bytes()];
+    let mut out: Vec<u8> = [10, 0, 10, 0, -2].into_iter().flat_map(bytes).collect();
+    out.extend((0..257).flat_map(|i| bytes(iif![i > 65; 4; 0])));
+    out.extend([5, 10, 5, 0x76]);
+    out
+}
+
+#[test]
+fn gsv_font_loading() {
+    let mut text = GsvText::new();
+    text.size(10.0, 0.0);
+    text.text("A");
+    let strokes = |text: &GsvText| text.vertices().map(|v| (v.x, v.y, v.cmd)).collect::<Vec<_>>();
+    let default = strokes(&text);
+
+    // Both byte orders read the same strokes
+    text.font(&gsv_font(false)).unwrap();
+    let expected = [
+        (0., 0., PathCommand::MoveTo),
+        (5., 10., PathCommand::LineTo),
+        (10., 0., PathCommand::LineTo),
+    ];
+    assert_eq!(strokes(&text), expected);
+    assert_eq!((text.advance('A'), text.descent()), (10.0, -2.0));
+    text.font(&gsv_font(true)).unwrap();
+    assert_eq!(strokes(&text), expected);
+
+    // Invalid fonts are rejected, keeping the current one
+    let font = gsv_font(false);
+    assert!(matches![text.font(&font[..8]), Err(AggFontError::Malformed(_))]);
+    assert!(matches![text.font(&font[..font.len() - 1]), Err(AggFontError::Malformed(_))]);
+    let mut flat = font.clone();
+    flat[4..6].fill(0);
+    assert!(matches![text.font(&flat), Err(AggFontError::Malformed(_))]);
+    assert_eq!(strokes(&text), expected);
+
+    text.font(GsvDefaultFont::get()).unwrap();
+    assert_eq!(strokes(&text), default);
+
+    #[cfg(feature = "std")]
+    {
+        let path = std::env::temp_dir().join("agrega_gsv_font_loading.gsv");
+        std::fs::write(&path, &font).unwrap();
+        text.load_font(&path).unwrap();
+        assert_eq!(strokes(&text), expected);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches![text.load_font(&path), Err(AggFontError::Io(_))]);
+    }
+}
+
+#[test]
+fn lcd_filter() {
+    // The coverage is spread symmetrically, and preserved
+    let covers = LcdFilter::default().apply(&[255, 0, 0, 0, 0, 90]);
+    assert_eq!(covers.len(), 10);
+    assert_eq!(covers[..5], [28, 57, 85, 57, 28]);
+    assert_eq!(covers[5..], [10, 20, 30, 20, 10]);
+    let filter = LcdFilter::new(3.0, 2.0, 1.0);
+    assert_eq!(filter, LcdFilter::default());
+    assert!(
+        (filter.primary() + 2.0 * (filter.secondary() + filter.tertiary()) - 1.0).abs() < 1e-12
+    );
+    assert_eq!(LcdFilter::NONE.apply(&[7, 255]), [0, 0, 7, 255, 0, 0]);
+
+    // Subpixels are grouped from the offset of the first one
+    let pixels = SubpixelOrder::Rgb.pixels(&[1, 2, 3, 4, 5], 2);
+    assert_eq!(pixels, [[0, 0, 1], [2, 3, 4], [5, 0, 0]]);
+    let pixels = SubpixelOrder::Bgr.pixels(&[1, 2, 3, 4, 5], 2);
+    assert_eq!(pixels, [[1, 0, 0], [4, 3, 2], [0, 0, 5]]);
+    assert!(SubpixelOrder::Rgb.pixels(&[], 1).is_empty());
+}
+
+#[test]
+fn raster_fonts() {
+    let fonts = [
+        (RasterFonts::MONO_4X6, 6, 1, Some(4)),
+        (RasterFonts::MONO_6X9, 9, 2, Some(6)),
+        (RasterFonts::MONO_7X9_BOLD, 9, 2, Some(7)),
+        (RasterFonts::SANS_9, 9, 2, None),
+    ];
+    for (data, height, baseline, width) in fonts {
+        let font = GlyphRasterBin::new(data).unwrap();
+        assert_eq!((font.height(), font.baseline()), (height, baseline));
+        for c in ' '..='~' {
+            let glyph = font.glyph(c).unwrap();
+            iif![let Some(width) = width; assert_eq!(glyph.width(), width)];
+        }
+        assert!(font.glyph('\u{1f}').is_none() && font.glyph('\u{7f}').is_none());
+        assert!(font.glyph('é').is_none());
+    }
+
+    // The rows are read from the top, with the first pixel in the highest bit
+    let font = GlyphRasterBin::new(RasterFonts::MONO_6X9).unwrap();
+    let a = font.glyph('A').unwrap();
+    let row = |glyph: &RasterGlyph, row| (0..6).map(|x| glyph.is_set(x, row)).collect::<Vec<_>>();
+    assert_eq!(row(&a, 0), [false, true, true, true, false, false]);
+    assert_eq!(row(&a, 4), [true, true, true, true, true, false]);
+    assert!(row(&a, 8).iter().all(|&set| !set));
+    assert!(!a.is_set(6, 4) && !a.is_set(0, 9));
+    assert_eq!(font.width("A\u{7f}A"), 12);
+
+    // Proportional glyphs keep a column of space
+    let font = GlyphRasterBin::new(RasterFonts::SANS_9).unwrap();
+    assert_eq!(font.glyph('i').unwrap().width(), 4);
+    assert_eq!(font.glyph('M').unwrap().width(), 6);
+    assert_eq!(font.glyph(' ').unwrap().width(), 3);
+
+    // Malformed fonts are rejected
+    let truncated = &RasterFonts::MONO_6X9[..RasterFonts::MONO_6X9.len() - 1];
+    assert!(matches!(GlyphRasterBin::new(truncated), Err(AggFontError::Malformed(_))));
+    assert!(GlyphRasterBin::new(&RasterFonts::MONO_6X9[..3]).is_err());
+    assert!(GlyphRasterBin::new(&RasterFonts::MONO_6X9[..100]).is_err());
+    assert!(GlyphRasterBin::new(&[9, 10, 0x20, 0]).is_err());
+    assert!(GlyphRasterBin::new(&[9, 2, 0x20, 1, 0xff, 0xff, 0]).is_err());
+    assert!(GlyphRasterBin::new(&[9, 2, 0x20, 0]).unwrap().glyph(' ').is_none());
+}
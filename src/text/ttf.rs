@@ -0,0 +1,1296 @@
+// agrega::font::ttf
+//
+//! TrueType and OpenType fonts
+//!
+//! A native reader for fonts with `glyf` or `CFF ` outlines, which doesn't need
+//! FreeType. Glyph outlines keep their curves, as `Curve3` commands for TrueType
+//! and `Curve4` commands for CFF, so they can be transformed, stroked, dashed
+//! and clipped like any other path once passed through a [`Curve`] converter.
+//!
+//! # Examples
+//! ```
+//! use agrega::{Curve, OutlineFont, OutlineText, VertexSource};
+//!
+//! let data = std::fs::read("tests/std/assets/Helvetica.ttc").unwrap();
+//! let font = OutlineFont::from_collection(data, 0).unwrap();
+//! let glyph = font.glyph_index('A').unwrap();
+//! assert!(font.advance_width(glyph) > 0);
+//!
+//! let mut text = OutlineText::new(&font);
+//! text.size(24.0, 0.0);
+//! text.start_point(10.0, 30.0);
+//! text.flip(true);
+//! text.text("Hello");
+//! let lines = Curve::new(text).xconvert();
+//! assert!(!lines.is_empty());
+//! ```
+//!
+//! [`Curve`]: crate::Curve
+//
+// TOC
+// - struct OutlineFont
+// - struct OutlineText
+// - glyf outlines
+// - CFF outlines
+// - helpers
+
+use super::cache::{fnv1a, FNV_OFFSET};
+use crate::{
+    bounding_rect, paths::Vertex, AggFontError, Curve, GlyphCache, GlyphData, LayoutFont, Path,
+    TextMetrics, Transform, VertexSource,
+};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::ops::Range;
+use devela::iif;
+
+/// A TrueType or OpenType font, read without FreeType.
+///
+/// Supports `glyf` outlines, including composite glyphs, and `CFF ` outlines,
+/// including CID-keyed fonts. Glyph outlines are returned in font units,
+/// with the y axis pointing up.
+#[derive(Clone, Debug)]
+pub struct OutlineFont {
+    data: Vec<u8>,
+    units_per_em: u16,
+    num_glyphs: u16,
+    ascender: i16,
+    descender: i16,
+    line_gap: i16,
+    num_h_metrics: u16,
+    hmtx: Range<usize>,
+    /// Offset of the selected `cmap` subtable, and whether it is a symbol one.
+    cmap: Option<(usize, bool)>,
+    /// Offset and number of the kerning pairs of the horizontal `kern` subtable.
+    kern: Option<(usize, usize)>,
+    outlines: Outlines,
+    /// A hash of the table directory and the `head` table, that identifies the font.
+    signature: u64,
+}
+
+#[derive(Clone, Debug)]
+enum Outlines {
+    Glyf { loca: Range<usize>, glyf: Range<usize>, long_loca: bool },
+    Cff(Cff),
+}
+
+impl OutlineFont {
+    /// Reads a font from its bytes.
+    ///
+    /// For a font collection, reads its first font.
+    pub fn new(data: Vec<u8>) -> Result<Self, AggFontError> {
+        Self::from_collection(data, 0)
+    }
+
+    /// Reads the font at `index` of a font collection (`.ttc`) from its bytes.
+    ///
+    /// A single font is read as a collection of one font.
+    pub fn from_collection(data: Vec<u8>, index: usize) -> Result<Self, AggFontError> {
+        iif![index >= Self::collection_len(&data); return Err(AggFontError::FaceIndex(index))];
+        let offset = if data.starts_with(b"ttcf") {
+            u32_at(&data, 12 + 4 * index).ok_or(AggFontError::Malformed("collection header"))?
+                as usize
+        } else {
+            0
+        };
+        match u32_at(&data, offset) {
+            Some(0x0001_0000 | 0x7472_7565 | 0x4F54_544F) => {} // 1.0, 'true', 'OTTO'
+            _ => return Err(AggFontError::Unsupported("font format")),
+        }
+
+        let table = |tag: &'static str| find_table(&data, offset, tag);
+        let required = |tag: &'static str| table(tag).ok_or(AggFontError::MissingTable(tag));
+        let head = required("head")?;
+        let maxp = required("maxp")?;
+        let hhea = required("hhea")?;
+        let hmtx = required("hmtx")?;
+
+        let malformed = |what| AggFontError::Malformed(what);
+        let units_per_em = u16_at(&data, head.start + 18).ok_or(malformed("head"))?;
+        iif![units_per_em == 0; return Err(malformed("head"))];
+        let long_loca = i16_at(&data, head.start + 50).ok_or(malformed("head"))? != 0;
+        let num_glyphs = u16_at(&data, maxp.start + 4).ok_or(malformed("maxp"))?;
+        let ascender = i16_at(&data, hhea.start + 4).ok_or(malformed("hhea"))?;
+        let descender = i16_at(&data, hhea.start + 6).ok_or(malformed("hhea"))?;
+        let line_gap = i16_at(&data, hhea.start + 8).ok_or(malformed("hhea"))?;
+        let num_h_metrics = u16_at(&data, hhea.start + 34).ok_or(malformed("hhea"))?;
+        iif![num_h_metrics == 0 || hmtx.len() < 4 * num_h_metrics as usize;
+            return Err(malformed("hmtx"))];
+
+        let cmap = table("cmap").and_then(|cmap| select_cmap(&data, cmap));
+        let kern = table("kern").and_then(|kern| select_kern(&data, kern));
+        let outlines = match (table("glyf"), table("loca"), table("CFF ")) {
+            (Some(glyf), Some(loca), _) => Outlines::Glyf { loca, glyf, long_loca },
+            (_, _, Some(cff)) => Outlines::Cff(Cff::parse(&data, cff)?),
+            _ => return Err(AggFontError::MissingTable("glyf")),
+        };
+        let num_tables = u16_at(&data, offset + 4).unwrap_or(0) as usize;
+        let directory = data.get(offset..offset + 12 + 16 * num_tables).unwrap_or_default();
+        let signature = fnv1a(fnv1a(FNV_OFFSET, directory), &data[head]);
+        Ok(Self {
+            data,
+            units_per_em,
+            num_glyphs,
+            ascender,
+            descender,
+            line_gap,
+            num_h_metrics,
+            hmtx,
+            cmap,
+            kern,
+            outlines,
+            signature,
+        })
+    }
+
+    /// Returns the number of fonts in `data`, which is 1 if it isn't a collection.
+    #[must_use]
+    pub fn collection_len(data: &[u8]) -> usize {
+        iif![data.starts_with(b"ttcf"); u32_at(data, 8).unwrap_or(0) as usize; 1]
+    }
+
+    /// Returns the number of font units per em square.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn units_per_em(&self) -> u16 { self.units_per_em }
+
+    /// Returns the number of glyphs in the font.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn num_glyphs(&self) -> u16 { self.num_glyphs }
+
+    /// Returns the typographic ascender, in font units.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn ascender(&self) -> i16 { self.ascender }
+
+    /// Returns the typographic descender, in font units, usually negative.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn descender(&self) -> i16 { self.descender }
+
+    /// Returns the gap between lines, in font units.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub const fn line_gap(&self) -> i16 { self.line_gap }
+
+    /// Returns a hash that identifies the font, for caching its glyphs.
+    #[inline] #[must_use] #[rustfmt::skip]
+    pub(crate) const fn signature(&self) -> u64 { self.signature }
+
+    /// Returns the glyph index of the character `c`.
+    ///
+    /// Returns `None` if the font has no glyph for it.
+    #[must_use]
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        let (sub, symbol) = self.cmap?;
+        let glyph = cmap_lookup(&self.data, sub, c as u32)
+            .filter(|&g| g != 0)
+            // Symbol fonts map their characters in the private use area
+            .or_else(|| iif![symbol; cmap_lookup(&self.data, sub, 0xF000 | c as u32); None])?;
+        iif![glyph != 0 && glyph < self.num_glyphs; Some(glyph); None]
+    }
+
+    /// Returns the horizontal advance of `glyph`, in font units.
+    #[must_use]
+    pub fn advance_width(&self, glyph: u16) -> u16 {
+        let i = glyph.min(self.num_h_metrics - 1) as usize;
+        u16_at(&self.data, self.hmtx.start + 4 * i).unwrap_or(0)
+    }
+
+    /// Returns the left side bearing of `glyph`, in font units.
+    #[must_use]
+    pub fn left_side_bearing(&self, glyph: u16) -> i16 {
+        let (g, n) = (glyph as usize, self.num_h_metrics as usize);
+        let at = iif![g < n; 4 * g + 2; 4 * n + 2 * (g - n)];
+        iif![at + 2 <= self.hmtx.len(); i16_at(&self.data, self.hmtx.start + at).unwrap_or(0); 0]
+    }
+
+    /// Returns the kerning adjustment between the glyphs `left` and `right`,
+    /// in font units.
+    ///
+    /// Reads the pairs of the `kern` table, and returns 0 if there is none.
+    #[must_use]
+    pub fn kerning(&self, left: u16, right: u16) -> i16 {
+        let Some((pairs, len)) = self.kern else { return 0 };
+        let pair = (u32::from(left) << 16) | u32::from(right);
+        let (mut lo, mut hi) = (0, len);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let Some(key) = u32_at(&self.data, pairs + 6 * mid) else { return 0 };
+            match key.cmp(&pair) {
+                core::cmp::Ordering::Less => lo = mid + 1,
+                core::cmp::Ordering::Greater => hi = mid,
+                core::cmp::Ordering::Equal => {
+                    return i16_at(&self.data, pairs + 6 * mid + 4).unwrap_or(0)
+                }
+            }
+        }
+        0
+    }
+
+    /// Returns the outline of `glyph` in font units, with the y axis pointing up.
+    ///
+    /// Empty glyphs, like the space, return an empty path.
+    pub fn glyph_outline(&self, glyph: u16) -> Result<Path, AggFontError> {
+        let mut path = Path::new();
+        self.glyph_outline_into(&mut path, glyph, &Transform::new())?;
+        Ok(path)
+    }
+
+    /// Appends the outline of `glyph` to `path`, transformed by `trans`.
+    pub(crate) fn glyph_outline_into(
+        &self,
+        path: &mut Path,
+        glyph: u16,
+        trans: &Transform,
+    ) -> Result<(), AggFontError> {
+        iif![glyph >= self.num_glyphs; return Err(AggFontError::Malformed("glyph index"))];
+        match &self.outlines {
+            Outlines::Glyf { .. } => self.glyf_outline(path, glyph, trans, 0, &mut 0),
+            Outlines::Cff(cff) => cff.outline(&self.data, path, glyph, trans),
+        }
+    }
+}
+
+/// Renders text with an [`OutlineFont`] as vector graphics.
+///
+/// The outlines keep their curves, so pass it through a
+/// [`Curve`][crate::Curve] converter before rasterizing or stroking it.
+#[derive(Clone, Debug)]
+pub struct OutlineText<'a> {
+    font: &'a OutlineFont,
+    x: f64,
+    y: f64,
+    start_x: f64,
+    width: f64,
+    height: f64,
+    line_space: f64,
+    text: String,
+    flip: bool,
+    cache: Option<&'a GlyphCache>,
+}
+
+impl<'a> OutlineText<'a> {
+    /// Creates a new `OutlineText` using the given `font`.
+    pub fn new(font: &'a OutlineFont) -> Self {
+        Self {
+            font,
+            x: 0.0,
+            y: 0.0,
+            start_x: 0.0,
+            width: 0.0,
+            height: 10.0,
+            line_space: 0.0,
+            text: String::new(),
+            flip: false,
+            cache: None,
+        }
+    }
+    /// Sets the height of the em square and the width of the text.
+    ///
+    /// A `width` of 0.0 keeps the proportions of the font.
+    pub fn size(&mut self, height: f64, width: f64) {
+        self.height = height;
+        self.width = width;
+    }
+    /// Adjusts the spacing between lines of text.
+    pub fn line_space(&mut self, line_space: f64) {
+        self.line_space = line_space;
+    }
+    /// Defines where the baseline of the text begins on the rendering surface.
+    pub fn start_point(&mut self, x: f64, y: f64) {
+        self.x = x;
+        self.start_x = x;
+        self.y = y;
+    }
+    /// Mirrors the text vertically, for surfaces with the y axis pointing down.
+    pub fn flip(&mut self, flip: bool) {
+        self.flip = flip;
+    }
+    /// Specifies the actual string of text to render.
+    pub fn text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+    /// Reads the glyph outlines through the given `cache`.
+    pub fn cache(&mut self, cache: &'a GlyphCache) {
+        self.cache = Some(cache);
+    }
+
+    /// Measures the text, relative to a start point at the origin and unflipped.
+    ///
+    /// For several lines, the advance is the one of the widest line.
+    #[must_use]
+    pub fn metrics(&self) -> TextMetrics {
+        let font = self.font;
+        let (sx, sy) = self.scale();
+        let mut metrics = TextMetrics {
+            ascent: f64::from(font.ascender()) * sy,
+            descent: f64::from(font.descender()) * sy,
+            line_gap: f64::from(font.line_gap()) * sy,
+            ..TextMetrics::default()
+        };
+        let (mut x, mut prev) = (0.0, None);
+        for chr in self.text.chars() {
+            if chr == '\n' {
+                metrics.advance = metrics.advance.max(x);
+                (x, prev) = (0.0, None);
+                continue;
+            }
+            let glyph = font.glyph_index(chr).unwrap_or(0);
+            iif![let Some(prev) = prev; x += f64::from(font.kerning(prev, glyph)) * sx];
+            x += f64::from(font.advance_width(glyph)) * sx;
+            prev = Some(glyph);
+        }
+        metrics.advance = metrics.advance.max(x);
+
+        let mut text = self.clone();
+        text.start_point(0.0, 0.0);
+        text.flip(false);
+        if let Some(ink) = bounding_rect(&Curve::new(&text)) {
+            metrics.add_ink(ink.x1(), ink.y1(), ink.x2(), ink.y2());
+        }
+        metrics
+    }
+
+    /// Returns the horizontal and vertical scale from font units.
+    fn scale(&self) -> (f64, f64) {
+        let units = f64::from(self.font.units_per_em());
+        let sy = self.height / units;
+        (iif![self.width == 0.0; sy; self.width / units], sy)
+    }
+}
+
+impl VertexSource for OutlineText<'_> {
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        let font = self.font;
+        let (sx, sy) = self.scale();
+        let line = f64::from(i32::from(font.ascender()) - i32::from(font.descender()))
+            + f64::from(font.line_gap());
+        let line_height = line * sy + self.line_space;
+
+        // The pen position carries over from one glyph to the next
+        let mut x = self.start_x;
+        let mut y = self.y;
+        let mut prev = None;
+        self.text.chars().flat_map(move |chr| {
+            let mut path = Path::new();
+            if chr == '\n' {
+                x = self.start_x;
+                y += iif![self.flip; line_height; -line_height];
+                prev = None;
+                return path.vertices.into_iter();
+            }
+            let glyph = font.glyph_index(chr).unwrap_or(0);
+            iif![let Some(prev) = prev; x += f64::from(font.kerning(prev, glyph)) * sx];
+            prev = Some(glyph);
+            let trans =
+                Transform { sx, sy: iif![self.flip; -sy; sy], tx: x, ty: y, ..Transform::new() };
+            // A malformed glyph is left out, like a missing one
+            if let Some(cache) = self.cache {
+                if let Ok(cached) = cache.outline_glyph(font, glyph) {
+                    iif![let GlyphData::Outline(outline) = &cached.data;
+                        path = outline.transformed(&trans)];
+                }
+            } else if font.glyph_outline_into(&mut path, glyph, &trans).is_err() {
+                path.remove_all();
+            }
+            x += f64::from(font.advance_width(glyph)) * sx;
+            path.vertices.into_iter()
+        })
+    }
+}
+
+impl LayoutFont for OutlineText<'_> {
+    fn advance(&self, c: char) -> f64 {
+        let glyph = self.font.glyph_index(c).unwrap_or(0);
+        f64::from(self.font.advance_width(glyph)) * self.scale().0
+    }
+    fn kerning(&self, left: char, right: char) -> f64 {
+        let (left, right) = (self.font.glyph_index(left), self.font.glyph_index(right));
+        f64::from(self.font.kerning(left.unwrap_or(0), right.unwrap_or(0))) * self.scale().0
+    }
+    fn ascent(&self) -> f64 {
+        f64::from(self.font.ascender()) * self.scale().1
+    }
+    fn descent(&self) -> f64 {
+        f64::from(self.font.descender()) * self.scale().1
+    }
+    fn line_height(&self) -> f64 {
+        let font = self.font;
+        let line =
+            i32::from(font.ascender()) - i32::from(font.descender()) + i32::from(font.line_gap());
+        f64::from(line) * self.scale().1
+    }
+}
+
+/* glyf outlines */
+
+/// Maximum nesting of composite glyphs.
+const MAX_COMPONENT_DEPTH: usize = 8;
+/// Maximum number of components read for a glyph, across all nesting levels.
+const MAX_COMPONENTS: usize = 1024;
+
+impl OutlineFont {
+    /// Returns the byte range of `glyph` in the `glyf` table.
+    fn glyf_range(&self, glyph: u16) -> Option<Range<usize>> {
+        let Outlines::Glyf { loca, glyf, long_loca } = &self.outlines else { return None };
+        let g = glyph as usize;
+        let (start, end) = if *long_loca {
+            let at = loca.start + 4 * g;
+            (u32_at(&self.data, at)? as usize, u32_at(&self.data, at + 4)? as usize)
+        } else {
+            let at = loca.start + 2 * g;
+            (2 * u16_at(&self.data, at)? as usize, 2 * u16_at(&self.data, at + 2)? as usize)
+        };
+        iif![start > end || glyf.start.checked_add(end)? > glyf.end; return None];
+        Some(glyf.start + start..glyf.start + end)
+    }
+
+    fn glyf_outline(
+        &self,
+        path: &mut Path,
+        glyph: u16,
+        trans: &Transform,
+        depth: usize,
+        components: &mut usize,
+    ) -> Result<(), AggFontError> {
+        let malformed = || AggFontError::Malformed("glyf");
+        let range = self.glyf_range(glyph).ok_or(AggFontError::Malformed("loca"))?;
+        iif![range.is_empty(); return Ok(())];
+        let g = &self.data[range];
+        let contours = i16_at(g, 0).ok_or_else(malformed)?;
+        // The origin is placed so that the left side bearing matches the `hmtx` one
+        let shifted;
+        let trans = if depth == 0 {
+            let x_min = i16_at(g, 2).ok_or_else(malformed)?;
+            let shift = f64::from(self.left_side_bearing(glyph)) - f64::from(x_min);
+            shifted = Transform { tx: shift, ..Transform::new() } * *trans;
+            iif![shift == 0.0; trans; &shifted]
+        } else {
+            trans
+        };
+        if contours >= 0 {
+            return simple_glyph(path, g, contours as usize, trans).ok_or_else(malformed);
+        }
+        iif![depth >= MAX_COMPONENT_DEPTH; return Err(malformed())];
+        let mut pos = 10;
+        loop {
+            // Bounds the total work, since each level can reuse components many times
+            *components += 1;
+            iif![*components > MAX_COMPONENTS; return Err(malformed())];
+            let (flags, component, local, next) =
+                composite_component(g, pos).ok_or_else(malformed)?;
+            self.glyf_outline(path, component, &(local * *trans), depth + 1, components)?;
+            iif![flags & MORE_COMPONENTS == 0; break];
+            pos = next;
+        }
+        Ok(())
+    }
+}
+
+// Composite glyph flags
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const SCALED_COMPONENT_OFFSET: u16 = 0x0800;
+
+// Simple glyph flags
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const REPEAT_FLAG: u8 = 0x08;
+const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+
+/// Appends the contours of a simple glyph to `path`.
+fn simple_glyph(path: &mut Path, g: &[u8], contours: usize, trans: &Transform) -> Option<()> {
+    iif![contours == 0; return Some(())];
+    let ends: Vec<usize> = (0..contours)
+        .map(|i| u16_at(g, 10 + 2 * i).map(usize::from))
+        .collect::<Option<_>>()?;
+    let n = ends[contours - 1] + 1;
+    let mut pos = 12 + 2 * contours + u16_at(g, 10 + 2 * contours)? as usize;
+
+    let mut flags = Vec::with_capacity(n);
+    while flags.len() < n {
+        let flag = *g.get(pos)?;
+        pos += 1;
+        let repeat = iif![flag & REPEAT_FLAG != 0; { pos += 1; *g.get(pos - 1)? }; 0];
+        for _ in 0..=repeat {
+            flags.push(flag);
+        }
+    }
+    flags.truncate(n);
+
+    let mut coords = |short: u8, same_or_positive: u8| -> Option<Vec<f64>> {
+        let mut v = 0_i32;
+        let mut out = Vec::with_capacity(n);
+        for &flag in &flags {
+            if flag & short != 0 {
+                let d = i32::from(*g.get(pos)?);
+                pos += 1;
+                v += iif![flag & same_or_positive != 0; d; -d];
+            } else if flag & same_or_positive == 0 {
+                v += i32::from(i16_at(g, pos)?);
+                pos += 2;
+            }
+            out.push(f64::from(v));
+        }
+        Some(out)
+    };
+    let xs = coords(X_SHORT_VECTOR, X_IS_SAME_OR_POSITIVE)?;
+    let ys = coords(Y_SHORT_VECTOR, Y_IS_SAME_OR_POSITIVE)?;
+
+    let mut start = 0;
+    for &end in &ends {
+        iif![end < start || end >= n; return None];
+        let points: Vec<_> = (start..=end)
+            .map(|i| {
+                let (x, y) = trans.transform(xs[i], ys[i]);
+                (x, y, flags[i] & ON_CURVE_POINT != 0)
+            })
+            .collect();
+        quadratic_contour(path, &points);
+        start = end + 1;
+    }
+    Some(())
+}
+
+/// Reads the component of a composite glyph at `pos`.
+///
+/// Returns its flags, glyph index, transformation and the position of the next one.
+fn composite_component(g: &[u8], mut pos: usize) -> Option<(u16, u16, Transform, usize)> {
+    let flags = u16_at(g, pos)?;
+    let glyph = u16_at(g, pos + 2)?;
+    pos += 4;
+    let (arg1, arg2) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+        pos += 4;
+        (i16_at(g, pos - 4)?, i16_at(g, pos - 2)?)
+    } else {
+        pos += 2;
+        (i16::from(*g.get(pos - 2)? as i8), i16::from(*g.get(pos - 1)? as i8))
+    };
+    let f2dot14 = |at: usize| i16_at(g, at).map(|v| f64::from(v) / 16384.0);
+    let (mut a, mut b, mut c, mut d) = (1.0, 0.0, 0.0, 1.0);
+    if flags & WE_HAVE_A_SCALE != 0 {
+        a = f2dot14(pos)?;
+        d = a;
+        pos += 2;
+    } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+        (a, d) = (f2dot14(pos)?, f2dot14(pos + 2)?);
+        pos += 4;
+    } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+        (a, b, c, d) = (f2dot14(pos)?, f2dot14(pos + 2)?, f2dot14(pos + 4)?, f2dot14(pos + 6)?);
+        pos += 8;
+    }
+    // Anchoring components by matching points isn't supported, so they stay in place
+    let (mut dx, mut dy) =
+        iif![flags & ARGS_ARE_XY_VALUES != 0; (f64::from(arg1), f64::from(arg2)); (0.0, 0.0)];
+    if flags & SCALED_COMPONENT_OFFSET != 0 {
+        (dx, dy) = (a * dx + c * dy, b * dx + d * dy);
+    }
+    Some((flags, glyph, Transform { sx: a, shy: b, shx: c, sy: d, tx: dx, ty: dy }, pos))
+}
+
+/// Appends a closed contour of on-curve and off-curve points to `path`.
+///
+/// Two consecutive off-curve points imply an on-curve point between them.
+fn quadratic_contour(path: &mut Path, points: &[(f64, f64, bool)]) {
+    iif![points.len() < 2; return];
+    let mid = |a: (f64, f64, bool), b: (f64, f64, bool)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    let (first, last) = (points[0], points[points.len() - 1]);
+    // Starts on an on-curve point, which may have to be implied
+    let (start, rest) = if first.2 {
+        ((first.0, first.1), &points[1..])
+    } else if last.2 {
+        ((last.0, last.1), &points[..points.len() - 1])
+    } else {
+        (mid(first, last), points)
+    };
+    path.move_to(start.0, start.1);
+    let mut ctrl: Option<(f64, f64, bool)> = None;
+    for &p in rest {
+        match (ctrl, p.2) {
+            (Some(c), true) => path.curve3(c.0, c.1, p.0, p.1),
+            (None, true) => path.line_to(p.0, p.1),
+            (Some(c), false) => {
+                let (x, y) = mid(c, p);
+                path.curve3(c.0, c.1, x, y);
+            }
+            (None, false) => {}
+        }
+        ctrl = iif![p.2; None; Some(p)];
+    }
+    if let Some(c) = ctrl {
+        path.curve3(c.0, c.1, start.0, start.1);
+    }
+    path.close_polygon();
+}
+
+/* CFF outlines */
+
+/// The parsed structure of a `CFF ` table.
+#[derive(Clone, Debug)]
+struct Cff {
+    /// The byte range of the table.
+    table: Range<usize>,
+    charstrings: CffIndex,
+    global_subrs: CffIndex,
+    /// The local subroutines of each font dict.
+    local_subrs: Vec<CffIndex>,
+    /// The offset of the `FDSelect` structure of CID-keyed fonts.
+    fd_select: Option<usize>,
+}
+
+/// An INDEX structure of a `CFF ` table, with offsets relative to the table.
+#[derive(Clone, Copy, Debug, Default)]
+struct CffIndex {
+    count: usize,
+    off_size: usize,
+    /// The offset of the offset array.
+    offsets: usize,
+    /// The offset preceding the first byte of data, since offsets start at 1.
+    data: usize,
+    /// The offset after the last byte of data.
+    end: usize,
+}
+
+impl CffIndex {
+    fn parse(t: &[u8], at: usize) -> Option<Self> {
+        let count = u16_at(t, at)? as usize;
+        iif![count == 0; return Some(Self { end: at + 2, ..Self::default() })];
+        let off_size = *t.get(at + 2)? as usize;
+        iif![!(1..=4).contains(&off_size); return None];
+        let offsets = at + 3;
+        let data = offsets + (count + 1) * off_size - 1;
+        let end = data.checked_add(offset_at(t, offsets + count * off_size, off_size)?)?;
+        iif![end > t.len(); return None];
+        Some(Self { count, off_size, offsets, data, end })
+    }
+    fn get<'a>(&self, t: &'a [u8], i: usize) -> Option<&'a [u8]> {
+        iif![i >= self.count; return None];
+        let start = offset_at(t, self.offsets + i * self.off_size, self.off_size)?;
+        let end = offset_at(t, self.offsets + (i + 1) * self.off_size, self.off_size)?;
+        t.get(self.data.checked_add(start)?..self.data.checked_add(end)?)
+    }
+}
+
+// Top and private DICT operators
+const OP_CHARSTRINGS: u16 = 17;
+const OP_PRIVATE: u16 = 18;
+const OP_SUBRS: u16 = 19;
+const OP_CHARSTRING_TYPE: u16 = 0x0c06;
+const OP_ROS: u16 = 0x0c1e;
+const OP_FD_ARRAY: u16 = 0x0c24;
+const OP_FD_SELECT: u16 = 0x0c25;
+
+impl Cff {
+    fn parse(data: &[u8], table: Range<usize>) -> Result<Self, AggFontError> {
+        let malformed = || AggFontError::Malformed("CFF");
+        let t = &data[table.clone()];
+        let header_size = *t.get(2).ok_or_else(malformed)? as usize;
+        let names = CffIndex::parse(t, header_size).ok_or_else(malformed)?;
+        let top_dicts = CffIndex::parse(t, names.end).ok_or_else(malformed)?;
+        let strings = CffIndex::parse(t, top_dicts.end).ok_or_else(malformed)?;
+        let global_subrs = CffIndex::parse(t, strings.end).ok_or_else(malformed)?;
+        let top = top_dicts.get(t, 0).ok_or_else(malformed)?;
+
+        if dict_get(top, OP_CHARSTRING_TYPE).is_some_and(|v| v.first() != Some(&2.0)) {
+            return Err(AggFontError::Unsupported("CFF charstring type"));
+        }
+        let offset = |op| dict_get(top, op).and_then(|v| v.first().map(|&o| o as usize));
+        let charstrings = offset(OP_CHARSTRINGS)
+            .and_then(|at| CffIndex::parse(t, at))
+            .ok_or_else(malformed)?;
+
+        let (local_subrs, fd_select) = if dict_get(top, OP_ROS).is_some() {
+            let fd_array = offset(OP_FD_ARRAY)
+                .and_then(|at| CffIndex::parse(t, at))
+                .ok_or_else(malformed)?;
+            let subrs = (0..fd_array.count)
+                .map(|i| fd_array.get(t, i).map(|fd| private_subrs(t, fd)).ok_or_else(malformed))
+                .collect::<Result<_, _>>()?;
+            (subrs, Some(offset(OP_FD_SELECT).ok_or_else(malformed)?))
+        } else {
+            (vec![private_subrs(t, top)], None)
+        };
+        Ok(Self { table, charstrings, global_subrs, local_subrs, fd_select })
+    }
+
+    /// Returns the font dict used by `glyph`.
+    fn font_dict(&self, t: &[u8], glyph: u16) -> Option<usize> {
+        let Some(at) = self.fd_select else { return Some(0) };
+        match *t.get(at)? {
+            0 => t.get(at + 1 + glyph as usize).map(|&fd| fd as usize),
+            3 => {
+                let ranges = u16_at(t, at + 1)? as usize;
+                let mut fd = None;
+                for i in 0..ranges {
+                    let range = at + 3 + 3 * i;
+                    iif![u16_at(t, range)? > glyph; break];
+                    fd = t.get(range + 2).map(|&fd| fd as usize);
+                }
+                fd
+            }
+            _ => None,
+        }
+    }
+
+    fn outline(
+        &self,
+        data: &[u8],
+        path: &mut Path,
+        glyph: u16,
+        trans: &Transform,
+    ) -> Result<(), AggFontError> {
+        let malformed = || AggFontError::Malformed("CFF");
+        let t = &data[self.table.clone()];
+        let code = self.charstrings.get(t, glyph as usize).ok_or_else(malformed)?;
+        let fd = self.font_dict(t, glyph).ok_or_else(malformed)?;
+        let local_subrs = self.local_subrs.get(fd).copied().unwrap_or_default();
+        let mut cs = Charstring {
+            t,
+            global_subrs: self.global_subrs,
+            local_subrs,
+            path,
+            trans,
+            stack: Vec::with_capacity(MAX_CHARSTRING_ARGS),
+            x: 0.0,
+            y: 0.0,
+            stems: 0,
+            width_done: false,
+            open: false,
+        };
+        cs.run(code, 0)?;
+        cs.close();
+        Ok(())
+    }
+}
+
+/// Returns the local subroutines of the private dict referenced by `dict`.
+fn private_subrs(t: &[u8], dict: &[u8]) -> CffIndex {
+    let private = dict_get(dict, OP_PRIVATE)
+        .filter(|v| v.len() == 2)
+        .map(|v| (v[0] as usize, v[1] as usize));
+    private
+        .and_then(|(size, at)| {
+            let subrs = dict_get(t.get(at..at.checked_add(size)?)?, OP_SUBRS)?;
+            CffIndex::parse(t, at.checked_add(*subrs.first()? as usize)?)
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the operands of the operator `op` in a DICT structure.
+fn dict_get(dict: &[u8], op: u16) -> Option<Vec<f64>> {
+    let mut operands = vec![];
+    let mut pos = 0;
+    while pos < dict.len() {
+        let b0 = dict[pos];
+        pos += 1;
+        match b0 {
+            0..=21 => {
+                let operator = if b0 == 12 {
+                    pos += 1;
+                    0x0c00 | u16::from(*dict.get(pos - 1)?)
+                } else {
+                    u16::from(b0)
+                };
+                iif![operator == op; return Some(operands)];
+                operands.clear();
+            }
+            28 => {
+                operands.push(f64::from(i16_at(dict, pos)?));
+                pos += 2;
+            }
+            29 => {
+                operands.push(f64::from(u32_at(dict, pos)? as i32));
+                pos += 4;
+            }
+            30 => {
+                let (value, len) = dict_real(&dict[pos..])?;
+                operands.push(value);
+                pos += len;
+            }
+            32..=246 => operands.push(f64::from(i32::from(b0) - 139)),
+            247..=254 => {
+                let b1 = i32::from(*dict.get(pos)?);
+                pos += 1;
+                let v = iif![b0 < 251;
+                    (i32::from(b0) - 247) * 256 + b1 + 108;
+                    -(i32::from(b0) - 251) * 256 - b1 - 108];
+                operands.push(f64::from(v));
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Parses a real number operand, returning it with the number of bytes read.
+fn dict_real(bytes: &[u8]) -> Option<(f64, usize)> {
+    let (mut mantissa, mut scale, mut exponent) = (0.0, 0_i32, 0_i32);
+    let (mut negative, mut fraction, mut in_exponent, mut exp_negative) =
+        (false, false, false, false);
+    for (i, &b) in bytes.iter().enumerate() {
+        for nibble in [b >> 4, b & 0xf] {
+            match nibble {
+                // Clamped, since larger exponents over or underflow anyway
+                0..=9 if in_exponent => {
+                    exponent = (exponent * 10 + i32::from(nibble)).min(MAX_DICT_EXPONENT);
+                }
+                0..=9 => {
+                    mantissa = mantissa * 10.0 + f64::from(nibble);
+                    iif![fraction; scale -= 1];
+                }
+                0xa => fraction = true,
+                0xb => in_exponent = true,
+                0xc => (in_exponent, exp_negative) = (true, true),
+                0xe => negative = true,
+                0xf => {
+                    let exponent = (scale + iif![exp_negative; -exponent; exponent])
+                        .clamp(-MAX_DICT_EXPONENT, MAX_DICT_EXPONENT);
+                    let value = mantissa * powi10(exponent);
+                    return Some((iif![negative; -value; value], i + 1));
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Maximum magnitude of a DICT real exponent, beyond the range of `f64`.
+const MAX_DICT_EXPONENT: i32 = 400;
+
+/// Returns 10 raised to `exp`, without needing floating-point functions.
+fn powi10(exp: i32) -> f64 {
+    let mut v = 1.0;
+    for _ in 0..exp.unsigned_abs() {
+        v *= 10.0;
+    }
+    iif![exp < 0; 1.0 / v; v]
+}
+
+/// Maximum number of arguments on the charstring stack.
+const MAX_CHARSTRING_ARGS: usize = 48;
+/// Maximum nesting of subroutine calls.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// A Type 2 charstring interpreter.
+struct Charstring<'a, 'p> {
+    t: &'a [u8],
+    global_subrs: CffIndex,
+    local_subrs: CffIndex,
+    path: &'p mut Path,
+    trans: &'p Transform,
+    stack: Vec<f64>,
+    x: f64,
+    y: f64,
+    /// The number of stem hints declared, to skip the hint masks.
+    stems: usize,
+    /// Whether the optional advance width argument was already handled.
+    width_done: bool,
+    /// Whether there is a contour to close.
+    open: bool,
+}
+
+impl Charstring<'_, '_> {
+    /// Runs a charstring, returning `true` once it ends the glyph.
+    fn run(&mut self, code: &[u8], depth: usize) -> Result<bool, AggFontError> {
+        let malformed = || AggFontError::Malformed("CFF charstring");
+        iif![depth > MAX_SUBR_DEPTH; return Err(malformed())];
+        let mut pos = 0;
+        while pos < code.len() {
+            let b0 = code[pos];
+            pos += 1;
+            let n = self.stack.len();
+            match b0 {
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => {
+                    self.width(n % 2 == 1);
+                    self.stems += self.stack.len() / 2;
+                    self.stack.clear();
+                }
+                // hintmask, cntrmask
+                19 | 20 => {
+                    self.width(n % 2 == 1);
+                    self.stems += self.stack.len() / 2;
+                    self.stack.clear();
+                    pos += self.stems.div_ceil(8);
+                }
+                // rmoveto
+                21 => {
+                    self.width(n > 2);
+                    let s = self.args(2)?;
+                    self.move_to(s[0], s[1]);
+                }
+                // hmoveto
+                22 => {
+                    self.width(n > 1);
+                    let s = self.args(1)?;
+                    self.move_to(s[0], 0.0);
+                }
+                // vmoveto
+                4 => {
+                    self.width(n > 1);
+                    let s = self.args(1)?;
+                    self.move_to(0.0, s[0]);
+                }
+                // rlineto
+                5 => {
+                    for d in core::mem::take(&mut self.stack).chunks_exact(2) {
+                        self.line_to(d[0], d[1]);
+                    }
+                }
+                // hlineto, vlineto
+                6 | 7 => {
+                    let mut horizontal = b0 == 6;
+                    for d in core::mem::take(&mut self.stack) {
+                        iif![horizontal; self.line_to(d, 0.0); self.line_to(0.0, d)];
+                        horizontal = !horizontal;
+                    }
+                }
+                // rrcurveto
+                8 => {
+                    for d in core::mem::take(&mut self.stack).chunks_exact(6) {
+                        self.curve_to(d[0], d[1], d[2], d[3], d[4], d[5]);
+                    }
+                }
+                // rcurveline
+                24 => {
+                    let s = core::mem::take(&mut self.stack);
+                    iif![s.len() < 8; return Err(malformed())];
+                    let (curves, line) = s.split_at(s.len() - 2);
+                    for d in curves.chunks_exact(6) {
+                        self.curve_to(d[0], d[1], d[2], d[3], d[4], d[5]);
+                    }
+                    self.line_to(line[0], line[1]);
+                }
+                // rlinecurve
+                25 => {
+                    let s = core::mem::take(&mut self.stack);
+                    iif![s.len() < 8; return Err(malformed())];
+                    let (lines, d) = s.split_at(s.len() - 6);
+                    for l in lines.chunks_exact(2) {
+                        self.line_to(l[0], l[1]);
+                    }
+                    self.curve_to(d[0], d[1], d[2], d[3], d[4], d[5]);
+                }
+                // vvcurveto, hhcurveto
+                26 | 27 => {
+                    let s = core::mem::take(&mut self.stack);
+                    let (mut extra, curves) = iif![s.len() % 4 == 1;
+                        (s[0], &s[1..]); (0.0, &s[..])];
+                    for d in curves.chunks_exact(4) {
+                        if b0 == 26 {
+                            self.curve_to(extra, d[0], d[1], d[2], 0.0, d[3]);
+                        } else {
+                            self.curve_to(d[0], extra, d[1], d[2], d[3], 0.0);
+                        }
+                        extra = 0.0;
+                    }
+                }
+                // vhcurveto, hvcurveto
+                30 | 31 => {
+                    let s = core::mem::take(&mut self.stack);
+                    let mut horizontal = b0 == 31;
+                    let mut i = 0;
+                    while i + 4 <= s.len() {
+                        let last = iif![s.len() - i == 5; s[i + 4]; 0.0];
+                        let d = &s[i..i + 4];
+                        if horizontal {
+                            self.curve_to(d[0], 0.0, d[1], d[2], last, d[3]);
+                        } else {
+                            self.curve_to(0.0, d[0], d[1], d[2], d[3], last);
+                        }
+                        horizontal = !horizontal;
+                        i += 4;
+                    }
+                }
+                // callsubr, callgsubr
+                10 | 29 => {
+                    let subrs = iif![b0 == 10; self.local_subrs; self.global_subrs];
+                    let index = (self.stack.pop().ok_or_else(malformed)? as i32)
+                        .saturating_add(subr_bias(subrs.count));
+                    let subr = usize::try_from(index)
+                        .ok()
+                        .and_then(|i| subrs.get(self.t, i))
+                        .ok_or_else(malformed)?;
+                    iif![self.run(subr, depth + 1)?; return Ok(true)];
+                }
+                // return
+                11 => return Ok(false),
+                // endchar
+                14 => {
+                    self.width(n == 1 || n == 5);
+                    iif![self.stack.len() == 4;
+                        return Err(AggFontError::Unsupported("CFF accented characters"))];
+                    return Ok(true);
+                }
+                12 => {
+                    let b1 = *code.get(pos).ok_or_else(malformed)?;
+                    pos += 1;
+                    self.flex(b1)?;
+                }
+                28 => {
+                    let v = i16_at(code, pos).ok_or_else(malformed)?;
+                    pos += 2;
+                    self.push(f64::from(v))?;
+                }
+                32..=246 => self.push(f64::from(i32::from(b0) - 139))?,
+                247..=254 => {
+                    let b1 = i32::from(*code.get(pos).ok_or_else(malformed)?);
+                    pos += 1;
+                    let v = iif![b0 < 251;
+                        (i32::from(b0) - 247) * 256 + b1 + 108;
+                        -(i32::from(b0) - 251) * 256 - b1 - 108];
+                    self.push(f64::from(v))?;
+                }
+                255 => {
+                    let v = u32_at(code, pos).ok_or_else(malformed)? as i32;
+                    pos += 4;
+                    self.push(f64::from(v) / 65536.0)?;
+                }
+                _ => return Err(malformed()),
+            }
+        }
+        Ok(false)
+    }
+
+    /// Handles the flex operators, which are drawn as their two curves.
+    fn flex(&mut self, op: u8) -> Result<(), AggFontError> {
+        let s = core::mem::take(&mut self.stack);
+        let args = |n| iif![s.len() == n; Ok(&s[..]); Err(AggFontError::Malformed("CFF flex"))];
+        match op {
+            // flex
+            35 => {
+                let d = args(13)?;
+                self.curve_to(d[0], d[1], d[2], d[3], d[4], d[5]);
+                self.curve_to(d[6], d[7], d[8], d[9], d[10], d[11]);
+            }
+            // hflex
+            34 => {
+                let d = args(7)?;
+                self.curve_to(d[0], 0.0, d[1], d[2], d[3], 0.0);
+                self.curve_to(d[4], 0.0, d[5], -d[2], d[6], 0.0);
+            }
+            // hflex1
+            36 => {
+                let d = args(9)?;
+                self.curve_to(d[0], d[1], d[2], d[3], d[4], 0.0);
+                self.curve_to(d[5], 0.0, d[6], d[7], d[8], -(d[1] + d[3] + d[7]));
+            }
+            // flex1
+            37 => {
+                let d = args(11)?;
+                let dx = d[0] + d[2] + d[4] + d[6] + d[8];
+                let dy = d[1] + d[3] + d[5] + d[7] + d[9];
+                let (dx6, dy6) = iif![dx.abs() > dy.abs(); (d[10], -dy); (-dx, d[10])];
+                self.curve_to(d[0], d[1], d[2], d[3], d[4], d[5]);
+                self.curve_to(d[6], d[7], d[8], d[9], dx6, dy6);
+            }
+            _ => return Err(AggFontError::Unsupported("CFF charstring operator")),
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, v: f64) -> Result<(), AggFontError> {
+        iif![self.stack.len() >= MAX_CHARSTRING_ARGS;
+            return Err(AggFontError::Malformed("CFF charstring stack"))];
+        self.stack.push(v);
+        Ok(())
+    }
+    /// Takes exactly `n` arguments from the stack.
+    fn args(&mut self, n: usize) -> Result<Vec<f64>, AggFontError> {
+        iif![self.stack.len() != n; return Err(AggFontError::Malformed("CFF charstring"))];
+        Ok(core::mem::take(&mut self.stack))
+    }
+    /// Drops the advance width argument, which only precedes the first
+    /// stack clearing operator.
+    fn width(&mut self, has_width: bool) {
+        if !self.width_done && has_width {
+            self.stack.remove(0);
+        }
+        self.width_done = true;
+    }
+
+    fn point(&self) -> (f64, f64) {
+        self.trans.transform(self.x, self.y)
+    }
+    fn close(&mut self) {
+        iif![self.open; self.path.close_polygon()];
+        self.open = false;
+    }
+    fn move_to(&mut self, dx: f64, dy: f64) {
+        self.close();
+        (self.x, self.y) = (self.x + dx, self.y + dy);
+        let (x, y) = self.point();
+        self.path.move_to(x, y);
+        self.open = true;
+    }
+    fn line_to(&mut self, dx: f64, dy: f64) {
+        (self.x, self.y) = (self.x + dx, self.y + dy);
+        let (x, y) = self.point();
+        self.path.line_to(x, y);
+    }
+    fn curve_to(&mut self, dx1: f64, dy1: f64, dx2: f64, dy2: f64, dx3: f64, dy3: f64) {
+        let (x1, y1) = self.trans.transform(self.x + dx1, self.y + dy1);
+        let (x2, y2) = self.trans.transform(self.x + dx1 + dx2, self.y + dy1 + dy2);
+        (self.x, self.y) = (self.x + dx1 + dx2 + dx3, self.y + dy1 + dy2 + dy3);
+        let (x, y) = self.point();
+        self.path.curve4(x1, y1, x2, y2, x, y);
+    }
+}
+
+/// Returns the bias added to subroutine numbers.
+const fn subr_bias(count: usize) -> i32 {
+    iif![count < 1240; 107; iif![count < 33900; 1131; 32768]]
+}
+
+/* helpers */
+
+fn u16_at(d: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*d.get(at)?, *d.get(at + 1)?]))
+}
+fn i16_at(d: &[u8], at: usize) -> Option<i16> {
+    u16_at(d, at).map(|v| v as i16)
+}
+fn u32_at(d: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(d.get(at..at.checked_add(4)?)?.try_into().ok()?))
+}
+/// Reads a big-endian offset of `size` bytes.
+fn offset_at(d: &[u8], at: usize, size: usize) -> Option<usize> {
+    d.get(at..at.checked_add(size)?)?
+        .iter()
+        .try_fold(0_usize, |v, &b| Some((v << 8) | b as usize))
+}
+
+/// Returns the byte range of the table `tag` of the font starting at `offset`.
+fn find_table(data: &[u8], offset: usize, tag: &str) -> Option<Range<usize>> {
+    let count = u16_at(data, offset + 4)? as usize;
+    (0..count).find_map(|i| {
+        let record = offset + 12 + 16 * i;
+        iif![data.get(record..record + 4)? != tag.as_bytes(); return None];
+        let start = u32_at(data, record + 8)? as usize;
+        let end = start.checked_add(u32_at(data, record + 12)? as usize)?;
+        iif![end <= data.len(); Some(start..end); None]
+    })
+}
+
+/// Selects the first horizontal format 0 subtable of a `kern` table.
+///
+/// Returns the offset of its kerning pairs and their number.
+fn select_kern(data: &[u8], kern: Range<usize>) -> Option<(usize, usize)> {
+    // The Apple table has a 32-bit version 1, the OpenType one a 16-bit version 0
+    let apple = u16_at(data, kern.start)? == 1;
+    let (count, mut sub) = iif![apple;
+        (u32_at(data, kern.start + 4)? as usize, kern.start + 8);
+        (u16_at(data, kern.start + 2)? as usize, kern.start + 4)];
+    for _ in 0..count {
+        let (len, coverage, header) = if apple {
+            (u32_at(data, sub)? as usize, u16_at(data, sub + 4)?, 8)
+        } else {
+            (u16_at(data, sub + 2)? as usize, u16_at(data, sub + 4)?, 6)
+        };
+        let (format, horizontal) = if apple {
+            // Vertical, cross-stream and variation subtables are skipped
+            (coverage & 0xff, coverage & 0xe000 == 0)
+        } else {
+            // Horizontal, without minimum values or cross-stream kerning
+            (coverage >> 8, coverage & 0x07 == 0x01)
+        };
+        if format == 0 && horizontal {
+            let pairs = sub + header + 8;
+            let n_pairs = u16_at(data, sub + header)? as usize;
+            iif![pairs + 6 * n_pairs <= kern.end; return Some((pairs, n_pairs)); return None];
+        }
+        iif![len == 0; return None];
+        sub += len;
+    }
+    None
+}
+
+/// Selects the best supported Unicode subtable of a `cmap` table.
+///
+/// Returns its offset and whether it is a symbol subtable.
+fn select_cmap(data: &[u8], cmap: Range<usize>) -> Option<(usize, bool)> {
+    let count = u16_at(data, cmap.start + 2)? as usize;
+    let mut best = None;
+    let mut best_rank = 0;
+    for i in 0..count {
+        let record = cmap.start + 4 + 8 * i;
+        let (platform, encoding) = (u16_at(data, record)?, u16_at(data, record + 2)?);
+        let sub = cmap.start.checked_add(u32_at(data, record + 4)? as usize)?;
+        let format = u16_at(data, sub)?;
+        iif![!matches!(format, 0 | 4 | 6 | 12); continue];
+        let rank = match (platform, encoding) {
+            (0, _) | (3, 1 | 10) => 6,
+            (3, 0) => 4,
+            (1, 0) => 2,
+            _ => continue,
+        } + usize::from(format == 12);
+        if rank > best_rank {
+            best = Some((sub, platform == 3 && encoding == 0));
+            best_rank = rank;
+        }
+    }
+    best
+}
+
+/// Returns the glyph index of the character code `c` in a `cmap` subtable.
+fn cmap_lookup(data: &[u8], sub: usize, c: u32) -> Option<u16> {
+    match u16_at(data, sub)? {
+        0 => iif![c < 256; data.get(sub + 6 + c as usize).map(|&g| u16::from(g)); None],
+        4 => {
+            iif![c > 0xFFFF; return None];
+            let segments = u16_at(data, sub + 6)? as usize / 2;
+            let ends = sub + 14;
+            let starts = ends + 2 * segments + 2;
+            let deltas = starts + 2 * segments;
+            let range_offsets = deltas + 2 * segments;
+            // The first segment whose end is not below c
+            let (mut lo, mut hi) = (0, segments);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                iif![u32::from(u16_at(data, ends + 2 * mid)?) < c; lo = mid + 1; hi = mid];
+            }
+            iif![lo == segments; return None];
+            let start = u32::from(u16_at(data, starts + 2 * lo)?);
+            iif![c < start; return None];
+            let delta = u16_at(data, deltas + 2 * lo)?;
+            let range_offset = u16_at(data, range_offsets + 2 * lo)? as usize;
+            if range_offset == 0 {
+                return Some((c as u16).wrapping_add(delta));
+            }
+            let at = range_offsets + 2 * lo + range_offset + 2 * (c - start) as usize;
+            let glyph = u16_at(data, at)?;
+            Some(iif![glyph == 0; 0; glyph.wrapping_add(delta)])
+        }
+        6 => {
+            let first = u32::from(u16_at(data, sub + 6)?);
+            let count = u32::from(u16_at(data, sub + 8)?);
+            iif![c < first || c >= first + count; return None];
+            u16_at(data, sub + 10 + 2 * (c - first) as usize)
+        }
+        12 => {
+            let groups = u32_at(data, sub + 12)? as usize;
+            let (mut lo, mut hi) = (0, groups);
+            while lo < hi {
+                let mid = (lo + hi) / 2;
+                let group = sub + 16 + 12 * mid;
+                let (start, end) = (u32_at(data, group)?, u32_at(data, group + 4)?);
+                if c < start {
+                    hi = mid;
+                } else if c > end {
+                    lo = mid + 1;
+                } else {
+                    return u16::try_from(u32_at(data, group + 8)?.checked_add(c - start)?).ok();
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
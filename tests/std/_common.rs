@@ -56,12 +56,8 @@ impl<T: VertexSource> Roundoff<T> {
     }
 }
 impl<T: VertexSource> VertexSource for Roundoff<T> {
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.src
-            .xconvert()
-            .into_iter()
-            .map(|v| Vertex::new(v.x.floor(), v.y.floor(), v.cmd))
-            .collect()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.src.vertices().map(|v| Vertex::new(v.x.floor(), v.y.floor(), v.cmd))
     }
 }
 
@@ -78,8 +74,8 @@ pub struct Spiral {
     dr: f64,
 }
 impl VertexSource for Spiral {
-    fn xconvert(&self) -> Vec<Vertex<f64>> {
-        self.spin_spin_spin()
+    fn vertices(&self) -> impl Iterator<Item = Vertex<f64>> {
+        self.spin_spin_spin().into_iter()
     }
 }
 impl Spiral {
@@ -38,7 +38,7 @@ fn lion_cw_aa_srgba() {
     let t: Vec<Path> = paths.into_iter().map(|p| p.transformed(&mtx)).collect();
     println!("polygons: {}", t.len());
 
-    render_all_paths(&mut ras, &mut ren, &t, &colors);
+    render_all_paths(&mut ras, &mut ren, t.iter().zip(&colors));
 
     ren.to_file("tests/std/tmp/lion_cw_aa_srgba.png").unwrap();
 
@@ -41,7 +41,7 @@ fn lion_outline() {
 
     let mut stroke: Vec<_> = t.into_iter().map(Stroke::new).collect();
     stroke.iter_mut().for_each(|p| p.width(7.0));
-    render_all_paths(&mut ras, &mut ren, &stroke, &colors);
+    render_all_paths(&mut ras, &mut ren, stroke.iter().zip(&colors));
 
     ren.to_file("tests/std/tmp/lion_outline.png").unwrap();
     assert!(img_diff("tests/std/tmp/lion_outline.png", "tests/images/lion_outline.png").unwrap());
@@ -42,7 +42,7 @@ fn lion_outline_width1() {
 
     let mut stroke: Vec<_> = t.into_iter().map(Stroke::new).collect();
     stroke.iter_mut().for_each(|p| p.width(1.0));
-    render_all_paths(&mut ras, &mut ren, &stroke, &colors);
+    render_all_paths(&mut ras, &mut ren, stroke.iter().zip(&colors));
 
     ren.to_file("tests/std/tmp/lion_outline_width1.png").unwrap();
     assert!(img_diff(
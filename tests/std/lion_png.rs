@@ -34,7 +34,7 @@ fn lion_png() {
     mtx.set_translate((w / 2) as f64, (h / 2) as f64);
     let t: Vec<Path> = paths.into_iter().map(|p| p.transformed(&mtx)).collect();
 
-    render_all_paths(&mut ras, &mut ren, &t, &colors);
+    render_all_paths(&mut ras, &mut ren, t.iter().zip(&colors));
 
     ren.to_file("tests/std/tmp/lion.png").unwrap();
 
@@ -35,3 +35,15 @@ mod t21_line_join;
 mod t22_inner_join;
 #[cfg(feature = "freetype")]
 mod t23_font;
+mod t24_outline_font;
+mod t25_text_layout;
+mod t26_raster_text;
+mod t27_var_stroke;
+mod t28_markers;
+mod t29_outline_join;
+mod t30_outline_dash;
+mod t31_multi_path;
+mod t32_extreme_coords;
+mod t33_cell_limit;
+mod t34_scanline_storage;
+mod t35_render_bands;
@@ -1,6 +1,6 @@
 use agrega::{
-    img_diff, DrawOutline, Path, Pixfmt, RasterizerOutlineAA, RendererOutlineAA, RenderingBase,
-    Rgb8,
+    img_diff, DrawOutline, Path, PixelSource, Pixfmt, RasterizerOutlineAA, RendererOutlineAA,
+    RenderingBase, Rgb8,
 };
 
 #[test]
@@ -24,3 +24,24 @@ fn t20_outline_render() {
 
     assert!(img_diff("tests/std/tmp/outline_aa.png", "tests/images/outline_aa.png").unwrap());
 }
+
+#[test]
+fn outline_aa_joined_horizontal() {
+    let pix = Pixfmt::<Rgb8>::new(100, 100);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+    let mut ren = RendererOutlineAA::with_base(&mut ren_base);
+    ren.color(Rgb8::black());
+    ren.width(10.0);
+
+    // the horizontal segment is joined at both ends
+    let mut path = Path::new();
+    path.move_to(10.0, 10.0);
+    path.line_to(20.0, 60.0);
+    path.line_to(80.0, 60.0);
+    path.line_to(90.0, 10.0);
+
+    let mut ras = RasterizerOutlineAA::with_renderer(&mut ren);
+    ras.add_path(&path);
+    assert_eq!(Rgb8::from_trait(ren_base.pixf.get((50, 60))), Rgb8::black());
+}
@@ -1,5 +1,8 @@
 use agrega::{
-    Label, Pixfmt, RenderingBase, Rgb8, Rgba8, XAlign, YAlign, _dep::freetype::Library, draw_text,
+    _dep::freetype::{Face, Library},
+    draw_text, string_width, string_width_cached, text_metrics, AggFontError, FontChain,
+    GlyphCache, GlyphData, GlyphRender, Label, LcdFilter, MissingGlyph, Pixfmt, RenderingBase,
+    Rgb8, Rgba8, SubpixelOrder, XAlign, YAlign,
 };
 
 #[test]
@@ -12,13 +15,13 @@ fn t23_font() {
     let mut ren_base = RenderingBase::new(pix);
     ren_base.clear(Rgb8::white());
 
-    draw_text("Hello World!!!", 50, 45, Rgba8::black(), &font, &mut ren_base);
+    draw_text("Hello World!!!", 50, 45, Rgba8::black(), &font, &mut ren_base).unwrap();
 
     let mut label = Label::new("Hello World!!!", 50., 58., 13.0, &font)
         .unwrap()
         .xalign(XAlign::Center)
         .yalign(YAlign::Center);
-    label.draw_subpixel(&mut ren_base);
+    label.draw_subpixel(&mut ren_base).unwrap();
 
     ren_base.blend_hline(50, 57, 50, Rgb8::new(255, 0, 0), 255);
 
@@ -26,3 +29,234 @@ fn t23_font() {
     // FIXME: images differ slightly, this font is rendered taller
     // assert!(img_diff("tests/std/tmp/font.png", "tests/images/font.png").unwrap());
 }
+
+#[test]
+fn t23_font_cache() {
+    let lib = Library::init().unwrap();
+    let font = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+    let cache = GlyphCache::default();
+
+    let draw = |cache: Option<&GlyphCache>| {
+        let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 100));
+        ren_base.clear(Rgb8::white());
+        let mut label = Label::new("Hello World!!!", 50., 58., 13.0, &font).unwrap();
+        if let Some(cache) = cache {
+            label = label.cache(cache);
+        }
+        label.draw(&mut ren_base).unwrap();
+        label.draw_subpixel(&mut ren_base).unwrap();
+        ren_base.as_bytes().to_vec()
+    };
+    let uncached = draw(None);
+    assert_eq!(draw(Some(&cache)), uncached);
+    let len = cache.len();
+    assert!(len > 0 && cache.memory_usage() > 0);
+    // Drawing again reuses the cached glyphs
+    assert_eq!(draw(Some(&cache)), uncached);
+    assert_eq!(cache.len(), len);
+
+    // Other sizes and render modes are cached apart
+    let glyph = font.get_char_index('H' as usize).unwrap();
+    let outline = cache.ft_glyph(&font, glyph, GlyphRender::Outline, false).unwrap();
+    assert!(matches!(&outline.data, GlyphData::Outline(path) if !path.vertices.is_empty()));
+    font.set_char_size(20 * 64, 0, 72, 0).unwrap();
+    let _ = cache.ft_glyph(&font, glyph, GlyphRender::Outline, false).unwrap();
+    assert_eq!(cache.len(), len + 2);
+
+    // Measuring loads the glyphs without rendering them
+    let cache = GlyphCache::default();
+    let width = string_width_cached("Hello", &FontChain::new(&font), &cache).unwrap();
+    assert_eq!(width, string_width("Hello", &font).unwrap());
+    assert_eq!(cache.len(), 4);
+    let h = cache.ft_glyph(&font, glyph, GlyphRender::Advance, true).unwrap();
+    assert!(matches!(h.data, GlyphData::Empty));
+    assert_eq!(cache.len(), 4);
+
+    // And the same font loaded twice is cached apart
+    let twin = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+    twin.set_char_size(20 * 64, 0, 72, 0).unwrap();
+    let _ = cache.ft_glyph(&twin, glyph, GlyphRender::Advance, true).unwrap();
+    assert_eq!(cache.len(), 5);
+
+    // Even when a face is loaded where a dropped one was
+    let bitmap = |cache: &GlyphCache, face: &Face| match &cache
+        .ft_glyph(face, glyph, GlyphRender::Gray8, true)
+        .unwrap()
+        .data
+    {
+        GlyphData::Gray8(bitmap) => bitmap.clone(),
+        _ => unreachable!(),
+    };
+    for index in [0, 1, 0, 1] {
+        let face = lib.new_face("tests/std/assets/Helvetica.ttc", index).unwrap();
+        face.set_char_size(20 * 64, 0, 72, 0).unwrap();
+        assert_eq!(bitmap(&cache, &face), bitmap(&GlyphCache::new(0), &face));
+    }
+}
+
+#[test]
+fn t23_font_metrics() {
+    let lib = Library::init().unwrap();
+    let font = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+
+    let label = Label::new("Hello, gyp", 0., 0., 13.0, &font).unwrap();
+    let m = label.metrics().unwrap();
+    assert!(m.ascent > 0.0 && m.descent < 0.0);
+    assert_eq!(m.line_height(), font.size_metrics().unwrap().height as f64 / 64.0);
+    assert_eq!(label.size().unwrap(), (m.advance, m.height()));
+    assert_eq!(m.advance, string_width("Hello, gyp", &font).unwrap());
+    let ink = m.ink.unwrap();
+    assert!(ink.x1() >= 0.0 && ink.x2() <= m.advance + 1.0);
+    assert!(ink.y1() < 0.0 && ink.y1() >= m.descent.floor() && ink.y2() <= m.ascent.ceil());
+    assert!(text_metrics("   ", &font).unwrap().ink.is_none());
+
+    // Returns the rows and columns of the drawn pixels, aligned at (50, 50)
+    let bounds = |xalign, yalign, subpixel| {
+        let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 100));
+        ren_base.clear(Rgb8::white());
+        let mut label = Label::new("Hello, gyp", 50., 50., 13.0, &font)
+            .unwrap()
+            .xalign(xalign)
+            .yalign(yalign);
+        if subpixel {
+            label.draw_subpixel(&mut ren_base).unwrap();
+        } else {
+            label.draw(&mut ren_base).unwrap();
+        }
+        let inked: Vec<_> = ren_base
+            .as_bytes()
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, p)| p != &[255, 255, 255])
+            .map(|(i, _)| (i as i64 % 100, i as i64 / 100))
+            .collect();
+        let cols = inked.iter().map(|p| p.0);
+        let rows = inked.iter().map(|p| p.1);
+        (
+            cols.clone().min().unwrap(),
+            cols.max().unwrap(),
+            rows.clone().min().unwrap(),
+            rows.max().unwrap(),
+        )
+    };
+    for subpixel in [true, false] {
+        let (x1, _, y1, _) = bounds(XAlign::Left, YAlign::Top, subpixel);
+        assert!(x1 >= 50 && y1 >= 50 && y1 <= 50 + (m.ascent - ink.y2()).ceil() as i64);
+        let (_, x2, _, y2) = bounds(XAlign::Right, YAlign::Bottom, subpixel);
+        assert!(x2 < 50 && y2 < 50 && y2 >= 50 - (ink.y1() - m.descent).ceil() as i64 - 1);
+        let (_, _, _, y2) = bounds(XAlign::Center, YAlign::Baseline, subpixel);
+        assert_eq!(y2, 50 - ink.y1() as i64 - 1);
+        let (x1, x2, y1, y2) = bounds(XAlign::Center, YAlign::Center, subpixel);
+        assert!((x1 + x2 - 100).abs() <= 2);
+        assert!(y1 < 50 && y2 > 50);
+    }
+}
+
+#[test]
+fn t23_font_fallback() {
+    let lib = Library::init().unwrap();
+    let light = lib.new_face("tests/std/assets/Helvetica.ttc", 4).unwrap();
+    let regular = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+    light.set_char_size(13 * 64, 0, 72, 0).unwrap();
+    regular.set_char_size(13 * 64, 0, 72, 0).unwrap();
+    let cache = GlyphCache::default();
+
+    // Missing characters are errors by default, and nothing is drawn
+    assert!(matches!(string_width("aƂ", &light), Err(AggFontError::GlyphNotFound('Ƃ'))));
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 100));
+    ren_base.clear(Rgb8::white());
+    assert!(draw_text("aƂ", 50, 50, Rgba8::black(), &light, &mut ren_base).is_err());
+    assert!(ren_base.as_bytes().iter().all(|&v| v == 255));
+
+    // The fallback faces are searched in order
+    let fonts = FontChain::new(&light).fallback(&regular);
+    let (face, _) = fonts.glyph('a').unwrap();
+    assert!(std::ptr::eq(face, &light));
+    let (face, _) = fonts.glyph('Ƃ').unwrap();
+    assert!(std::ptr::eq(face, &regular));
+    let width = string_width_cached("aƂ", &fonts, &cache).unwrap();
+    let expected = string_width("a", &light).unwrap() + string_width("Ƃ", &regular).unwrap();
+    assert_eq!(width, expected);
+    assert!(matches!(fonts.glyph('✓'), Err(AggFontError::GlyphNotFound('✓'))));
+
+    // Or replaced
+    let fonts = fonts.missing_glyph(MissingGlyph::Notdef);
+    assert_eq!(fonts.glyph('✓').unwrap(), (&light, 0));
+    let fonts = fonts.missing_glyph(MissingGlyph::Replace('?'));
+    assert_eq!(fonts.glyph('✓').unwrap(), fonts.glyph('?').unwrap());
+    let width = string_width_cached("a✓", &fonts, &cache).unwrap();
+    assert_eq!(width, string_width("a?", &light).unwrap());
+
+    // Labels set the size of their fallback faces
+    let mut label = Label::new("aƂ✓", 50., 50., 20.0, &light)
+        .unwrap()
+        .fallback(&regular)
+        .unwrap()
+        .missing_glyph(MissingGlyph::Notdef);
+    assert_eq!(regular.size_metrics().unwrap().y_ppem, 20);
+    label.draw_subpixel(&mut ren_base).unwrap();
+    assert!(ren_base.as_bytes().iter().any(|&v| v != 255));
+}
+
+#[test]
+fn t23_font_lcd() {
+    let lib = Library::init().unwrap();
+    let font = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+    let cache = GlyphCache::default();
+
+    let draw = |order, filter| {
+        let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 30));
+        ren_base.clear(Rgb8::white());
+        let mut label = Label::new("Hello World!!!", 50., 15., 13.0, &font)
+            .unwrap()
+            .cache(&cache)
+            .xalign(XAlign::Center)
+            .yalign(YAlign::Center)
+            .subpixel_order(order)
+            .lcd_filter(filter);
+        label.draw_lcd(&mut ren_base).unwrap();
+        ren_base
+    };
+    let rgb = draw(SubpixelOrder::Rgb, LcdFilter::default());
+    rgb.to_file("tests/std/tmp/font_lcd.png").unwrap();
+    let pixels: Vec<_> = rgb.as_bytes().chunks(3).collect();
+    assert!(pixels.iter().any(|p| p[0] != p[2]), "no color fringes");
+
+    // The channels are swapped for BGR screens
+    let bgr = draw(SubpixelOrder::Bgr, LcdFilter::default());
+    let reds = rgb.as_bytes().iter().step_by(3);
+    let blues = bgr.as_bytes().iter().skip(2).step_by(3);
+    assert!(reds.eq(blues));
+    assert_ne!(draw(SubpixelOrder::Rgb, LcdFilter::NONE).as_bytes(), rgb.as_bytes());
+
+    // The text is centered like the grayscale one
+    let columns = |bytes: &[u8]| {
+        let inked: Vec<_> = bytes
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, p)| p != &[255, 255, 255])
+            .map(|(i, _)| i as i64 % 100)
+            .collect();
+        (*inked.iter().min().unwrap(), *inked.iter().max().unwrap())
+    };
+    let mut gray = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 30));
+    gray.clear(Rgb8::white());
+    let mut label = Label::new("Hello World!!!", 50., 15., 13.0, &font)
+        .unwrap()
+        .xalign(XAlign::Center)
+        .yalign(YAlign::Center);
+    label.draw_subpixel(&mut gray).unwrap();
+    let ((x1, x2), (g1, g2)) = (columns(rgb.as_bytes()), columns(gray.as_bytes()));
+    assert!((x1 - g1).abs() <= 1 && (x2 - g2).abs() <= 1, "{x1} {x2} {g1} {g2}");
+
+    // Transparent buffers get the alpha of the most covered channel
+    let mut rgba = RenderingBase::new(Pixfmt::<Rgba8>::new(100, 30));
+    rgba.clear(Rgba8::new(255, 255, 255, 0));
+    let mut label = Label::new("Hello World!!!", 50., 15., 13.0, &font)
+        .unwrap()
+        .xalign(XAlign::Center)
+        .yalign(YAlign::Center);
+    label.draw_lcd(&mut rgba).unwrap();
+    assert!(rgba.as_bytes().chunks(4).any(|p| p[3] == 255));
+    assert!(rgba.as_bytes().chunks(4).all(|p| p[3] > 0 || p[..3] == [255, 255, 255]));
+}
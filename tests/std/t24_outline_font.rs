@@ -0,0 +1,39 @@
+use agrega::{
+    img_diff, render_scanlines, Curve, OutlineFont, OutlineText, Pixfmt, RasterizerScanline,
+    Render, RenderingBase, RenderingScanlineAASolid, Rgb8, Stroke,
+};
+
+#[test]
+fn t24_outline_font() {
+    let data = std::fs::read("tests/std/assets/Helvetica.ttc").unwrap();
+    let font = OutlineFont::from_collection(data, 0).unwrap();
+
+    let pix = Pixfmt::<Rgb8>::new(240, 120);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+    let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+    let mut ras = RasterizerScanline::new();
+
+    // Filled text
+    let mut text = OutlineText::new(&font);
+    text.size(28.0, 0.0);
+    text.start_point(10.0, 40.0);
+    text.flip(true);
+    text.text("Hello World");
+    ras.add_path(&Curve::new(&text));
+    ren.color(Rgb8::black());
+    render_scanlines(&mut ras, &mut ren);
+
+    // Stretched and stroked text, over two lines
+    text.size(24.0, 36.0);
+    text.start_point(10.0, 75.0);
+    text.text("agrega\nqjÉ");
+    let mut stroke = Stroke::new(Curve::new(&text));
+    stroke.width(1.0);
+    ras.add_path(&stroke);
+    ren.color(Rgb8::new(0, 0, 160));
+    render_scanlines(&mut ras, &mut ren);
+
+    ren_base.to_file("tests/std/tmp/outline_font.png").unwrap();
+    assert!(img_diff("tests/std/tmp/outline_font.png", "tests/images/outline_font.png").unwrap());
+}
@@ -0,0 +1,80 @@
+use agrega::{
+    img_diff, render_scanlines, GsvLayout, GsvText, Overflow, Pixfmt, RasterizerScanline, Render,
+    RenderingBase, RenderingScanlineAASolid, Rgb8, Stroke, TextLayout, XAlign,
+};
+
+const TEXT: &str =
+    "The quick brown fox jumps over the lazy dog.\nPack my box with five dozen liquor jugs.";
+
+#[test]
+fn t25_text_layout() {
+    let pix = Pixfmt::<Rgb8>::new(240, 200);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+    let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+    let mut ras = RasterizerScanline::new();
+
+    let mut font = GsvText::new();
+    font.size(8.0, 0.0);
+    let layouts = [
+        (XAlign::Left, Overflow::Visible, 10.0, 10.0),
+        (XAlign::Center, Overflow::Ellipsis, 120.0, 80.0),
+        (XAlign::Right, Overflow::Clip, 230.0, 150.0),
+    ];
+    for (xalign, overflow, x, y) in layouts {
+        let runs = TextLayout::new(TEXT)
+            .position(x, y)
+            .max_width(150.0)
+            .max_lines(4)
+            .xalign(xalign)
+            .line_space(3.0)
+            .overflow(overflow)
+            .layout(&font);
+        assert!(runs.len() <= 4);
+        let mut stroke = Stroke::new(GsvLayout::new(&font, &runs));
+        stroke.width(0.7);
+        ras.add_path(&stroke);
+    }
+    ren.color(Rgb8::black());
+    render_scanlines(&mut ras, &mut ren);
+
+    ren_base.to_file("tests/std/tmp/text_layout.png").unwrap();
+    assert!(img_diff("tests/std/tmp/text_layout.png", "tests/images/text_layout.png").unwrap());
+}
+
+#[cfg(feature = "freetype")]
+#[test]
+fn t25_text_layout_freetype() {
+    use agrega::{_dep::freetype::Library, draw_layout, FtFont, GlyphCache, Rgba8, YAlign};
+
+    let lib = Library::init().unwrap();
+    let face = lib.new_face("tests/std/assets/Helvetica.ttc", 0).unwrap();
+    face.set_char_size(11 * 64, 0, 72, 0).unwrap();
+    let cache = GlyphCache::default();
+    let font = FtFont::new(&face, &cache);
+
+    let runs = TextLayout::new(TEXT)
+        .position(120.0, 100.0)
+        .max_width(200.0)
+        .xalign(XAlign::Center)
+        .yalign(YAlign::Center)
+        .overflow(Overflow::Clip)
+        .layout(&font);
+    assert_eq!(runs.len(), 3);
+    assert!(runs.iter().all(|run| run.width <= 200.0 && run.x >= 20.0));
+
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(240, 200));
+    ren_base.clear(Rgb8::white());
+    draw_layout(&runs, Rgba8::black(), &font, &mut ren_base).unwrap();
+    ren_base.to_file("tests/std/tmp/text_layout_freetype.png").unwrap();
+
+    // The text is centered vertically around y = 100
+    let rows: Vec<_> = ren_base
+        .as_bytes()
+        .chunks(240 * 3)
+        .map(|row| row.iter().any(|&v| v != 255))
+        .collect();
+    let first = rows.iter().position(|&inked| inked).unwrap() as f64;
+    let last = rows.iter().rposition(|&inked| inked).unwrap() as f64;
+    assert!((first + last - 200.0).abs() <= 4.0, "{first} {last}");
+}
@@ -0,0 +1,41 @@
+use agrega::{
+    img_diff, GlyphRasterBin, Pixfmt, RasterFonts, RendererRasterHTextSolid,
+    RendererRasterVTextSolid, RenderingBase, Rgb8, Rgba8,
+};
+
+#[test]
+fn t26_raster_text() {
+    let pix = Pixfmt::<Rgb8>::new(330, 120);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+
+    let fonts = [
+        RasterFonts::MONO_4X6,
+        RasterFonts::MONO_6X9,
+        RasterFonts::MONO_7X9_BOLD,
+        RasterFonts::SANS_9,
+    ];
+    let mut ren =
+        RendererRasterHTextSolid::with_base(&mut ren_base, GlyphRasterBin::new(fonts[0]).unwrap());
+    let mut y = 4.0;
+    for font in fonts {
+        let font = GlyphRasterBin::new(font).unwrap();
+        ren.font(font);
+        y += font.height() as f64;
+        ren.render_text(4.0, y, " !\"#$%&'()*+,-./0123456789:;<=>?@");
+        y += font.height() as f64 + 1.0;
+        ren.render_text(4.0, y, "The quick brown fox jumps over the lazy dog.");
+        y += 3.0;
+    }
+    // Blended colors, and text clipped at the edges
+    ren.color(Rgba8::new(255, 0, 0, 128));
+    ren.render_text(300.0, 112.0, "Clipped");
+
+    let mut ren =
+        RendererRasterVTextSolid::with_base(&mut ren_base, GlyphRasterBin::new(fonts[1]).unwrap());
+    ren.color(Rgb8::new(0, 0, 255));
+    ren.render_text(318.0, 4.0, "Vertical");
+
+    ren_base.to_file("tests/std/tmp/raster_text.png").unwrap();
+    assert!(img_diff("tests/std/tmp/raster_text.png", "tests/images/raster_text.png").unwrap());
+}
@@ -0,0 +1,54 @@
+use super::text_h12 as text;
+use agrega::{
+    img_diff, render_scanlines, Curve, LineCap, LineJoin, Path, Pixfmt, RasterizerScanline,
+    RenderingBase, RenderingScanlineAASolid, Rgb8, VarStroke,
+};
+
+#[test]
+fn t27_var_stroke() {
+    let pix = Pixfmt::<Rgb8>::new(300, 120);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+    let mut ras = RasterizerScanline::new();
+
+    // A width per vertex, with round joins and caps
+    let mut zigzag = Path::new();
+    zigzag.move_to(15.0, 80.0);
+    zigzag.line_to(35.0, 25.0);
+    zigzag.line_to(55.0, 80.0);
+    zigzag.line_to(75.0, 25.0);
+    zigzag.line_to(85.0, 60.0);
+    let mut stroke = VarStroke::new(&zigzag, vec![2.0, 16.0, 6.0, 20.0, 2.0]);
+    stroke.line_join(LineJoin::Round);
+    stroke.line_cap(LineCap::Round);
+    ras.add_path(&stroke);
+
+    // A brush stroke along a curve, tapering at the ends
+    let mut curve = Path::new();
+    curve.move_to(110.0, 80.0);
+    curve.curve4(120.0, 0.0, 180.0, 110.0, 190.0, 25.0);
+    let curve = Curve::new(curve);
+    let mut brush =
+        VarStroke::new(&curve, |d: f64, len: f64| 6.0 + 10.0 * (d / len * 6.0).sin().abs());
+    brush.taper(30.0, 50.0);
+    ras.add_path(&brush);
+
+    // A closed polygon, thicker at the bottom, with miter and bevel joins
+    let mut triangle = Path::new();
+    triangle.move_to(220.0, 80.0);
+    triangle.line_to(250.0, 20.0);
+    triangle.line_to(280.0, 80.0);
+    triangle.close_polygon();
+    let mut ring = VarStroke::new(&triangle, vec![10.0, 2.0, 10.0]);
+    ring.line_join(LineJoin::Bevel);
+    ras.add_path(&ring);
+
+    let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+    render_scanlines(&mut ras, &mut ren);
+    text(&mut ras, &mut ren, 25.0, 110.0, "Vertices");
+    text(&mut ras, &mut ren, 120.0, 110.0, "Distance");
+    text(&mut ras, &mut ren, 230.0, 110.0, "Closed");
+
+    ren_base.to_file("tests/std/tmp/var_stroke.png").unwrap();
+    assert!(img_diff("tests/std/tmp/var_stroke.png", "tests/images/var_stroke.png").unwrap());
+}
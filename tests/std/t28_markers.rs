@@ -0,0 +1,52 @@
+use agrega::{img_diff, Marker, Pixfmt, RendererOutline, RenderingBase, Rgb8};
+
+#[test]
+fn t28_markers() {
+    let pix = Pixfmt::<Rgb8>::new(300, 120);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+
+    let mut ren = RendererOutline::with_base(&mut ren_base);
+    ren.line_color(Rgb8::black());
+    ren.fill_color(Rgb8::new(80, 160, 230));
+
+    // Primitives
+    ren.rectangle(10, 10, 40, 30);
+    ren.solid_rectangle(50, 10, 80, 30);
+    ren.outlined_rectangle(90, 10, 120, 30);
+    ren.ellipse(155, 20, 20, 10);
+    ren.solid_ellipse(205, 20, 20, 10);
+    ren.outlined_ellipse(255, 20, 20, 10);
+
+    // Markers, in two rows
+    let markers = [
+        Marker::Square,
+        Marker::Diamond,
+        Marker::Circle,
+        Marker::CrossedCircle,
+        Marker::SemiEllipseLeft,
+        Marker::SemiEllipseRight,
+        Marker::SemiEllipseUp,
+        Marker::SemiEllipseDown,
+        Marker::TriangleLeft,
+        Marker::TriangleRight,
+        Marker::TriangleUp,
+        Marker::TriangleDown,
+        Marker::FourRays,
+        Marker::Cross,
+        Marker::X,
+        Marker::Dash,
+        Marker::Dot,
+        Marker::Pixel,
+    ];
+    for (i, marker) in markers.into_iter().enumerate() {
+        let (x, y) = (20 + 30 * (i as i64 % 9), 55 + 35 * (i as i64 / 9));
+        ren.marker(x, y, 10, marker);
+    }
+
+    // Markers partly or fully outside the image
+    ren.markers(&[(-5, 110), (305, 110), (150, 200)], 8, Marker::Circle);
+
+    ren_base.to_file("tests/std/tmp/markers.png").unwrap();
+    assert!(img_diff("tests/std/tmp/markers.png", "tests/images/markers.png").unwrap());
+}
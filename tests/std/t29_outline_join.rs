@@ -0,0 +1,44 @@
+use super::text_h12 as text;
+use agrega::{
+    img_diff, DrawOutline, LineJoin, Path, Pixfmt, RasterizerOutlineAA, RasterizerScanline,
+    RendererOutlineAA, RenderingBase, RenderingScanlineAASolid, Rgb8,
+};
+
+#[test]
+fn t29_outline_join() {
+    let pix = Pixfmt::<Rgb8>::new(400, 200);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+
+    let joins = [LineJoin::None, LineJoin::Miter, LineJoin::Round, LineJoin::MiterAccurate];
+    for (i, join) in joins.iter().enumerate() {
+        let dx = 100.0 * i as f64;
+        let mut path = Path::new();
+        // an open polyline
+        path.move_to(15.0 + dx, 70.0);
+        path.line_to(35.0 + dx, 20.0);
+        path.line_to(55.0 + dx, 60.0);
+        path.line_to(85.0 + dx, 25.0);
+        // a closed polygon
+        path.move_to(20.0 + dx, 160.0);
+        path.line_to(50.0 + dx, 100.0);
+        path.line_to(80.0 + dx, 160.0);
+        path.close_polygon();
+
+        let mut ren = RendererOutlineAA::with_base(&mut ren_base);
+        ren.color(Rgb8::new(0, 0, 128));
+        ren.width(10.0);
+        let mut ras = RasterizerOutlineAA::with_renderer(&mut ren);
+        ras.line_join(*join);
+        ras.add_path(&path);
+    }
+    let mut ras = RasterizerScanline::new();
+    let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+    text(&mut ras, &mut ren, 35.0, 190.0, "None");
+    text(&mut ras, &mut ren, 130.0, 190.0, "Miter");
+    text(&mut ras, &mut ren, 228.0, 190.0, "Round");
+    text(&mut ras, &mut ren, 320.0, 190.0, "Accurate");
+
+    ren_base.to_file("tests/std/tmp/outline_join.png").unwrap();
+    assert!(img_diff("tests/std/tmp/outline_join.png", "tests/images/outline_join.png").unwrap());
+}
@@ -0,0 +1,69 @@
+use agrega::{
+    img_diff, DrawOutline, LineImagePatternPow2, Path, PatternFilterBilinear, Pixel, Pixfmt,
+    RasterizerOutlineAA, RendererOutlineAA, RendererOutlineImg, RenderingBase, Rgb8, Rgba32,
+};
+
+/// A pattern of two stripes, darker along the length to show its continuity.
+fn stripes() -> Pixfmt<Rgba32> {
+    let mut pix = Pixfmt::<Rgba32>::new(16, 6);
+    for x in 0..16 {
+        let v = x as f32 / 32.0;
+        for y in 0..6 {
+            let c = if y < 3 {
+                Rgba32::new(0.9 - v, 0.1, 0.1, 1.0)
+            } else {
+                Rgba32::new(0.1, 0.1, 0.9 - v, 1.0)
+            };
+            pix.set((x, y), c);
+        }
+    }
+    pix
+}
+
+#[test]
+fn t30_outline_dash() {
+    let pix = Pixfmt::<Rgb8>::new(300, 150);
+    let mut ren_base = RenderingBase::new(pix);
+    ren_base.clear(Rgb8::white());
+
+    let mut zigzag = Path::new();
+    zigzag.move_to(10.0, 60.0);
+    zigzag.line_to(50.0, 15.0);
+    zigzag.line_to(90.0, 60.0);
+    zigzag.line_to(130.0, 15.0);
+    let mut rect = Path::new();
+    rect.move_to(15.0, 85.0);
+    rect.line_to(125.0, 85.0);
+    rect.line_to(125.0, 135.0);
+    rect.line_to(15.0, 135.0);
+    rect.close_polygon();
+    {
+        let mut ren = RendererOutlineAA::with_base(&mut ren_base);
+        ren.color(Rgb8::new(0, 96, 0));
+        ren.width(5.0);
+        let mut ras = RasterizerOutlineAA::with_renderer(&mut ren);
+        ras.round_cap(true);
+        ras.add_dash(14.0, 8.0);
+        ras.add_path(&zigzag);
+        ras.remove_all_dashes();
+        ras.add_dash(20.0, 6.0);
+        ras.add_dash(3.0, 6.0);
+        ras.dash_start(10.0);
+        ras.add_path(&rect);
+    }
+    {
+        let mut pattern = LineImagePatternPow2::new(PatternFilterBilinear::new());
+        pattern.create(&stripes());
+        let mut ren = RendererOutlineImg::with_base_and_pattern(&mut ren_base, pattern);
+        let mut ras = RasterizerOutlineAA::with_renderer(&mut ren);
+        ras.add_dash(30.0, 10.0);
+        let mut triangle = Path::new();
+        triangle.move_to(170.0, 130.0);
+        triangle.line_to(230.0, 20.0);
+        triangle.line_to(290.0, 130.0);
+        triangle.close_polygon();
+        ras.add_path(&triangle);
+    }
+    ren_base.to_file("tests/std/tmp/outline_dash.png").unwrap();
+    assert!(img_diff("tests/std/tmp/outline_dash.png", "tests/images/outline_dash.png").unwrap());
+}
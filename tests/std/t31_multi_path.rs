@@ -0,0 +1,78 @@
+use agrega::{
+    render_all_paths, render_scanlines, Path, PathCommand, PixelSource, Pixfmt, RasterizerScanline,
+    Render, RenderingBase, RenderingScanlineAASolid, Rgb8, Vertex,
+};
+
+fn square(path: &mut Path, x: f64, y: f64) {
+    path.move_to(x, y);
+    path.line_to(x + 10.0, y);
+    path.line_to(x + 10.0, y + 10.0);
+    path.line_to(x, y + 10.0);
+    path.close_polygon();
+}
+
+#[test]
+fn t31_multi_path() {
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(40, 20));
+    ren_base.clear(Rgb8::white());
+    let (black, white, red) = (Rgb8::black(), Rgb8::white(), Rgb8::new(255, 0, 0));
+
+    // paths added after a sweep are kept with add_path_to, but not with add_path
+    let mut ras = RasterizerScanline::new();
+    let (mut a, mut b, mut c) = (Path::new(), Path::new(), Path::new());
+    square(&mut a, 0.0, 0.0);
+    square(&mut b, 10.0, 0.0);
+    square(&mut c, 20.0, 0.0);
+    {
+        let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+        ren.color(red);
+        ras.add_path(&a);
+        render_scanlines(&mut ras, &mut ren);
+        ren.color(black);
+        ras.add_path_to(&b);
+        render_scanlines(&mut ras, &mut ren);
+        ren.color(red);
+        ras.add_path(&c);
+        render_scanlines(&mut ras, &mut ren);
+    }
+    let pix =
+        |ren_base: &RenderingBase<Pixfmt<Rgb8>>, x, y| Rgb8::from_trait(ren_base.pixf.get((x, y)));
+    assert_eq!(pix(&ren_base, 5, 5), black);
+    assert_eq!(pix(&ren_base, 15, 5), black);
+    assert_eq!(pix(&ren_base, 25, 5), red);
+
+    // a stop command ends the path
+    ren_base.clear(white);
+    let mut stopped = a.clone();
+    stopped.vertices.push(Vertex::new(0.0, 0.0, PathCommand::Stop));
+    square(&mut stopped, 10.0, 0.0);
+    {
+        let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+        ren.color(black);
+        ras.add_path(&stopped);
+        render_scanlines(&mut ras, &mut ren);
+    }
+    assert_eq!(pix(&ren_base, 5, 5), black);
+    assert_eq!(pix(&ren_base, 15, 5), white);
+
+    // paths of a storage selected by id, with their colors
+    ren_base.clear(white);
+    let mut storage = Path::new();
+    let ids: Vec<_> = (0..4)
+        .map(|i| {
+            let id = storage.start_new_path();
+            square(&mut storage, 10.0 * i as f64, 10.0);
+            id
+        })
+        .collect();
+    let colors = [red, black, red, black];
+    {
+        let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+        let paths = ids.iter().map(|&id| storage.select(id));
+        render_all_paths(&mut ras, &mut ren, paths.zip(&colors));
+    }
+    for (i, color) in colors.iter().enumerate() {
+        assert_eq!(pix(&ren_base, 10 * i + 5, 15), *color);
+    }
+    assert_eq!(pix(&ren_base, 5, 5), white);
+}
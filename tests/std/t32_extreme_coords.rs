@@ -0,0 +1,104 @@
+use agrega::{
+    render_scanlines, Path, PixelSource, Pixfmt, RasterizerScanline, Render, RenderingBase,
+    RenderingScanlineAASolid, Rgb8, CELL_BLOCK_SIZE, POLY_MAX_COORD,
+};
+
+fn fill(ren_base: &mut RenderingBase<Pixfmt<Rgb8>>, ras: &mut RasterizerScanline, path: &Path) {
+    let mut ren = RenderingScanlineAASolid::with_base(ren_base);
+    ren.color(Rgb8::black());
+    ras.add_path(path);
+    render_scanlines(ras, &mut ren);
+}
+
+#[test]
+fn t32_extreme_coords() {
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(100, 100));
+    let pix =
+        |ren_base: &RenderingBase<Pixfmt<Rgb8>>, x, y| Rgb8::from_trait(ren_base.pixf.get((x, y)));
+    let (black, white) = (Rgb8::black(), Rgb8::white());
+    let mut ras = RasterizerScanline::new();
+
+    // edges longer than 16384 pixels are split once, and not also drawn whole
+    ren_base.clear(white);
+    let mut path = Path::new();
+    path.move_to(-20000.0, 0.0);
+    path.line_to(20000.0, 100.0);
+    path.line_to(-20000.0, 100.0);
+    path.close_polygon();
+    fill(&mut ren_base, &mut ras, &path);
+    assert_eq!(pix(&ren_base, 50, 50), Rgb8::new(31, 31, 31));
+    assert_eq!(pix(&ren_base, 50, 25), white);
+    assert_eq!(pix(&ren_base, 10, 90), black);
+
+    // non-finite vertices are dropped
+    ren_base.clear(white);
+    let mut path = Path::new();
+    path.move_to(f64::NAN, 0.0);
+    path.line_to(10.0, 10.0);
+    path.line_to(30.0, 10.0);
+    path.line_to(f64::INFINITY, 20.0);
+    path.line_to(30.0, 30.0);
+    path.line_to(10.0, f64::NEG_INFINITY);
+    path.line_to(10.0, 30.0);
+    path.close_polygon();
+    fill(&mut ren_base, &mut ras, &path);
+    assert_eq!(ras.dropped_vertices(), 3);
+    assert_eq!(pix(&ren_base, 20, 20), black);
+    assert_eq!(pix(&ren_base, 40, 20), white);
+    ras.reset();
+    assert_eq!(ras.dropped_vertices(), 0);
+
+    // huge coordinates are clipped before converting them into subpixels
+    ren_base.clear(white);
+    ras.clip_box(0.0, 0.0, 100.0, 100.0);
+    let mut path = Path::new();
+    path.move_to(-1e300, -1e300);
+    path.line_to(1e300, -1e300);
+    path.line_to(1e300, 1e300);
+    path.close_polygon();
+    fill(&mut ren_base, &mut ras, &path);
+    ren_base.clear(white);
+    let mut path = Path::new();
+    path.move_to(-1e12, -1e12);
+    path.line_to(1e12, -1e12);
+    path.line_to(1e12, 1e12);
+    path.close_polygon();
+    fill(&mut ren_base, &mut ras, &path);
+    assert_eq!(pix(&ren_base, 90, 10), black);
+    assert_eq!(pix(&ren_base, 99, 0), black);
+    assert_eq!(pix(&ren_base, 10, 90), white);
+
+    // without a clip box they're clipped to the safe limits
+    let mut ras = RasterizerScanline::new();
+    ren_base.clear(white);
+    let mut path = Path::new();
+    path.move_to(50.0, 50.0);
+    path.line_to(1e300, 50.0);
+    path.line_to(1e300, 60.0);
+    path.line_to(50.0, 60.0);
+    path.close_polygon();
+    fill(&mut ren_base, &mut ras, &path);
+    assert_eq!(pix(&ren_base, 99, 55), black);
+    assert_eq!(pix(&ren_base, 40, 55), white);
+
+    // a huge path is rasterized up to the safe limits, unless it's clipped,
+    // which bounds the cells to the visible part
+    let triangle = |r: f64| {
+        let mut path = Path::new();
+        path.move_to(-r, -r);
+        path.line_to(r, -r + 100.0);
+        path.line_to(r, r);
+        path.close_polygon();
+        path
+    };
+    let mut ras = RasterizerScanline::new();
+    ras.add_path(&triangle(1e5));
+    assert!(ras.memory_usage() > 64 * CELL_BLOCK_SIZE * 32);
+    let mut ras = RasterizerScanline::new();
+    ras.clip_box(0.0, 0.0, 100.0, 100.0);
+    ren_base.clear(white);
+    fill(&mut ren_base, &mut ras, &triangle(POLY_MAX_COORD));
+    assert!(!ras.cell_limit_reached());
+    assert_eq!(ras.memory_usage(), CELL_BLOCK_SIZE * 32);
+    assert_eq!((pix(&ren_base, 90, 10), pix(&ren_base, 10, 90)), (black, white));
+}
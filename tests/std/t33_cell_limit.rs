@@ -0,0 +1,74 @@
+use agrega::{
+    render_scanlines, CellOverflow, Path, PixelSource, Pixfmt, RasterizerScanline, Render,
+    RenderingBase, RenderingScanlineAASolid, Rgb8, CELL_BLOCK_SIZE, CELL_LIMIT,
+};
+
+/// A grid of 20 by 20 squares, with 16 cells each.
+fn squares() -> Path {
+    let mut path = Path::new();
+    for y in 0..20 {
+        for x in 0..20 {
+            let (x, y) = (x as f64 * 20.0, y as f64 * 20.0);
+            path.move_to(x, y);
+            path.line_to(x + 8.0, y);
+            path.line_to(x + 8.0, y + 8.0);
+            path.line_to(x, y + 8.0);
+            path.close_polygon();
+        }
+    }
+    path
+}
+
+#[test]
+fn t33_cell_limit() {
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(400, 400));
+    let pix =
+        |ren_base: &RenderingBase<Pixfmt<Rgb8>>, x, y| Rgb8::from_trait(ren_base.pixf.get((x, y)));
+    let (black, white) = (Rgb8::black(), Rgb8::white());
+    let draw = |ren_base: &mut RenderingBase<Pixfmt<Rgb8>>, ras: &mut RasterizerScanline| {
+        ren_base.clear(white);
+        let mut ren = RenderingScanlineAASolid::with_base(ren_base);
+        ren.color(black);
+        ras.add_path(&squares());
+        render_scanlines(ras, &mut ren);
+    };
+    let block = CELL_BLOCK_SIZE * 32;
+
+    // no limit is reached by default, and the memory is kept across resets
+    let mut ras = RasterizerScanline::new();
+    assert_eq!((ras.cell_limit(), ras.memory_usage()), (CELL_LIMIT, 0));
+    draw(&mut ren_base, &mut ras);
+    assert!(!ras.cell_limit_reached());
+    assert_eq!(ras.memory_usage(), 2 * block);
+    assert_eq!((pix(&ren_base, 4, 4), pix(&ren_base, 384, 384)), (black, black));
+    ras.reset();
+    assert_eq!(ras.memory_usage(), 2 * block);
+
+    // the limit is rounded up to whole blocks, and frees the unused ones
+    ras.set_cell_limit(100);
+    assert_eq!((ras.cell_limit(), ras.memory_usage()), (CELL_BLOCK_SIZE, block));
+
+    // truncated paths keep their first cells
+    draw(&mut ren_base, &mut ras);
+    assert!(ras.cell_limit_reached());
+    assert_eq!(ras.memory_usage(), block);
+    assert_eq!((pix(&ren_base, 4, 4), pix(&ren_base, 384, 384)), (black, white));
+
+    // discarded paths aren't rendered at all
+    ras.set_cell_overflow(CellOverflow::Discard);
+    draw(&mut ren_base, &mut ras);
+    assert!(ras.cell_limit_reached());
+    assert_eq!((pix(&ren_base, 4, 4), pix(&ren_base, 384, 384)), (white, white));
+    ras.reset();
+    assert!(!ras.cell_limit_reached());
+
+    // the allocation doubles as it grows, up to the limit
+    let mut ras = RasterizerScanline::new();
+    (0..4).for_each(|_| ras.add_path(&squares()));
+    assert_eq!(ras.memory_usage(), 8 * block);
+    ras.reset();
+    ras.set_cell_limit(6 * CELL_BLOCK_SIZE);
+    (0..4).for_each(|_| ras.add_path(&squares()));
+    assert!(ras.cell_limit_reached());
+    assert_eq!(ras.memory_usage(), 6 * block);
+}
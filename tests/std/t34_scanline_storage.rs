@@ -0,0 +1,61 @@
+use agrega::{
+    render_scanlines, Ellipse, Pixel, PixelSource, Pixfmt, RasterizerScanline, Render,
+    RenderingBase, RenderingScanlineAASolid, RenderingScanlineBinSolid, Rgb8, ScanlineStorage,
+};
+
+fn image() -> RenderingBase<Pixfmt<Rgb8>> {
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(60, 40));
+    ren_base.clear(Rgb8::white());
+    ren_base
+}
+
+#[test]
+fn t34_scanline_storage() {
+    let mut ras = RasterizerScanline::new();
+    ras.add_path(&Ellipse::new(10.3, 10.6, 7.5, 5.2, 40));
+    let storage = ScanlineStorage::from_rasterizer(&mut ras);
+    assert!(!storage.is_empty());
+    assert_eq!((storage.min_x(), storage.max_x()), (2, 17));
+
+    // replaying at an offset is the same as rasterizing the translated shape
+    let mut expected = image();
+    ras.add_path(&Ellipse::new(40.3, 25.6, 7.5, 5.2, 40));
+    let mut ren = RenderingScanlineAASolid::with_base(&mut expected);
+    ren.color(Rgb8::new(0, 0, 128));
+    render_scanlines(&mut ras, &mut ren);
+    assert_eq!(Rgb8::from_trait(expected.pixf.get((40, 25))), Rgb8::new(0, 0, 128));
+
+    let mut replayed = image();
+    let mut ren = RenderingScanlineAASolid::with_base(&mut replayed);
+    ren.color(Rgb8::new(0, 0, 128));
+    storage.replay(&mut ren, 30, 15);
+    assert_eq!(replayed.pixf.as_bytes(), expected.pixf.as_bytes());
+
+    // and so is replaying the deserialized scanlines
+    let bytes = storage.to_bytes();
+    assert_eq!(bytes.len(), storage.byte_size());
+    let restored = ScanlineStorage::from_bytes(&bytes).unwrap();
+    assert_eq!(restored, storage);
+    let mut ren_base = image();
+    let mut ren = RenderingScanlineAASolid::with_base(&mut ren_base);
+    ren.color(Rgb8::new(0, 0, 128));
+    restored.replay(&mut ren, 30, 15);
+    assert_eq!(ren_base.pixf.as_bytes(), expected.pixf.as_bytes());
+
+    // any renderer can replay them
+    let mut ren_base = image();
+    let mut ren = RenderingScanlineBinSolid::with_base(&mut ren_base);
+    ren.color(Rgb8::black());
+    restored.replay(&mut ren, 0, 0);
+    let pix = Rgb8::from_trait(ren_base.pixf.get((10, 10)));
+    assert_eq!(pix, Rgb8::black());
+
+    // malformed data is rejected
+    assert_eq!(ScanlineStorage::from_bytes(&[]), None);
+    assert_eq!(ScanlineStorage::from_bytes(&bytes[..bytes.len() - 1]), None);
+    let mut wrong_size = bytes.clone();
+    wrong_size[16] += 1;
+    assert_eq!(ScanlineStorage::from_bytes(&wrong_size), None);
+    let empty = ScanlineStorage::new();
+    assert_eq!(ScanlineStorage::from_bytes(&empty.to_bytes()), Some(empty));
+}
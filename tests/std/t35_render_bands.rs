@@ -0,0 +1,81 @@
+use agrega::{
+    render_all_paths, render_bands, render_scanlines, Ellipse, GradientX, Path, Pixel, Pixfmt,
+    RasterizerScanline, RenderingBase, RenderingScanlineAA, RenderingScanlineAASolid, Rgb8,
+    SpanGradient, Transform, CELL_BLOCK_SIZE, POLY_MAX_COORD,
+};
+
+fn image() -> RenderingBase<Pixfmt<Rgb8>> {
+    let mut ren_base = RenderingBase::new(Pixfmt::<Rgb8>::new(400, 301));
+    ren_base.clear(Rgb8::white());
+    ren_base
+}
+
+#[test]
+fn t35_render_bands() {
+    let (paths, colors) = super::parse_lion();
+    let mtx = Transform::new()
+        .translate(-120.0, -220.0)
+        .rotate(0.4)
+        .scale(1.3, 1.3)
+        .translate(210.0, 160.0);
+    let paths: Vec<Path> = paths.into_iter().map(|p| p.transformed(&mtx)).collect();
+    let draw = |ras: &mut RasterizerScanline, ren_base: &mut RenderingBase<Pixfmt<Rgb8>>| {
+        let mut ren = RenderingScanlineAASolid::with_base(ren_base);
+        render_all_paths(ras, &mut ren, paths.iter().zip(&colors));
+    };
+
+    let mut expected = image();
+    draw(&mut RasterizerScanline::new(), &mut expected);
+    for bands in [1, 2, 3, 7, 16, 301, 1000] {
+        let mut banded = image();
+        render_bands(&mut banded, bands, draw);
+        assert!(banded.pixf.as_bytes() == expected.pixf.as_bytes(), "{bands} bands");
+    }
+}
+
+#[test]
+fn t35_render_bands_gradient() {
+    // the gradient varies with y, which the span generator gets in absolute rows
+    let colors: Vec<Rgb8> = (0..256).map(|i| Rgb8::new(i as u8, 0, 255 - i as u8)).collect();
+    let draw = |ras: &mut RasterizerScanline, ren_base: &mut RenderingBase<Pixfmt<Rgb8>>| {
+        let span =
+            SpanGradient::new(Transform::new().rotate(0.7), GradientX {}, &colors, 0.0, 300.0);
+        let mut ren = RenderingScanlineAA::new(ren_base, span);
+        ras.add_path(&Ellipse::new(200.0, 150.0, 180.0, 130.0, 128));
+        render_scanlines(ras, &mut ren);
+    };
+
+    let mut expected = image();
+    draw(&mut RasterizerScanline::new(), &mut expected);
+    for bands in [2, 5, 64] {
+        let mut banded = image();
+        render_bands(&mut banded, bands, draw);
+        assert!(banded.pixf.as_bytes() == expected.pixf.as_bytes(), "{bands} bands");
+    }
+}
+
+#[test]
+fn t35_render_bands_clipped() {
+    // a huge path clipped to the image costs only its visible part in each band
+    let mut path = Path::new();
+    path.move_to(-POLY_MAX_COORD, -POLY_MAX_COORD);
+    path.line_to(POLY_MAX_COORD, -POLY_MAX_COORD + 300.0);
+    path.line_to(POLY_MAX_COORD, POLY_MAX_COORD);
+    path.close_polygon();
+    let draw = |ras: &mut RasterizerScanline, ren_base: &mut RenderingBase<Pixfmt<Rgb8>>| {
+        ras.clip_box(0.0, 0.0, 400.0, 301.0);
+        ras.add_path(&path);
+        assert!(!ras.cell_limit_reached() && ras.memory_usage() <= CELL_BLOCK_SIZE * 32);
+        let mut ren = RenderingScanlineAASolid::with_base(ren_base);
+        render_scanlines(ras, &mut ren);
+    };
+
+    let mut expected = image();
+    draw(&mut RasterizerScanline::new(), &mut expected);
+    assert!(expected.pixf.as_bytes().iter().any(|&v| v != 255));
+    for bands in [2, 7] {
+        let mut banded = image();
+        render_bands(&mut banded, bands, draw);
+        assert!(banded.pixf.as_bytes() == expected.pixf.as_bytes(), "{bands} bands");
+    }
+}